@@ -0,0 +1,668 @@
+use crate::domain::{
+    Category, DependencyType, FileHashes, MinecraftVersion, ModDependency, ModEnvironment, ModInfo,
+    ModLoader, ModProvider, ProjectType, ProjectVersion, ProviderKind, RateLimited, ReleaseChannel,
+    SearchSort, TransferProgress, VersionType,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::Deserialize;
+use std::sync::RwLock;
+use std::time::Duration;
+
+const API_BASE: &str = "https://api.curseforge.com/v1";
+const MINECRAFT_GAME_ID: u32 = 432;
+
+pub struct CurseForgeProvider {
+    client: RwLock<Client>,
+    api_key: RwLock<String>,
+}
+
+impl CurseForgeProvider {
+    pub fn new() -> Self {
+        Self {
+            client: RwLock::new(Client::new()),
+            api_key: RwLock::new(String::new()),
+        }
+    }
+
+    pub fn set_client(&self, client: Client) {
+        if let Ok(mut current) = self.client.write() {
+            *current = client;
+        }
+    }
+
+    fn client(&self) -> Client {
+        self.client.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn set_api_key(&self, api_key: String) {
+        if let Ok(mut key) = self.api_key.write() {
+            *key = api_key;
+        }
+    }
+
+    fn request(&self, url: &str) -> anyhow::Result<RequestBuilder> {
+        let api_key = self.api_key.read().map(|k| k.clone()).unwrap_or_default();
+
+        if api_key.is_empty() {
+            return Err(anyhow::anyhow!("CurseForge API key is not configured"));
+        }
+
+        Ok(self
+            .client()
+            .get(url)
+            .header("Accept", "application/json")
+            .header("x-api-key", api_key))
+    }
+
+    async fn send(&self, url: &str) -> anyhow::Result<Response> {
+        let response = self.request(url)?.send().await?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(Duration::from_secs);
+            return Err(RateLimited { retry_after }.into());
+        }
+
+        Ok(response.error_for_status()?)
+    }
+
+    async fn fetch_files(
+        &self,
+        mod_id: &str,
+        version: &str,
+        loader_type: Option<u32>,
+    ) -> anyhow::Result<Vec<CurseForgeFile>> {
+        let mut url = format!("{API_BASE}/mods/{mod_id}/files?pageSize=50");
+        if !version.is_empty() {
+            url.push_str(&format!("&gameVersion={}", urlencoding::encode(version)));
+        }
+        if let Some(loader_type) = loader_type {
+            url.push_str(&format!("&modLoaderType={loader_type}"));
+        }
+
+        let response: CurseForgeResponse<Vec<CurseForgeFile>> =
+            self.send(&url).await?.json().await?;
+        let mut files = response.data;
+        files.sort_by_key(|f| std::cmp::Reverse(f.file_date));
+        Ok(files)
+    }
+}
+
+#[derive(Deserialize)]
+struct CurseForgeResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeCategory {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    is_class: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeMod {
+    id: u64,
+    name: String,
+    slug: String,
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    download_count: f64,
+    #[serde(default)]
+    class_id: Option<u32>,
+    #[serde(default)]
+    authors: Vec<CurseForgeAuthor>,
+    #[serde(default)]
+    logo: Option<CurseForgeLogo>,
+    #[serde(default)]
+    latest_files_indexes: Vec<CurseForgeFileIndex>,
+}
+
+#[derive(Deserialize)]
+struct CurseForgeAuthor {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeLogo {
+    #[serde(default)]
+    thumbnail_url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFileIndex {
+    game_version: String,
+    #[serde(default)]
+    mod_loader: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFile {
+    id: u64,
+    display_name: String,
+    #[allow(dead_code)]
+    file_name: String,
+    #[serde(default)]
+    download_url: Option<String>,
+    #[serde(default)]
+    game_versions: Vec<String>,
+    file_date: DateTime<Utc>,
+    #[serde(default)]
+    dependencies: Vec<CurseForgeDependency>,
+    #[serde(default)]
+    hashes: Vec<CurseForgeHash>,
+    #[serde(default)]
+    file_length: u64,
+    #[serde(default = "default_release_type")]
+    release_type: u32,
+}
+
+fn default_release_type() -> u32 {
+    1
+}
+
+fn channel_for_release_type(release_type: u32) -> ReleaseChannel {
+    match release_type {
+        2 => ReleaseChannel::Beta,
+        3 => ReleaseChannel::Alpha,
+        _ => ReleaseChannel::Release,
+    }
+}
+
+#[derive(Deserialize)]
+struct CurseForgeHash {
+    value: String,
+    algo: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeDependency {
+    mod_id: u64,
+    relation_type: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeGameVersion {
+    version_string: String,
+}
+
+fn class_id_for_type(project_type: &ProjectType) -> u32 {
+    match project_type {
+        ProjectType::Mod => 6,
+        ProjectType::ResourcePack => 12,
+        ProjectType::Shader => 6552,
+        ProjectType::Datapack => 6945,
+        ProjectType::Plugin => 5,
+    }
+}
+
+fn type_for_class_id(class_id: Option<u32>) -> ProjectType {
+    match class_id {
+        Some(12) => ProjectType::ResourcePack,
+        Some(6552) => ProjectType::Shader,
+        Some(6945) => ProjectType::Datapack,
+        Some(5) => ProjectType::Plugin,
+        _ => ProjectType::Mod,
+    }
+}
+
+fn loader_type_for_id(loader: &str) -> Option<u32> {
+    match loader.to_lowercase().as_str() {
+        "forge" => Some(1),
+        "cauldron" => Some(2),
+        "liteloader" => Some(3),
+        "fabric" => Some(4),
+        "quilt" => Some(5),
+        "neoforge" => Some(6),
+        _ => None,
+    }
+}
+
+fn loader_id_for_type(loader_type: u32) -> Option<&'static str> {
+    match loader_type {
+        1 => Some("forge"),
+        2 => Some("cauldron"),
+        3 => Some("liteloader"),
+        4 => Some("fabric"),
+        5 => Some("quilt"),
+        6 => Some("neoforge"),
+        _ => None,
+    }
+}
+
+fn split_game_versions(tags: &[String], project_type: ProjectType) -> (Vec<String>, Vec<String>) {
+    let mut versions = Vec::new();
+    let mut loaders = Vec::new();
+
+    for tag in tags {
+        if tag.starts_with(|c: char| c.is_ascii_digit()) {
+            versions.push(tag.clone());
+        } else if project_type == ProjectType::Mod
+            && let Some(loader) = loader_type_for_id(tag).and_then(loader_id_for_type)
+        {
+            loaders.push(loader.to_string());
+        }
+    }
+
+    (versions, loaders)
+}
+
+impl CurseForgeMod {
+    fn into_mod_info(self, file: Option<&CurseForgeFile>) -> ModInfo {
+        let project_type = type_for_class_id(self.class_id);
+
+        let (supported_versions, supported_loaders) = match file {
+            Some(file) => split_game_versions(&file.game_versions, project_type),
+            None => {
+                let mut versions = Vec::new();
+                let mut loaders = Vec::new();
+                for index in &self.latest_files_indexes {
+                    if !versions.contains(&index.game_version) {
+                        versions.push(index.game_version.clone());
+                    }
+                    if project_type == ProjectType::Mod
+                        && let Some(loader) = index.mod_loader.and_then(loader_id_for_type)
+                        && !loaders.iter().any(|l| l == loader)
+                    {
+                        loaders.push(loader.to_string());
+                    }
+                }
+                (versions, loaders)
+            }
+        };
+
+        ModInfo {
+            id: self.id.to_string(),
+            slug: self.slug,
+            name: self.name,
+            icon_url: self.logo.map(|l| l.thumbnail_url).unwrap_or_default(),
+            description: self.summary,
+            version: file.map(|f| f.display_name.clone()).unwrap_or_default(),
+            version_id: file.map(|f| f.id.to_string()).unwrap_or_default(),
+            author: self
+                .authors
+                .into_iter()
+                .next()
+                .map(|a| a.name)
+                .unwrap_or_default(),
+            download_count: self.download_count.min(u32::MAX as f64) as u32,
+            download_url: file
+                .and_then(|f| f.download_url.clone())
+                .unwrap_or_default(),
+            hashes: FileHashes {
+                sha1: file
+                    .and_then(|f| f.hashes.iter().find(|h| h.algo == 1))
+                    .map(|h| h.value.clone())
+                    .unwrap_or_default(),
+                sha512: String::new(),
+            },
+            file_size: file.map(|f| f.file_length).unwrap_or_default(),
+            supported_versions,
+            supported_loaders,
+            project_type,
+            provider: ProviderKind::CurseForge,
+            // CurseForge doesn't tag projects by side
+            environment: ModEnvironment::Unknown,
+            channel: file
+                .map(|f| channel_for_release_type(f.release_type))
+                .unwrap_or_default(),
+            dependencies: file
+                .map(|f| {
+                    f.dependencies
+                        .iter()
+                        .filter_map(|d| {
+                            let dependency_type = match d.relation_type {
+                                1 => DependencyType::Embedded,
+                                2 => DependencyType::Optional,
+                                3 => DependencyType::Required,
+                                5 => DependencyType::Incompatible,
+                                _ => return None,
+                            };
+                            Some(ModDependency {
+                                mod_id: d.mod_id.to_string(),
+                                dependency_type,
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ModProvider for CurseForgeProvider {
+    async fn search_mods(
+        &self,
+        query: &str,
+        version: &str,
+        loader: &str,
+        project_type: &ProjectType,
+        categories: &[String],
+        sort: SearchSort,
+    ) -> anyhow::Result<Vec<ModInfo>> {
+        let sort_field = match sort {
+            SearchSort::Relevance => 2,
+            SearchSort::Downloads => 6,
+            SearchSort::Newest => 11,
+            SearchSort::Updated => 3,
+        };
+        let mut url = format!(
+            "{API_BASE}/mods/search?gameId={MINECRAFT_GAME_ID}&classId={}&searchFilter={}&sortField={sort_field}&sortOrder=desc&pageSize=20",
+            class_id_for_type(project_type),
+            urlencoding::encode(query)
+        );
+
+        if !version.is_empty() {
+            url.push_str(&format!("&gameVersion={}", urlencoding::encode(version)));
+        }
+        if *project_type == ProjectType::Mod
+            && let Some(loader_type) = loader_type_for_id(loader)
+        {
+            url.push_str(&format!("&modLoaderType={loader_type}"));
+        }
+        if !categories.is_empty() {
+            url.push_str(&format!(
+                "&categoryIds={}",
+                urlencoding::encode(&format!("[{}]", categories.join(",")))
+            ));
+        }
+
+        let response: CurseForgeResponse<Vec<CurseForgeMod>> =
+            self.send(&url).await?.json().await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .map(|m| m.into_mod_info(None))
+            .collect())
+    }
+
+    async fn fetch_mod_details(
+        &self,
+        mod_id: &str,
+        version: &str,
+        loader: &str,
+        channel: ReleaseChannel,
+    ) -> anyhow::Result<ModInfo> {
+        let project: CurseForgeResponse<CurseForgeMod> = self
+            .send(&format!("{API_BASE}/mods/{mod_id}"))
+            .await?
+            .json()
+            .await?;
+        let project = project.data;
+
+        let loader_type = if type_for_class_id(project.class_id) == ProjectType::Mod {
+            loader_type_for_id(loader)
+        } else {
+            None
+        };
+
+        let allowed = |files: Vec<CurseForgeFile>| -> Vec<CurseForgeFile> {
+            files
+                .into_iter()
+                .filter(|f| channel.allows(channel_for_release_type(f.release_type)))
+                .collect()
+        };
+
+        let mut files = allowed(self.fetch_files(mod_id, version, loader_type).await?);
+
+        if files.is_empty() && !version.is_empty() {
+            log::warn!(
+                "No exact match for CurseForge project {mod_id} version={version} loader={loader}. Using latest file with same loader."
+            );
+            files = allowed(self.fetch_files(mod_id, "", loader_type).await?);
+        }
+
+        let file = files
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No files available for project {mod_id}"))?;
+
+        if file.download_url.is_none() {
+            log::warn!(
+                "CurseForge project {} does not allow third-party downloads of file {}",
+                mod_id,
+                file.id
+            );
+        }
+
+        log::debug!(
+            "Selected CurseForge file '{}' for project {} (id: {})",
+            file.display_name,
+            mod_id,
+            file.id
+        );
+
+        Ok(project.into_mod_info(Some(file)))
+    }
+
+    async fn fetch_mod_version(&self, mod_id: &str, version_id: &str) -> anyhow::Result<ModInfo> {
+        let project: CurseForgeResponse<CurseForgeMod> = self
+            .send(&format!("{API_BASE}/mods/{mod_id}"))
+            .await?
+            .json()
+            .await?;
+
+        let file: CurseForgeResponse<CurseForgeFile> = self
+            .send(&format!("{API_BASE}/mods/{mod_id}/files/{version_id}"))
+            .await?
+            .json()
+            .await?;
+
+        Ok(project.data.into_mod_info(Some(&file.data)))
+    }
+
+    async fn fetch_project_versions(
+        &self,
+        mod_id: &str,
+        loader: &str,
+    ) -> anyhow::Result<Vec<ProjectVersion>> {
+        let project: CurseForgeResponse<CurseForgeMod> = self
+            .send(&format!("{API_BASE}/mods/{mod_id}"))
+            .await?
+            .json()
+            .await?;
+        let loader_type = if type_for_class_id(project.data.class_id) == ProjectType::Mod {
+            loader_type_for_id(loader)
+        } else {
+            None
+        };
+
+        Ok(self
+            .fetch_files(mod_id, "", loader_type)
+            .await?
+            .into_iter()
+            .map(|f| ProjectVersion {
+                id: f.id.to_string(),
+                name: f.display_name,
+                channel: channel_for_release_type(f.release_type),
+                // Game versions also carry loader and environment tags
+                game_versions: f
+                    .game_versions
+                    .into_iter()
+                    .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn get_minecraft_versions(&self) -> anyhow::Result<Vec<MinecraftVersion>> {
+        let response: CurseForgeResponse<Vec<CurseForgeGameVersion>> = self
+            .send(&format!("{API_BASE}/minecraft/version"))
+            .await?
+            .json()
+            .await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .map(|v| MinecraftVersion {
+                id: v.version_string.clone(),
+                name: v.version_string,
+                version_type: VersionType::Release,
+            })
+            .collect())
+    }
+
+    async fn fetch_categories(&self, project_type: ProjectType) -> anyhow::Result<Vec<Category>> {
+        let response: CurseForgeResponse<Vec<CurseForgeCategory>> = self
+            .send(&format!(
+                "{API_BASE}/categories?gameId={MINECRAFT_GAME_ID}&classId={}",
+                class_id_for_type(&project_type)
+            ))
+            .await?
+            .json()
+            .await?;
+
+        let mut categories: Vec<Category> = response
+            .data
+            .into_iter()
+            .filter(|c| !c.is_class)
+            .map(|c| Category {
+                id: c.id.to_string(),
+                name: c.name,
+            })
+            .collect();
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(categories)
+    }
+
+    async fn get_mod_loaders_for_type(
+        &self,
+        project_type: ProjectType,
+    ) -> anyhow::Result<Vec<ModLoader>> {
+        Ok(match project_type {
+            ProjectType::Mod => vec![
+                ModLoader {
+                    id: "fabric".to_string(),
+                    name: "Fabric".to_string(),
+                },
+                ModLoader {
+                    id: "forge".to_string(),
+                    name: "Forge".to_string(),
+                },
+                ModLoader {
+                    id: "neoforge".to_string(),
+                    name: "NeoForge".to_string(),
+                },
+                ModLoader {
+                    id: "quilt".to_string(),
+                    name: "Quilt".to_string(),
+                },
+                ModLoader {
+                    id: "liteloader".to_string(),
+                    name: "LiteLoader".to_string(),
+                },
+            ],
+            ProjectType::Plugin => vec![ModLoader {
+                id: "bukkit".to_string(),
+                name: "Bukkit".to_string(),
+            }],
+            _ => Vec::new(),
+        })
+    }
+
+    async fn download_mod(
+        &self,
+        download_url: &str,
+        hashes: &FileHashes,
+        destination: &std::path::Path,
+        progress_callback: Box<dyn Fn(TransferProgress) + Send>,
+    ) -> anyhow::Result<()> {
+        if download_url.is_empty() {
+            return Err(anyhow::anyhow!(
+                "This CurseForge project does not allow third-party downloads"
+            ));
+        }
+
+        let request = self.client().get(download_url);
+
+        super::write_verified_download(request, hashes, destination, progress_callback).await
+    }
+
+    async fn fetch_changelog(
+        &self,
+        mod_id: &str,
+        version_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let response: CurseForgeResponse<String> = self
+            .send(&format!(
+                "{API_BASE}/mods/{mod_id}/files/{version_id}/changelog"
+            ))
+            .await?
+            .json()
+            .await?;
+
+        let text = html_to_text(&response.data);
+        Ok((!text.is_empty()).then_some(text))
+    }
+
+    fn get_project_link(&self, _project_type: &ProjectType, mod_id: &str) -> String {
+        format!("https://www.curseforge.com/projects/{mod_id}")
+    }
+}
+
+// CurseForge changelogs are HTML, reduce them to readable plain text
+fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    let mut tag = String::new();
+
+    for c in html.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                tag.clear();
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                let name = tag
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '/')
+                    .next()
+                    .unwrap_or_default()
+                    .to_lowercase();
+                match name.as_str() {
+                    "br" | "p" | "div" | "ul" | "ol" | "h1" | "h2" | "h3" | "h4" => text.push('\n'),
+                    "li" if !tag.starts_with('/') => text.push_str("\n• "),
+                    _ => {}
+                }
+            }
+            _ if in_tag => tag.push(c),
+            _ => text.push(c),
+        }
+    }
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if line.trim().is_empty() && lines.last().is_none_or(|l| l.trim().is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
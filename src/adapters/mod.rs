@@ -1,3 +1,152 @@
+pub mod curseforge;
 pub mod modrinth;
+mod rate_limit;
 
+pub use curseforge::CurseForgeProvider;
 pub use modrinth::ModrinthProvider;
+
+use crate::domain::{FileHashes, HashMismatch, RateLimited, TransferProgress, to_hex};
+use reqwest::{RequestBuilder, StatusCode, header};
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+
+async fn write_verified_download(
+    request: RequestBuilder,
+    hashes: &FileHashes,
+    destination: &std::path::Path,
+    progress_callback: Box<dyn Fn(TransferProgress) + Send>,
+) -> anyhow::Result<()> {
+    let part_path = destination.with_file_name(format!(
+        "{}.part",
+        destination.file_name().unwrap().to_string_lossy()
+    ));
+
+    tokio::fs::create_dir_all(destination.parent().unwrap()).await?;
+
+    let result = stream_and_verify(request, hashes, &part_path, progress_callback).await;
+    if let Err(e) = result {
+        // Interrupted transfers keep their .part file so the next attempt can resume
+        if e.is::<HashMismatch>() {
+            let _ = tokio::fs::remove_file(&part_path).await;
+        }
+        return Err(e);
+    }
+
+    tokio::fs::rename(&part_path, destination).await?;
+    Ok(())
+}
+
+async fn send_download_request(
+    request: RequestBuilder,
+    resume_from: u64,
+) -> anyhow::Result<reqwest::Response> {
+    let request = if resume_from > 0 {
+        request.header(header::RANGE, format!("bytes={resume_from}-"))
+    } else {
+        request
+    };
+
+    let response = request.send().await?;
+    if response.status() == StatusCode::TOO_MANY_REQUESTS {
+        return Err(RateLimited { retry_after: None }.into());
+    }
+    Ok(response)
+}
+
+async fn stream_and_verify(
+    request: RequestBuilder,
+    hashes: &FileHashes,
+    part_path: &std::path::Path,
+    progress_callback: Box<dyn Fn(TransferProgress) + Send>,
+) -> anyhow::Result<()> {
+    use futures_util::StreamExt;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let existing = tokio::fs::metadata(part_path)
+        .await
+        .map(|m| m.len())
+        .unwrap_or(0);
+
+    let ranged = if existing > 0 {
+        request.try_clone()
+    } else {
+        None
+    };
+    let (mut response, fallback) = match ranged {
+        Some(ranged) => (
+            send_download_request(ranged, existing).await?,
+            Some(request),
+        ),
+        None => (send_download_request(request, 0).await?, None),
+    };
+
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE
+        && let Some(full_request) = fallback
+    {
+        log::warn!("Server rejected resume of {}, restarting", response.url());
+        response = send_download_request(full_request, 0).await?;
+    }
+    response = response.error_for_status()?;
+
+    let mut sha1 = Sha1::new();
+    let mut sha512 = Sha512::new();
+
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+    let mut file = if resumed {
+        let mut existing_file = tokio::fs::File::open(part_path).await?;
+        let mut buffer = vec![0u8; 64 * 1024];
+        loop {
+            let read = existing_file.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+            sha1.update(&buffer[..read]);
+            sha512.update(&buffer[..read]);
+        }
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(part_path)
+            .await?
+    } else {
+        tokio::fs::File::create(part_path).await?
+    };
+
+    let mut downloaded: u64 = if resumed { existing } else { 0 };
+    let total_size = response
+        .content_length()
+        .map_or(0, |length| length + downloaded);
+    let mut stream = response.bytes_stream();
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        sha1.update(&chunk);
+        sha512.update(&chunk);
+        downloaded += chunk.len() as u64;
+
+        progress_callback(TransferProgress {
+            downloaded,
+            total: total_size,
+        });
+    }
+
+    file.flush().await?;
+
+    let (expected, actual) = if !hashes.sha512.is_empty() {
+        (&hashes.sha512, to_hex(&sha512.finalize()))
+    } else if !hashes.sha1.is_empty() {
+        (&hashes.sha1, to_hex(&sha1.finalize()))
+    } else {
+        return Ok(());
+    };
+
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(HashMismatch {
+            expected: expected.clone(),
+            actual,
+        }
+        .into());
+    }
+
+    Ok(())
+}
@@ -1,75 +1,226 @@
-use crate::domain::{MinecraftVersion, ModInfo, ModLoader, ModProvider, ProjectType};
+use super::rate_limit::RateLimitScheduler;
+use crate::domain::{
+    Category, DEFAULT_MODRINTH_API_URL, DependencyType, FileHashes, MinecraftVersion,
+    ModDependency, ModEnvironment, ModInfo, ModLoader, ModProvider, ProjectType, ProjectVersion,
+    ProviderKind, ReleaseChannel, SearchSort, TransferProgress, VersionType, validate_api_url,
+};
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use tokio::sync::watch;
+
+const UNKNOWN_AUTHOR: &str = "Unknown author";
 
-#[derive(Clone)]
 pub struct ModrinthProvider {
-    client: Client,
+    client: RwLock<Client>,
+    base_url: RwLock<String>,
+    scheduler: RateLimitScheduler,
 }
 
 impl ModrinthProvider {
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: RwLock::new(Client::new()),
+            base_url: RwLock::new(DEFAULT_MODRINTH_API_URL.to_string()),
+            scheduler: RateLimitScheduler::new(),
+        }
+    }
+
+    pub fn set_client(&self, client: Client) {
+        if let Ok(mut current) = self.client.write() {
+            *current = client;
+        }
+    }
+
+    fn client(&self) -> Client {
+        self.client.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    // True while API requests are held back to stay within Modrinth's rate limit
+    pub fn subscribe_rate_limited(&self) -> watch::Receiver<bool> {
+        self.scheduler.subscribe()
+    }
+
+    async fn api_get(&self, url: impl reqwest::IntoUrl) -> reqwest::Result<reqwest::Response> {
+        let request = self.client().get(url);
+        self.scheduler.send(request).await
+    }
+
+    // Invalid or empty URLs fall back to the official endpoint
+    pub fn set_base_url(&self, url: &str) {
+        let url = match validate_api_url(url) {
+            Ok(url) if !url.is_empty() => url,
+            Ok(_) => DEFAULT_MODRINTH_API_URL.to_string(),
+            Err(e) => {
+                log::warn!("Ignoring Modrinth API URL {url:?}: {e}");
+                DEFAULT_MODRINTH_API_URL.to_string()
+            }
+        };
+        if let Ok(mut base_url) = self.base_url.write() {
+            *base_url = url;
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        let base_url = self
+            .base_url
+            .read()
+            .map(|url| url.clone())
+            .unwrap_or_else(|_| DEFAULT_MODRINTH_API_URL.to_string());
+        format!("{base_url}{path}")
+    }
+
+    // Collections only exist in v3, mirrors without a versioned path get the base as-is
+    fn api_v3_url(&self, path: &str) -> String {
+        let url = self.api_url(path);
+        match url.split_once("/v2/") {
+            Some((root, rest)) => format!("{root}/v3/{rest}"),
+            None => url,
         }
     }
 }
 
+/// Deserializes a field that Modrinth may send as `null`, falling back to the type's default.
+fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 #[derive(Deserialize)]
 struct ModrinthSearchResult {
+    #[serde(default, deserialize_with = "null_default")]
     hits: Vec<ModrinthProject>,
 }
 
 #[derive(Deserialize)]
 struct ModrinthProject {
     project_id: String,
+    #[serde(default, deserialize_with = "null_default")]
     slug: String,
+    #[serde(default, deserialize_with = "null_default")]
     title: String,
+    #[serde(default, deserialize_with = "null_default")]
     description: String,
+    #[serde(default, deserialize_with = "null_default")]
     author: String,
+    #[serde(default, deserialize_with = "null_default")]
     downloads: u32,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_default")]
     versions: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     icon_url: String,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_default")]
     categories: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     project_type: String,
+    #[serde(default, deserialize_with = "null_default")]
+    client_side: String,
+    #[serde(default, deserialize_with = "null_default")]
+    server_side: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize)]
 struct ModrinthProjectDetails {
     id: String,
+    #[serde(default, deserialize_with = "null_default")]
     slug: String,
+    #[serde(default, deserialize_with = "null_default")]
     title: String,
+    #[serde(default, deserialize_with = "null_default")]
     description: String,
-    #[serde(default)]
-    team: String,
+    #[serde(default, deserialize_with = "null_default")]
     downloads: u32,
     #[allow(dead_code)]
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_default")]
     versions: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     icon_url: String,
     #[allow(dead_code)]
-    #[serde(default)]
+    #[serde(default, deserialize_with = "null_default")]
     categories: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     project_type: String,
+    #[serde(default, deserialize_with = "null_default")]
+    client_side: String,
+    #[serde(default, deserialize_with = "null_default")]
+    server_side: String,
 }
 
 #[derive(Deserialize)]
 struct ModrinthVersion {
     id: String,
+    #[serde(default, deserialize_with = "null_default")]
+    project_id: String,
+    #[serde(default, deserialize_with = "null_default")]
     version_number: String,
+    #[serde(default, deserialize_with = "null_default")]
     game_versions: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     loaders: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     files: Vec<ModrinthFile>,
+    #[serde(default, deserialize_with = "null_default")]
+    dependencies: Vec<ModrinthDependency>,
+    #[serde(default, deserialize_with = "null_default")]
+    version_type: String,
+}
+
+#[derive(Deserialize)]
+struct ModrinthChangelog {
+    changelog: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthDependency {
+    project_id: Option<String>,
+    #[serde(default, deserialize_with = "null_default")]
+    dependency_type: String,
+}
+
+impl ModrinthVersion {
+    // Files without a url can't be downloaded, the first usable one is the primary file
+    fn primary_file(&self) -> Option<&ModrinthFile> {
+        self.files.iter().find(|f| !f.url.is_empty())
+    }
 }
 
 #[derive(Deserialize)]
 struct ModrinthFile {
+    #[serde(default, deserialize_with = "null_default")]
     url: String,
     #[allow(dead_code)]
+    #[serde(default, deserialize_with = "null_default")]
     filename: String,
+    #[serde(default, deserialize_with = "null_default")]
+    hashes: FileHashes,
+    #[serde(default, deserialize_with = "null_default")]
+    size: u64,
+}
+
+#[derive(Deserialize)]
+struct ModrinthCategory {
+    name: String,
+    project_type: String,
+}
+
+#[derive(Deserialize)]
+struct ModrinthCollection {
+    #[serde(default, deserialize_with = "null_default")]
+    name: String,
+    #[serde(default, deserialize_with = "null_default")]
+    projects: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ModrinthProjectSlug {
+    id: String,
+    #[serde(default, deserialize_with = "null_default")]
+    slug: String,
 }
 
 #[derive(Deserialize)]
@@ -78,6 +229,240 @@ struct ModrinthGameVersion {
     version_type: String,
 }
 
+impl ModrinthProvider {
+    pub async fn find_by_hash(&self, sha1: &str) -> anyhow::Result<Option<ModInfo>> {
+        let response = self
+            .api_get(self.api_url(&format!("/version_file/{sha1}?algorithm=sha1")))
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let version: ModrinthVersion = response.error_for_status()?.json().await?;
+        let (project, author, project_type) = self.fetch_project(&version.project_id).await?;
+
+        Ok(Some(Self::build_mod_info(
+            project,
+            author,
+            project_type,
+            &version,
+        )))
+    }
+
+    // Looks up many files at once, keyed by the sha1 of each file that Modrinth knows
+    pub async fn find_by_hashes(
+        &self,
+        sha1s: &[String],
+    ) -> anyhow::Result<HashMap<String, ModInfo>> {
+        let request = self
+            .client()
+            .post(self.api_url("/version_files"))
+            .json(&serde_json::json!({ "hashes": sha1s, "algorithm": "sha1" }));
+        let versions: HashMap<String, ModrinthVersion> = self
+            .scheduler
+            .send(request)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if versions.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut project_ids: Vec<&str> = versions.values().map(|v| v.project_id.as_str()).collect();
+        project_ids.sort_unstable();
+        project_ids.dedup();
+        let url = reqwest::Url::parse_with_params(
+            &self.api_url("/projects"),
+            [("ids", serde_json::to_string(&project_ids)?)],
+        )?;
+        let projects: Vec<ModrinthProjectDetails> =
+            self.api_get(url).await?.error_for_status()?.json().await?;
+
+        // Authors would need a request per project, they are filled in once the list loads
+        Ok(versions
+            .into_iter()
+            .filter_map(|(sha1, version)| {
+                let project = projects.iter().find(|p| p.id == version.project_id)?;
+                let info = Self::build_mod_info(
+                    project.clone(),
+                    UNKNOWN_AUTHOR.to_string(),
+                    parse_project_type(&project.project_type),
+                    &version,
+                );
+                Some((sha1, info))
+            })
+            .collect())
+    }
+
+    // Returns the collection's name and project IDs
+    pub async fn fetch_collection(&self, id: &str) -> anyhow::Result<(String, Vec<String>)> {
+        let response = self
+            .api_get(self.api_v3_url(&format!("/collection/{id}")))
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            anyhow::bail!("Collection '{id}' was not found or is private");
+        }
+
+        let collection: ModrinthCollection = response.error_for_status()?.json().await?;
+        Ok((collection.name, collection.projects))
+    }
+
+    // Maps project IDs to slugs in one request, IDs without a readable slug are kept as-is
+    pub async fn fetch_project_slugs(&self, ids: &[String]) -> anyhow::Result<Vec<String>> {
+        let url = reqwest::Url::parse_with_params(
+            &self.api_url("/projects"),
+            [("ids", serde_json::to_string(ids)?)],
+        )?;
+        let response = self.api_get(url).await?;
+        let projects: Vec<ModrinthProjectSlug> = response.error_for_status()?.json().await?;
+
+        Ok(ids
+            .iter()
+            .map(|id| {
+                projects
+                    .iter()
+                    .find(|p| &p.id == id && !p.slug.is_empty())
+                    .map(|p| p.slug.clone())
+                    .unwrap_or_else(|| id.clone())
+            })
+            .collect())
+    }
+
+    async fn fetch_project(
+        &self,
+        mod_id: &str,
+    ) -> anyhow::Result<(ModrinthProjectDetails, String, ProjectType)> {
+        let project_url = self.api_url(&format!("/project/{mod_id}"));
+        let team_url = self.api_url(&format!("/project/{mod_id}/members"));
+
+        let project_response = self.api_get(&project_url).await?;
+
+        let project_text = project_response.text().await?;
+        let project: ModrinthProjectDetails = serde_json::from_str(&project_text)
+            .map_err(|e| anyhow::anyhow!("Failed to parse project: {e}"))?;
+
+        let author = match self.api_get(&team_url).await {
+            Ok(resp) => {
+                #[derive(Deserialize)]
+                struct TeamMember {
+                    #[serde(default, deserialize_with = "null_default")]
+                    role: String,
+                    user: TeamUser,
+                }
+                #[derive(Deserialize)]
+                struct TeamUser {
+                    #[serde(default, deserialize_with = "null_default")]
+                    username: String,
+                }
+                match resp.json::<Vec<TeamMember>>().await {
+                    Ok(members) => members
+                        .into_iter()
+                        .find(|m| m.role == "Owner" && !m.user.username.is_empty())
+                        .map(|m| m.user.username),
+                    Err(_) => None,
+                }
+            }
+            Err(_) => None,
+        }
+        // The project only carries a team id, which is meaningless to show as an author.
+        .unwrap_or_else(|| UNKNOWN_AUTHOR.to_string());
+
+        let project_type = parse_project_type(&project.project_type);
+
+        Ok((project, author, project_type))
+    }
+
+    fn build_mod_info(
+        project: ModrinthProjectDetails,
+        author: String,
+        project_type: ProjectType,
+        version: &ModrinthVersion,
+    ) -> ModInfo {
+        log::debug!(
+            "Selected version '{}' for mod {} (file count: {}, id: {})",
+            version.version_number,
+            project.id,
+            version.files.len(),
+            version.id
+        );
+
+        let download_url = version
+            .primary_file()
+            .map(|f| {
+                log::debug!("Download URL: {}", f.url);
+                f.url.clone()
+            })
+            .unwrap_or_else(|| {
+                log::warn!(
+                    "No files available for mod {} version {}",
+                    project.id,
+                    version.version_number
+                );
+                String::new()
+            });
+
+        let version_number = version.version_number.clone();
+
+        let (hashes, file_size) = version
+            .primary_file()
+            .map(|f| (f.hashes.clone(), f.size))
+            .unwrap_or_default();
+
+        let dependencies = version
+            .dependencies
+            .iter()
+            .filter_map(|d| {
+                let dependency_type = match d.dependency_type.as_str() {
+                    "required" => DependencyType::Required,
+                    "optional" => DependencyType::Optional,
+                    "incompatible" => DependencyType::Incompatible,
+                    "embedded" => DependencyType::Embedded,
+                    _ => return None,
+                };
+                Some(ModDependency {
+                    mod_id: d.project_id.clone()?,
+                    dependency_type,
+                })
+            })
+            .collect();
+
+        log::debug!(
+            "Creating ModInfo: version='{}' (len={}), download_url='{}' (len={})",
+            version_number,
+            version_number.len(),
+            download_url,
+            download_url.len()
+        );
+
+        let environment = ModEnvironment::from_sides(&project.client_side, &project.server_side);
+
+        ModInfo {
+            id: project.id,
+            slug: project.slug,
+            name: project.title,
+            description: project.description,
+            version: version_number,
+            version_id: version.id.clone(),
+            author,
+            icon_url: project.icon_url,
+            download_count: project.downloads,
+            download_url,
+            hashes,
+            file_size,
+            supported_versions: version.game_versions.clone(),
+            supported_loaders: version.loaders.clone(),
+            project_type,
+            provider: ProviderKind::Modrinth,
+            dependencies,
+            channel: ReleaseChannel::from_id(&version.version_type),
+            environment,
+        }
+    }
+}
+
 fn calculate_version_distance(target: &[u32], candidate: &[u32]) -> i64 {
     let max_len = target.len().max(candidate.len());
     let mut distance: i64 = 0;
@@ -101,19 +486,35 @@ impl ModProvider for ModrinthProvider {
         version: &str,
         loader: &str,
         project_type: &ProjectType,
+        categories: &[String],
+        sort: SearchSort,
     ) -> anyhow::Result<Vec<ModInfo>> {
-        let base = format!(
-            "https://api.modrinth.com/v2/search?query={}",
+        let index = match sort {
+            SearchSort::Relevance => "relevance",
+            SearchSort::Downloads => "downloads",
+            SearchSort::Newest => "newest",
+            SearchSort::Updated => "updated",
+        };
+        let base = self.api_url(&format!(
+            "/search?query={}&index={index}",
             urlencoding::encode(query)
-        );
+        ));
 
         let mut facets = vec![format!("\"project_type:{}\"", project_type.id())];
 
         if !version.is_empty() {
             facets.push(format!("\"versions:{version}\""));
         }
-        if !loader.is_empty() && *project_type == ProjectType::Mod {
-            facets.push(format!("\"categories:{loader}\""));
+        if !loader.is_empty()
+            && matches!(
+                project_type,
+                ProjectType::Mod | ProjectType::Shader | ProjectType::Plugin
+            )
+        {
+            facets.push(format!("\"categories:{}\"", loader.to_lowercase()));
+        }
+        for category in categories {
+            facets.push(format!("\"categories:{category}\""));
         }
 
         let url = format!(
@@ -126,41 +527,46 @@ impl ModProvider for ModrinthProvider {
                 .join(",")
         );
 
-        let response: ModrinthSearchResult = self
-            .client
-            .get(&url)
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
-            .await?
-            .json()
-            .await?;
+        let response: ModrinthSearchResult = self.api_get(&url).await?.json().await?;
 
         let mods = response
             .hits
             .into_iter()
             .map(|hit| {
-                let pt = match hit.project_type.as_str() {
-                    "mod" => ProjectType::Mod,
-                    "resourcepack" => ProjectType::ResourcePack,
-                    "shader" => ProjectType::Shader,
-                    "datapack" => ProjectType::Datapack,
-                    "plugin" => ProjectType::Plugin,
-                    _ => ProjectType::Mod,
+                let pt = parse_project_type(&hit.project_type);
+                let environment = ModEnvironment::from_sides(&hit.client_side, &hit.server_side);
+
+                // A hit without a title still needs something to show
+                let name = if hit.title.is_empty() {
+                    hit.slug.clone()
+                } else {
+                    hit.title
                 };
 
                 ModInfo {
                     id: hit.project_id,
                     slug: hit.slug,
-                    name: hit.title,
+                    name,
                     description: hit.description,
                     version: String::new(),
-                    author: hit.author,
+                    author: if hit.author.is_empty() {
+                        UNKNOWN_AUTHOR.to_string()
+                    } else {
+                        hit.author
+                    },
                     icon_url: hit.icon_url,
                     download_count: hit.downloads,
                     download_url: String::new(),
+                    hashes: FileHashes::default(),
+                    file_size: 0,
+                    version_id: String::new(),
                     supported_versions: hit.versions,
                     supported_loaders: hit.categories,
                     project_type: pt,
+                    provider: ProviderKind::Modrinth,
+                    dependencies: Vec::new(),
+                    channel: ReleaseChannel::Release,
+                    environment,
                 }
             })
             .collect();
@@ -173,70 +579,17 @@ impl ModProvider for ModrinthProvider {
         mod_id: &str,
         version: &str,
         loader: &str,
+        channel: ReleaseChannel,
     ) -> anyhow::Result<ModInfo> {
-        let project_url = format!("https://api.modrinth.com/v2/project/{mod_id}");
-        let versions_url = format!("https://api.modrinth.com/v2/project/{mod_id}/version");
-        let team_url = format!("https://api.modrinth.com/v2/project/{mod_id}/members");
-
-        let project_response = self
-            .client
-            .get(&project_url)
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
-            .await?;
-
-        let project_text = project_response.text().await?;
-        let project: ModrinthProjectDetails = serde_json::from_str(&project_text)
-            .map_err(|e| anyhow::anyhow!("Failed to parse project: {e}"))?;
-
-        let author = match self
-            .client
-            .get(&team_url)
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
-            .await
-        {
-            Ok(resp) => {
-                #[derive(Deserialize)]
-                struct TeamMember {
-                    role: String,
-                    user: TeamUser,
-                }
-                #[derive(Deserialize)]
-                struct TeamUser {
-                    username: String,
-                }
-                match resp.json::<Vec<TeamMember>>().await {
-                    Ok(members) => members
-                        .into_iter()
-                        .find(|m| m.role == "Owner")
-                        .map(|m| m.user.username.clone())
-                        .unwrap_or_else(|| project.team.clone()),
-                    Err(_) => project.team.clone(),
-                }
-            }
-            Err(_) => project.team.clone(),
-        };
+        let (project, author, project_type) = self.fetch_project(mod_id).await?;
+        let versions_url = self.api_url(&format!("/project/{mod_id}/version"));
 
-        let project_type = match project.project_type.as_str() {
-            "mod" => ProjectType::Mod,
-            "resourcepack" => ProjectType::ResourcePack,
-            "shader" => ProjectType::Shader,
-            "datapack" => ProjectType::Datapack,
-            "plugin" => ProjectType::Plugin,
-            _ => ProjectType::Mod,
-        };
-
-        let versions_response = self
-            .client
-            .get(&versions_url)
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
-            .await?;
+        let versions_response = self.api_get(&versions_url).await?;
 
         let versions_text = versions_response.text().await?;
-        let versions: Vec<ModrinthVersion> = serde_json::from_str(&versions_text)
+        let mut versions: Vec<ModrinthVersion> = serde_json::from_str(&versions_text)
             .map_err(|e| anyhow::anyhow!("Failed to parse versions: {e}"))?;
+        versions.retain(|v| channel.allows(ReleaseChannel::from_id(&v.version_type)));
 
         log::debug!(
             "Mod {} has {} versions. Looking for version={} loader={}",
@@ -354,78 +707,107 @@ impl ModProvider for ModrinthProvider {
                 }
             }).ok_or_else(|| anyhow::anyhow!("No versions available for project {mod_id}"))?;
 
-        log::debug!(
-            "Selected version '{}' for mod {} (file count: {}, id: {})",
-            compatible_version.version_number,
-            mod_id,
-            compatible_version.files.len(),
-            compatible_version.id
-        );
-
-        let download_url = compatible_version
-            .files
-            .first()
-            .map(|f| {
-                log::debug!("Download URL: {}", f.url);
-                f.url.clone()
-            })
-            .unwrap_or_else(|| {
-                log::warn!(
-                    "No files available for mod {} version {}",
-                    mod_id,
-                    compatible_version.version_number
-                );
-                String::new()
-            });
+        Ok(Self::build_mod_info(
+            project,
+            author,
+            project_type,
+            compatible_version,
+        ))
+    }
 
-        let version_number = compatible_version.version_number.clone();
+    async fn fetch_mod_version(&self, mod_id: &str, version_id: &str) -> anyhow::Result<ModInfo> {
+        let (project, author, project_type) = self.fetch_project(mod_id).await?;
 
-        log::debug!(
-            "Creating ModInfo: version='{}' (len={}), download_url='{}' (len={})",
-            version_number,
-            version_number.len(),
-            download_url,
-            download_url.len()
-        );
+        let version: ModrinthVersion = self
+            .api_get(self.api_url(&format!("/version/{version_id}")))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
 
-        Ok(ModInfo {
-            id: project.id,
-            slug: project.slug,
-            name: project.title,
-            description: project.description,
-            version: version_number,
+        Ok(Self::build_mod_info(
+            project,
             author,
-            icon_url: project.icon_url,
-            download_count: project.downloads,
-            download_url,
-            supported_versions: compatible_version.game_versions.clone(),
-            supported_loaders: compatible_version.loaders.clone(),
             project_type,
-        })
+            &version,
+        ))
+    }
+
+    async fn fetch_project_versions(
+        &self,
+        mod_id: &str,
+        loader: &str,
+    ) -> anyhow::Result<Vec<ProjectVersion>> {
+        let versions: Vec<ModrinthVersion> = self
+            .api_get(self.api_url(&format!("/project/{mod_id}/version")))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(versions
+            .into_iter()
+            .filter(|v| {
+                loader.is_empty() || v.loaders.iter().any(|l| l.eq_ignore_ascii_case(loader))
+            })
+            .map(|v| ProjectVersion {
+                channel: ReleaseChannel::from_id(&v.version_type),
+                id: v.id,
+                name: v.version_number,
+                game_versions: v.game_versions,
+            })
+            .collect())
     }
 
     async fn get_minecraft_versions(&self) -> anyhow::Result<Vec<MinecraftVersion>> {
         let response: Vec<ModrinthGameVersion> = self
-            .client
-            .get("https://api.modrinth.com/v2/tag/game_version")
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
+            .api_get(self.api_url("/tag/game_version"))
             .await?
             .json()
             .await?;
 
         let versions = response
             .into_iter()
-            .filter(|v| v.version_type == "release")
-            .map(|v| MinecraftVersion {
-                id: v.version.clone(),
-                name: v.version,
+            .filter_map(|v| {
+                let version_type = match v.version_type.as_str() {
+                    "release" => VersionType::Release,
+                    "snapshot" => VersionType::Snapshot,
+                    "beta" => VersionType::Beta,
+                    "alpha" => VersionType::Alpha,
+                    _ => return None,
+                };
+                Some(MinecraftVersion {
+                    id: v.version.clone(),
+                    name: v.version,
+                    version_type,
+                })
             })
             .collect();
 
         Ok(versions)
     }
 
+    async fn fetch_categories(&self, project_type: ProjectType) -> anyhow::Result<Vec<Category>> {
+        let response: Vec<ModrinthCategory> = self
+            .api_get(self.api_url("/tag/category"))
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut categories: Vec<Category> = response
+            .into_iter()
+            .filter(|c| c.project_type == project_type.id())
+            .map(|c| Category {
+                name: capitalize(&c.name),
+                id: c.name,
+            })
+            .collect();
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(categories)
+    }
+
     async fn get_mod_loaders_for_type(
         &self,
         project_type: ProjectType,
@@ -559,42 +941,181 @@ impl ModProvider for ModrinthProvider {
     async fn download_mod(
         &self,
         download_url: &str,
+        hashes: &FileHashes,
         destination: &std::path::Path,
-        progress_callback: Box<dyn Fn(f32) + Send>,
+        progress_callback: Box<dyn Fn(TransferProgress) + Send>,
     ) -> anyhow::Result<()> {
-        let response = self
-            .client
-            .get(download_url)
-            .header("User-Agent", "MinecraftModDownloader/1.0")
-            .send()
+        let request = self.client().get(download_url);
+
+        super::write_verified_download(request, hashes, destination, progress_callback).await
+    }
+
+    async fn fetch_changelog(
+        &self,
+        _mod_id: &str,
+        version_id: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let version: ModrinthChangelog = self
+            .api_get(self.api_url(&format!("/version/{version_id}")))
+            .await?
+            .error_for_status()?
+            .json()
             .await?;
 
-        let total_size = response.content_length().unwrap_or(0);
-        let mut downloaded: u64 = 0;
-        let mut stream = response.bytes_stream();
+        Ok(version.changelog.filter(|c| !c.trim().is_empty()))
+    }
 
-        tokio::fs::create_dir_all(destination.parent().unwrap()).await?;
-        let mut file = tokio::fs::File::create(destination).await?;
+    fn get_project_link(&self, project_type: &ProjectType, mod_id: &str) -> String {
+        format!("https://modrinth.com/{}/{}", project_type.id(), mod_id)
+    }
+}
 
-        use futures_util::StreamExt;
-        use tokio::io::AsyncWriteExt;
+fn parse_project_type(project_type: &str) -> ProjectType {
+    match project_type {
+        "mod" => ProjectType::Mod,
+        "resourcepack" => ProjectType::ResourcePack,
+        "shader" => ProjectType::Shader,
+        "datapack" => ProjectType::Datapack,
+        "plugin" => ProjectType::Plugin,
+        _ => ProjectType::Mod,
+    }
+}
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk?;
-            file.write_all(&chunk).await?;
-            downloaded += chunk.len() as u64;
+fn capitalize(name: &str) -> String {
+    let name = name.replace('-', " ");
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
 
-            if total_size > 0 {
-                let progress = downloaded as f32 / total_size as f32;
-                progress_callback(progress);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Trimmed from GET /v2/search?query=sodium
+    const SEARCH_RESPONSE: &str = r#"{
+        "hits": [
+            {
+                "project_id": "AANobbMI",
+                "project_type": "mod",
+                "slug": "sodium",
+                "author": "jellysquid3",
+                "title": "Sodium",
+                "description": "The fastest rendering optimization mod for Minecraft.",
+                "categories": ["fabric", "neoforge", "optimization", "quilt"],
+                "client_side": "required",
+                "server_side": "unsupported",
+                "downloads": 71234567,
+                "icon_url": "https://cdn.modrinth.com/data/AANobbMI/icon.png",
+                "versions": ["1.21.3", "1.21.4"]
+            },
+            {
+                "project_id": "P7dR8mSH",
+                "project_type": "mod",
+                "slug": "fabric-api",
+                "author": null,
+                "description": null,
+                "categories": null,
+                "downloads": 100,
+                "icon_url": null,
+                "versions": ["1.21.4"]
             }
-        }
+        ],
+        "offset": 0,
+        "limit": 10,
+        "total_hits": 2
+    }"#;
+
+    // Trimmed from GET /v2/project/sodium, without a title
+    const PROJECT_RESPONSE: &str = r#"{
+        "id": "AANobbMI",
+        "slug": "sodium",
+        "project_type": "mod",
+        "description": "The fastest rendering optimization mod for Minecraft.",
+        "client_side": "required",
+        "server_side": "unsupported",
+        "downloads": 71234567,
+        "icon_url": null
+    }"#;
+
+    // Trimmed from GET /v2/project/sodium/version, the first file lost its url
+    const VERSION_RESPONSE: &str = r#"{
+        "id": "c3YkZvne",
+        "project_id": "AANobbMI",
+        "version_number": "mc1.21.4-0.6.6-fabric",
+        "game_versions": ["1.21.4"],
+        "version_type": "release",
+        "loaders": ["fabric", "quilt"],
+        "dependencies": [
+            {"project_id": null, "dependency_type": "required"},
+            {"project_id": "P7dR8mSH", "dependency_type": "optional"}
+        ],
+        "files": [
+            {
+                "hashes": {"sha1": "aaaa", "sha512": "bbbb"},
+                "filename": "sodium-sources.jar",
+                "primary": false,
+                "size": 10
+            },
+            {
+                "hashes": {"sha1": "cccc", "sha512": "dddd"},
+                "url": "https://cdn.modrinth.com/data/AANobbMI/versions/c3YkZvne/sodium.jar",
+                "filename": "sodium-fabric-0.6.6+mc1.21.4.jar",
+                "primary": true,
+                "size": 1064519
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn parses_search_hits_with_missing_fields() {
+        let result: ModrinthSearchResult = serde_json::from_str(SEARCH_RESPONSE).unwrap();
+        assert_eq!(result.hits.len(), 2);
+        assert_eq!(result.hits[0].title, "Sodium");
+        assert_eq!(result.hits[0].downloads, 71234567);
 
-        file.flush().await?;
-        Ok(())
+        let fabric_api = &result.hits[1];
+        assert_eq!(fabric_api.project_id, "P7dR8mSH");
+        assert!(fabric_api.title.is_empty());
+        assert!(fabric_api.author.is_empty());
+        assert!(fabric_api.categories.is_empty());
     }
 
-    fn get_project_link(&self, project_type: &ProjectType, mod_id: &str) -> String {
-        format!("https://modrinth.com/{}/{}", project_type.id(), mod_id)
+    #[test]
+    fn parses_project_without_title() {
+        let project: ModrinthProjectDetails = serde_json::from_str(PROJECT_RESPONSE).unwrap();
+        assert_eq!(project.id, "AANobbMI");
+        assert!(project.title.is_empty());
+        assert!(project.icon_url.is_empty());
+    }
+
+    #[test]
+    fn skips_files_without_url() {
+        let version: ModrinthVersion = serde_json::from_str(VERSION_RESPONSE).unwrap();
+        assert_eq!(version.files.len(), 2);
+        let primary = version.primary_file().unwrap();
+        assert_eq!(primary.hashes.sha1, "cccc");
+
+        let project: ModrinthProjectDetails = serde_json::from_str(PROJECT_RESPONSE).unwrap();
+        let info = ModrinthProvider::build_mod_info(
+            project,
+            "jellysquid3".to_string(),
+            ProjectType::Mod,
+            &version,
+        );
+        assert!(info.download_url.ends_with("/sodium.jar"));
+        assert_eq!(info.file_size, 1064519);
+        assert_eq!(info.dependencies.len(), 1);
+        assert_eq!(info.channel, ReleaseChannel::Release);
+    }
+
+    #[test]
+    fn version_without_usable_files_has_no_download() {
+        let version: ModrinthVersion =
+            serde_json::from_str(r#"{"id": "x", "files": [{"filename": "a.jar", "url": null}]}"#)
+                .unwrap();
+        assert!(version.primary_file().is_none());
     }
 }
@@ -0,0 +1,145 @@
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+// Below this many remaining requests the rest of the window is spread out evenly
+const LOW_REMAINING_THRESHOLD: u32 = 10;
+const MAX_THROTTLED_ATTEMPTS: u32 = 3;
+// Used when a 429 carries no reset information
+const DEFAULT_RESET_SECS: u64 = 10;
+
+#[derive(Default)]
+struct Budget {
+    remaining: Option<u32>,
+    reset_at: Option<Instant>,
+}
+
+// Paces requests against one API using its X-Ratelimit-* headers, shared by every caller
+pub struct RateLimitScheduler {
+    budget: Mutex<Budget>,
+    waiting: AtomicUsize,
+    waiting_tx: watch::Sender<bool>,
+}
+
+impl RateLimitScheduler {
+    pub fn new() -> Self {
+        Self {
+            budget: Mutex::new(Budget::default()),
+            waiting: AtomicUsize::new(0),
+            waiting_tx: watch::Sender::new(false),
+        }
+    }
+
+    // True while requests are held back to stay within the limit
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.waiting_tx.subscribe()
+    }
+
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut attempt = 1;
+        loop {
+            self.wait_for_budget().await;
+
+            // Requests with streaming bodies can't be repeated, those get a single try
+            let Some(retry) = request.try_clone() else {
+                let response = request.send().await?;
+                self.record(&response);
+                return Ok(response);
+            };
+
+            let response = retry.send().await?;
+            self.record(&response);
+
+            if response.status() != StatusCode::TOO_MANY_REQUESTS
+                || attempt >= MAX_THROTTLED_ATTEMPTS
+            {
+                return Ok(response);
+            }
+            log::warn!(
+                "Rate limited by {}, waiting for the limit to reset",
+                response.url().host_str().unwrap_or_default()
+            );
+            attempt += 1;
+        }
+    }
+
+    async fn wait_for_budget(&self) {
+        let Some(delay) = self.next_delay() else {
+            return;
+        };
+
+        let _waiting = WaitingGuard::new(self);
+        tokio::time::sleep(delay).await;
+    }
+
+    fn next_delay(&self) -> Option<Duration> {
+        let mut budget = self.budget.lock().unwrap();
+        let (Some(remaining), Some(reset_at)) = (budget.remaining, budget.reset_at) else {
+            return None;
+        };
+
+        let until_reset = reset_at.saturating_duration_since(Instant::now());
+        if until_reset.is_zero() {
+            *budget = Budget::default();
+            return None;
+        }
+        if remaining >= LOW_REMAINING_THRESHOLD {
+            return None;
+        }
+
+        // Claim a slot so concurrent callers queue behind each other instead of bursting
+        budget.remaining = Some(remaining.saturating_sub(1));
+        if remaining == 0 {
+            Some(until_reset)
+        } else {
+            Some(until_reset / (remaining + 1))
+        }
+    }
+
+    fn record(&self, response: &Response) {
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        let throttled = response.status() == StatusCode::TOO_MANY_REQUESTS;
+        let remaining = header("x-ratelimit-remaining").map(|v| v.min(u32::MAX as u64) as u32);
+        let reset = header("x-ratelimit-reset").or_else(|| header("retry-after"));
+
+        let mut budget = self.budget.lock().unwrap();
+        if throttled {
+            budget.remaining = Some(0);
+            budget.reset_at = Some(
+                Instant::now() + Duration::from_secs(reset.unwrap_or(DEFAULT_RESET_SECS).max(1)),
+            );
+        } else if let (Some(remaining), Some(reset)) = (remaining, reset) {
+            budget.remaining = Some(remaining);
+            budget.reset_at = Some(Instant::now() + Duration::from_secs(reset));
+        }
+    }
+}
+
+// Keeps the waiting flag raised while any caller sleeps, including ones cancelled mid-wait
+struct WaitingGuard<'a>(&'a RateLimitScheduler);
+
+impl<'a> WaitingGuard<'a> {
+    fn new(scheduler: &'a RateLimitScheduler) -> Self {
+        if scheduler.waiting.fetch_add(1, Ordering::SeqCst) == 0 {
+            scheduler.waiting_tx.send_replace(true);
+        }
+        Self(scheduler)
+    }
+}
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.waiting.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.waiting_tx.send_replace(false);
+        }
+    }
+}
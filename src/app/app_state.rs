@@ -1,30 +1,109 @@
 use crate::app::*;
 use crate::domain::*;
-use crate::infra::DownloadMetadata;
+use crate::infra::{DownloadMetadata, MrpackPart, VerifyStatus};
 use chrono::Utc;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+const COMPATIBILITY_MATRIX_DEFAULT_VERSIONS: usize = 4;
+
 pub struct AppState {
     pub minecraft_versions: Vec<MinecraftVersion>,
     pub mod_loaders: Vec<ModLoader>,
     pub mod_lists: Vec<ModList>,
     pub current_list_id: Option<String>,
+    // Last list id written to the config, to persist selection changes
+    saved_list_id: Option<String>,
+    pub window_geometry: Option<WindowGeometry>,
+    pub theme: Theme,
     pub download_progress: HashMap<String, f32>,
     pub download_status: HashMap<String, DownloadStatus>,
+    pub jar_version_mismatches: HashMap<String, String>,
+    pub pack_format_mismatches: HashMap<String, String>,
+    // Downloads skipped because an identical file was already present, by mod id
+    pub deduped_downloads: HashMap<String, String>,
     event_rx: mpsc::Receiver<Event>,
     pub search_window_results: Vec<Arc<ModInfo>>,
     pub mods_being_loaded: HashSet<String>,
     pub mods_failed_loading: HashSet<String>,
+    // Last fetch or download error per mod, kept for the diagnostics report
+    pub last_errors: HashMap<String, String>,
     pub legacy_state: LegacyState,
     pub pending_legacy_mods: Option<Vec<Arc<ModInfo>>>,
+    // Parallel to the pending legacy mods so each match can be reviewed before importing
+    pub legacy_matches: Vec<LegacyMatch>,
+    pub legacy_candidates_loading: Option<usize>,
+    legacy_import_target: (String, String),
+    pub pending_legacy_preview: Option<(String, Vec<String>)>,
+    pub download_history: Vec<DownloadRecord>,
+    pub low_disk_space: Option<LowDiskSpace>,
+    // Files removed by the last bulk delete of unknown files, and the ones that failed with why
+    pub unknown_cleanup_result: Option<(usize, Vec<String>)>,
+    pub orphan_cleanup_result: Option<(usize, Vec<String>)>,
+    pub batch_progress: Option<BatchProgress>,
+    pub changelogs: HashMap<String, Option<String>>,
+    pub changelogs_loading: HashSet<String>,
+    pub project_versions: HashMap<String, Vec<ProjectVersion>>,
+    pub project_versions_loading: HashSet<String>,
+    // One-off download choices by version id, the info is None while it loads
+    pub chosen_versions: HashMap<String, (String, Option<Arc<ModInfo>>)>,
+    pub pending_dependencies: Option<(Arc<ModInfo>, Vec<Arc<ModInfo>>)>,
+    pub alternatives: Option<Alternatives>,
+    pub verification: Option<Verification>,
+    pub lockfile_install: Option<LockfileInstall>,
+    pub bulk_add: Option<BulkAdd>,
+    pub compatibility_matrix: Option<CompatibilityMatrix>,
+    undo_stack: UndoStack,
+    retargeted_lists: HashMap<String, (String, String)>,
+    watched_dir: Option<String>,
     pub search_filter_exact: bool,
+    pub search_provider: ProviderKind,
+    pub search_categories: HashSet<String>,
+    pub search_sort: SearchSort,
+    categories_by_key: HashMap<(ProviderKind, ProjectType), Vec<Category>>,
+    categories_loading: HashSet<(ProviderKind, ProjectType)>,
     pub default_list_name: String,
+    pub curseforge_api_key: String,
+    pub modrinth_api_url: String,
+    pub user_agent_contact: String,
+    pub proxy: ProxySettings,
+    pub timeouts: NetworkTimeouts,
+    // Why the proxy settings couldn't be applied
+    pub http_client_error: Option<String>,
+    pub max_parallel_downloads: usize,
+    pub download_retries: u32,
+    pub clean_replaced_files: bool,
+    pub compat_policy: CompatPolicy,
+    pub default_dirs: HashMap<ProjectType, String>,
+    pub bundles: Vec<Bundle>,
+    // Bundle members waiting for their details, keyed like cached_mods
+    pending_bundle_downloads: HashMap<(String, String, String), BundleTarget>,
+    pub update_check_hours: u64,
+    last_update_check: Option<std::time::Instant>,
+    update_check_running: bool,
+    // Found by the background check, per list
+    available_updates: HashMap<String, HashSet<String>>,
+    pub cache_ttl: CacheTtl,
     pub initial_loading: bool,
+    pub initial_load_step: Option<InitialLoadStep>,
+    // Skip was requested; keeps the button from being pressed twice
+    pub initial_load_skipped: bool,
+    pub is_offline: bool,
+    pub is_rate_limited: bool,
+    pub dropped_files_pending: usize,
+    pub stale_mods: HashSet<String>,
     loaders_by_type: HashMap<ProjectType, Vec<ModLoader>>,
     loaders_loading: HashSet<ProjectType>,
-    pub effective_settings_cache: HashMap<String, (String, String, String)>,
+    // Pinnable loader builds by (Minecraft version, loader)
+    pub loader_versions: HashMap<(String, String), Vec<String>>,
+    loader_versions_loading: HashSet<(String, String)>,
+    effective_settings_cache: HashMap<String, (String, String, String)>,
+    // Resolved download folder per list id, dropped together with effective_settings_cache
+    list_dirs: RefCell<HashMap<String, String>>,
+    // Compatibility verdicts per (mod_id, version, loader), filled from the render path
+    compat_cache: RefCell<HashMap<(String, String, String), bool>>,
     pub cached_mods: HashMap<(String, String, String), Arc<ModInfo>>,
     metadata_cache: HashMap<String, DownloadMetadata>,
 }
@@ -36,21 +115,84 @@ impl AppState {
             mod_loaders: Vec::new(),
             mod_lists: Vec::new(),
             current_list_id: None,
+            saved_list_id: None,
+            window_geometry: None,
+            theme: Theme::default(),
             download_progress: HashMap::new(),
             download_status: HashMap::new(),
+            jar_version_mismatches: HashMap::new(),
+            pack_format_mismatches: HashMap::new(),
+            deduped_downloads: HashMap::new(),
             event_rx,
             search_window_results: Vec::new(),
             mods_being_loaded: HashSet::new(),
             mods_failed_loading: HashSet::new(),
+            last_errors: HashMap::new(),
             legacy_state: LegacyState::Idle,
             pending_legacy_mods: None,
+            legacy_matches: Vec::new(),
+            legacy_candidates_loading: None,
+            legacy_import_target: (String::new(), String::new()),
+            pending_legacy_preview: None,
+            download_history: Vec::new(),
+            low_disk_space: None,
+            unknown_cleanup_result: None,
+            orphan_cleanup_result: None,
+            batch_progress: None,
+            changelogs: HashMap::new(),
+            changelogs_loading: HashSet::new(),
+            project_versions: HashMap::new(),
+            project_versions_loading: HashSet::new(),
+            chosen_versions: HashMap::new(),
+            pending_dependencies: None,
+            alternatives: None,
+            verification: None,
+            lockfile_install: None,
+            bulk_add: None,
+            compatibility_matrix: None,
+            undo_stack: UndoStack::default(),
+            retargeted_lists: HashMap::new(),
+            watched_dir: None,
             search_filter_exact: true,
+            search_provider: ProviderKind::default(),
+            search_categories: HashSet::new(),
+            search_sort: SearchSort::default(),
+            categories_by_key: HashMap::new(),
+            categories_loading: HashSet::new(),
             default_list_name: "New List".to_string(),
+            curseforge_api_key: String::new(),
+            modrinth_api_url: String::new(),
+            user_agent_contact: String::new(),
+            proxy: ProxySettings::default(),
+            timeouts: NetworkTimeouts::default(),
+            http_client_error: None,
+            max_parallel_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+            download_retries: DEFAULT_DOWNLOAD_RETRIES,
+            clean_replaced_files: false,
+            compat_policy: CompatPolicy::default(),
+            default_dirs: HashMap::new(),
+            bundles: Vec::new(),
+            pending_bundle_downloads: HashMap::new(),
+            update_check_hours: DEFAULT_UPDATE_CHECK_HOURS,
+            last_update_check: None,
+            update_check_running: false,
+            available_updates: HashMap::new(),
+            cache_ttl: CacheTtl::default(),
 
             initial_loading: true,
+            initial_load_step: None,
+            initial_load_skipped: false,
+            is_offline: false,
+            is_rate_limited: false,
+            dropped_files_pending: 0,
+            stale_mods: HashSet::new(),
             loaders_by_type: HashMap::new(),
             loaders_loading: HashSet::new(),
+            loader_versions: HashMap::new(),
+            loader_versions_loading: HashSet::new(),
             effective_settings_cache: HashMap::new(),
+            list_dirs: RefCell::new(HashMap::new()),
+            compat_cache: RefCell::new(HashMap::new()),
             cached_mods: HashMap::new(),
             metadata_cache: HashMap::new(),
         };
@@ -71,6 +213,38 @@ impl AppState {
             })
     }
 
+    // Names the current list's other entries download to, or already did
+    pub fn taken_filenames(&self, except_mod_id: &str) -> Vec<String> {
+        let Some(list) = self.get_current_list() else {
+            return Vec::new();
+        };
+        let metadata = self.metadata_cache.get(&self.get_effective_download_dir());
+
+        let mut names = Vec::new();
+        for entry in list.mods.iter().filter(|e| e.mod_id != except_mod_id) {
+            match &entry.filename_override {
+                Some(name) => names.push(name.clone()),
+                None => {
+                    if let Some(info) = self.get_cached_mod(&entry.mod_id) {
+                        names.push(generate_mod_filename(&info));
+                    }
+                }
+            }
+            if let Some(recorded) = metadata.and_then(|m| m.get_entry(&entry.mod_id)) {
+                let file = recorded
+                    .file
+                    .strip_suffix(".archived")
+                    .unwrap_or(&recorded.file);
+                names.push(
+                    file.strip_suffix(DISABLED_SUFFIX)
+                        .unwrap_or(file)
+                        .to_string(),
+                );
+            }
+        }
+        names
+    }
+
     pub fn get_cached_mod(&self, mod_id: &str) -> Option<Arc<ModInfo>> {
         let version = self.get_effective_version();
         let loader = self.get_effective_loader();
@@ -91,6 +265,32 @@ impl AppState {
         result
     }
 
+    pub fn get_conflicts(&self) -> HashMap<String, Vec<String>> {
+        let Some(list) = self.get_current_list() else {
+            return HashMap::new();
+        };
+
+        let version = self.get_effective_version();
+        let loader = self.get_effective_loader();
+        let name_of = |mod_id: &str| {
+            list.mods
+                .iter()
+                .find(|e| e.mod_id == mod_id)
+                .map(|e| e.mod_name.clone())
+                .unwrap_or_else(|| mod_id.to_string())
+        };
+
+        let mut conflicts: HashMap<String, Vec<String>> = HashMap::new();
+        for (a, b) in list
+            .find_conflicts(|mod_id| self.get_cached_mod_with_context(mod_id, &version, &loader))
+        {
+            conflicts.entry(a.clone()).or_default().push(name_of(&b));
+            conflicts.entry(b).or_default().push(name_of(&a));
+        }
+
+        conflicts
+    }
+
     pub fn get_cached_mod_with_context(
         &self,
         mod_id: &str,
@@ -101,6 +301,51 @@ impl AppState {
         self.cached_mods.get(&key).cloned()
     }
 
+    fn cache_mod_info(&mut self, key: (String, String, String), info: Arc<ModInfo>) {
+        self.compat_cache.get_mut().remove(&key);
+        self.cached_mods.insert(key, info);
+    }
+
+    fn forget_mod_info(&mut self, key: &(String, String, String)) {
+        self.compat_cache.get_mut().remove(key);
+        self.cached_mods.remove(key);
+    }
+
+    pub fn set_compat_policy(&mut self, policy: CompatPolicy) {
+        if self.compat_policy != policy {
+            self.compat_policy = policy;
+            self.compat_cache.get_mut().clear();
+        }
+    }
+
+    // Details fetched under a longer lifetime may already be stale, so a shorter one refetches them
+    pub fn set_cache_ttl(&mut self, ttl: CacheTtl) -> Vec<Effect> {
+        let lowered = ProjectType::all()
+            .into_iter()
+            .any(|project_type| ttl.ttl_for(project_type) < self.cache_ttl.ttl_for(project_type));
+        self.cache_ttl = ttl;
+        if !lowered {
+            return Vec::new();
+        }
+
+        self.cached_mods.clear();
+        self.invalidate_and_reload()
+    }
+
+    pub fn load_loader_versions_if_needed(&mut self, version: &str, loader: &str) -> Vec<Effect> {
+        let key = (version.to_string(), loader.to_string());
+        if !has_pinnable_loader_version(loader)
+            || self.loader_versions.contains_key(&key)
+            || !self.loader_versions_loading.insert(key)
+        {
+            return Vec::new();
+        }
+        vec![Effect::LoadLoaderVersions {
+            version: version.to_string(),
+            loader: loader.to_string(),
+        }]
+    }
+
     pub fn ensure_loaders_for_type(&mut self, project_type: ProjectType) -> Vec<Effect> {
         if self.loaders_by_type.contains_key(&project_type) {
             return Vec::new();
@@ -112,33 +357,157 @@ impl AppState {
         vec![Effect::LoadLoadersForType { project_type }]
     }
 
+    pub fn load_search_categories(&mut self) -> Vec<Effect> {
+        let key = (self.search_provider, self.get_current_list_type());
+
+        if self.categories_by_key.contains_key(&key) || !self.categories_loading.insert(key) {
+            return Vec::new();
+        }
+
+        vec![Effect::LoadCategories {
+            provider: key.0,
+            project_type: key.1,
+        }]
+    }
+
+    pub fn load_changelog_if_needed(&mut self, mod_info: &ModInfo) -> Vec<Effect> {
+        let version_id = &mod_info.version_id;
+        if version_id.is_empty()
+            || self.changelogs.contains_key(version_id)
+            || !self.changelogs_loading.insert(version_id.clone())
+        {
+            return Vec::new();
+        }
+
+        vec![Effect::FetchChangelog {
+            provider: mod_info.provider,
+            mod_id: mod_info.id.clone(),
+            version_id: version_id.clone(),
+        }]
+    }
+
+    pub fn load_project_versions_if_needed(&mut self, mod_info: &ModInfo) -> Vec<Effect> {
+        if self.project_versions.contains_key(&mod_info.id)
+            || !self.project_versions_loading.insert(mod_info.id.clone())
+        {
+            return Vec::new();
+        }
+
+        vec![Effect::FetchProjectVersions {
+            provider: mod_info.provider,
+            mod_id: mod_info.id.clone(),
+            loader: self.get_effective_loader(),
+        }]
+    }
+
+    // None goes back to the automatically selected version
+    pub fn choose_download_version(
+        &mut self,
+        mod_info: &ModInfo,
+        version_id: Option<String>,
+    ) -> Vec<Effect> {
+        let Some(version_id) = version_id else {
+            self.chosen_versions.remove(&mod_info.id);
+            return Vec::new();
+        };
+
+        self.chosen_versions
+            .insert(mod_info.id.clone(), (version_id.clone(), None));
+        vec![Effect::LoadChosenVersion {
+            provider: mod_info.provider,
+            mod_id: mod_info.id.clone(),
+            version_id,
+        }]
+    }
+
+    pub fn get_search_categories(&self) -> Option<&Vec<Category>> {
+        self.categories_by_key
+            .get(&(self.search_provider, self.get_current_list_type()))
+    }
+
+    pub fn refresh_game_data(&mut self) -> Vec<Effect> {
+        let mut project_types: Vec<ProjectType> = self.loaders_by_type.keys().copied().collect();
+        if !project_types.contains(&ProjectType::Mod) {
+            project_types.push(ProjectType::Mod);
+        }
+        self.loaders_loading.extend(project_types.iter().copied());
+
+        vec![Effect::RefreshGameData { project_types }]
+    }
+
+    pub fn is_refreshing_game_data(&self) -> bool {
+        !self.loaders_loading.is_empty()
+    }
+
     pub fn is_loading_loaders_for_type(&self, project_type: ProjectType) -> bool {
         self.loaders_loading.contains(&project_type)
     }
 
+    pub fn skip_initial_load(&mut self) -> Vec<Effect> {
+        if !self.initial_loading || self.initial_load_skipped {
+            return Vec::new();
+        }
+        self.initial_load_skipped = true;
+        vec![Effect::SkipInitialLoad]
+    }
+
     pub fn process_events(&mut self) -> Vec<Effect> {
         let mut effects = Vec::new();
 
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
+                Event::InitialLoadProgress { step } => {
+                    self.initial_load_step = Some(step);
+                }
                 Event::InitialDataLoaded {
                     mod_lists,
                     current_list_id,
                     minecraft_versions,
                     mod_loaders,
                     default_list_name,
+                    curseforge_api_key,
+                    modrinth_api_url,
+                    user_agent_contact,
+                    proxy,
+                    timeouts,
+                    max_parallel_downloads,
+                    download_retries,
+                    clean_replaced_files,
+                    update_check_hours,
+                    cache_ttl,
+                    compat_policy,
+                    default_dirs,
+                    bundles,
+                    download_history,
                 } => {
+                    self.download_history = download_history;
                     self.mod_lists = mod_lists;
+                    self.saved_list_id = current_list_id.clone();
                     self.current_list_id = current_list_id;
                     self.minecraft_versions = minecraft_versions;
                     self.mod_loaders = mod_loaders.clone();
                     self.default_list_name = default_list_name;
+                    self.curseforge_api_key = curseforge_api_key;
+                    self.modrinth_api_url = modrinth_api_url;
+                    self.user_agent_contact = user_agent_contact;
+                    self.proxy = *proxy;
+                    self.timeouts = timeouts;
+                    self.max_parallel_downloads =
+                        max_parallel_downloads.clamp(1, MAX_PARALLEL_DOWNLOADS_LIMIT);
+                    self.download_retries = download_retries.min(MAX_DOWNLOAD_RETRIES);
+                    self.clean_replaced_files = clean_replaced_files;
+                    self.update_check_hours = update_check_hours.min(MAX_UPDATE_CHECK_HOURS);
+                    self.cache_ttl = *cache_ttl;
+                    self.set_compat_policy(compat_policy);
+                    self.default_dirs = default_dirs;
+                    self.bundles = bundles;
                     self.initial_loading = false;
+                    self.initial_load_step = None;
 
                     self.loaders_by_type.insert(ProjectType::Mod, mod_loaders);
                     self.loaders_loading.remove(&ProjectType::Mod);
 
-                    self.effective_settings_cache.clear();
+                    self.invalidate_effective_settings();
 
                     let download_dir = self.get_effective_download_dir();
                     effects.push(Effect::ValidateMetadata { download_dir });
@@ -146,28 +515,91 @@ impl AppState {
                     effects.extend(self.invalidate_and_reload());
                 }
 
+                Event::CategoriesLoaded {
+                    provider,
+                    project_type,
+                    categories,
+                } => {
+                    self.categories_loading.remove(&(provider, project_type));
+                    self.categories_by_key
+                        .insert((provider, project_type), categories);
+                }
+                Event::MinecraftVersionsLoaded(versions) => {
+                    self.minecraft_versions = versions;
+                }
+                Event::LoaderVersionsLoaded {
+                    version,
+                    loader,
+                    versions,
+                } => {
+                    let key = (version, loader);
+                    self.loader_versions_loading.remove(&key);
+                    self.loader_versions.insert(key, versions);
+                }
                 Event::LoadersForTypeLoaded {
                     project_type,
                     loaders,
                 } => {
+                    if project_type == ProjectType::Mod {
+                        self.mod_loaders = loaders.clone();
+                    }
                     self.loaders_by_type.insert(project_type, loaders);
                     self.loaders_loading.remove(&project_type);
                 }
+                Event::AlternativesLoaded { mod_id, results } => {
+                    let version = self.get_effective_version();
+                    let loader = self.get_effective_loader();
+                    let project_type = self.get_current_list_type();
+                    let list = self.get_current_list();
+
+                    let compatible: Vec<_> = results
+                        .into_iter()
+                        .filter(|info| info.id != mod_id)
+                        .filter(|info| !list.is_some_and(|l| l.contains_mod(&info.id)))
+                        .filter(|info| {
+                            compatibility_for(
+                                project_type,
+                                info,
+                                &version,
+                                &loader,
+                                self.compat_policy,
+                                None,
+                            )
+                        })
+                        .collect();
+
+                    for mod_info in &compatible {
+                        let key = (mod_info.id.clone(), version.clone(), loader.clone());
+                        self.cache_mod_info(key, mod_info.clone());
+                    }
+
+                    if let Some(alternatives) = &mut self.alternatives
+                        && alternatives.mod_id == mod_id
+                    {
+                        alternatives.results = Some(compatible);
+                    }
+                }
                 Event::SearchResults(results) => {
                     let version = self.get_effective_version();
                     let loader = self.get_effective_loader();
                     for mod_info in &results {
                         let key = (mod_info.id.clone(), version.clone(), loader.clone());
-                        self.cached_mods.insert(key, mod_info.clone());
+                        self.cache_mod_info(key, mod_info.clone());
                     }
                     self.search_window_results = results;
                 }
                 Event::ModDetails {
+                    requested_id,
                     info: mod_info,
                     version,
                     loader,
+                    stale,
+                    revalidating,
                 } => {
                     let mod_id = mod_info.id.clone();
+                    if requested_id != mod_id {
+                        effects.extend(self.migrate_mod_id(&requested_id, &mod_info));
+                    }
                     log::debug!(
                         "ModDetails event for {}: version='{}', download_url='{}', fetched_for=({},{})",
                         mod_id,
@@ -177,35 +609,198 @@ impl AppState {
                         loader
                     );
                     let key = (mod_id.clone(), version, loader);
-                    self.cached_mods.insert(key, mod_info);
-                    self.mods_being_loaded.remove(&mod_id);
+                    if !revalidating
+                        && let Some(target) = self.pending_bundle_downloads.remove(&(
+                            requested_id,
+                            key.1.clone(),
+                            key.2.clone(),
+                        ))
+                    {
+                        effects.push(self.bundle_download(mod_info.clone(), target));
+                    }
+                    self.cache_mod_info(key, mod_info);
+                    // Expired data is shown as-is until the refetch settles, so the row doesn't flash
+                    if !revalidating {
+                        self.mods_being_loaded.remove(&mod_id);
+                        if stale {
+                            self.stale_mods.insert(mod_id);
+                        } else {
+                            self.last_errors.remove(&mod_id);
+                            self.stale_mods.remove(&mod_id);
+                        }
+                    }
                 }
-                Event::ModDetailsFailed { mod_id } => {
+                Event::ModDetailsFailed { mod_id, error } => {
+                    self.pending_bundle_downloads
+                        .retain(|(pending_id, _, _), _| *pending_id != mod_id);
+                    self.last_errors.insert(mod_id.clone(), error);
                     self.mods_being_loaded.remove(&mod_id);
                     self.mods_failed_loading.insert(mod_id);
                 }
+                Event::CompatibilityChecked {
+                    mod_id,
+                    version,
+                    info,
+                } => {
+                    let project_type = self.get_current_list_type();
+                    if let Some(matrix) = &mut self.compatibility_matrix {
+                        let cell = match &info {
+                            Some(info)
+                                if compatibility_for(
+                                    project_type,
+                                    info,
+                                    &version,
+                                    &matrix.loader,
+                                    CompatPolicy::Exact,
+                                    None,
+                                ) =>
+                            {
+                                MatrixCell::Available(info.version.clone())
+                            }
+                            Some(_) => MatrixCell::Unavailable,
+                            None => MatrixCell::Failed,
+                        };
+                        matrix.cells.insert((mod_id.clone(), version.clone()), cell);
+
+                        if let Some(info) = info {
+                            let key = (mod_id, version, matrix.loader.clone());
+                            self.cache_mod_info(key, info);
+                        }
+                    }
+                }
+                Event::DependenciesResolved {
+                    mod_info,
+                    dependencies,
+                } => {
+                    let missing: Vec<Arc<ModInfo>> = match self.get_current_list() {
+                        Some(list) if list.contains_mod(&mod_info.id) => dependencies
+                            .into_iter()
+                            .filter(|d| !list.contains_mod(&d.id))
+                            .collect(),
+                        _ => Vec::new(),
+                    };
+
+                    if !missing.is_empty() {
+                        self.pending_dependencies = Some((mod_info, missing));
+                    }
+                }
                 Event::DownloadProgress { mod_id, progress } => {
-                    if progress > 0.0 {
+                    if progress.downloaded > 0 {
                         self.download_status
                             .insert(mod_id.clone(), DownloadStatus::Downloading);
                     }
-                    self.download_progress.insert(mod_id, progress);
+                    if let Some(batch) = &mut self.batch_progress {
+                        batch.record(&mod_id, progress);
+                    }
+                    self.download_progress.insert(mod_id, progress.fraction());
                 }
-                Event::DownloadComplete { mod_id, success } => {
+                Event::DownloadRetrying {
+                    mod_id,
+                    attempt,
+                    max_attempts,
+                } => {
                     self.download_status.insert(
                         mod_id.clone(),
-                        if success {
-                            DownloadStatus::Complete
-                        } else {
-                            DownloadStatus::Failed
+                        DownloadStatus::Retrying {
+                            attempt,
+                            max_attempts,
+                        },
+                    );
+                    self.download_progress.insert(mod_id, 0.0);
+                }
+                Event::JarVersionMismatch {
+                    mod_id,
+                    requirement,
+                } => {
+                    self.jar_version_mismatches.insert(mod_id, requirement);
+                }
+                Event::PackFormatMismatch { mod_id, declared } => {
+                    self.pack_format_mismatches.insert(mod_id, declared);
+                    self.compat_cache.get_mut().clear();
+                }
+                Event::DownloadDeduped { mod_id, file } => {
+                    self.deduped_downloads.insert(mod_id, file);
+                }
+                Event::DownloadComplete { record } => {
+                    self.download_status.insert(
+                        record.mod_id.clone(),
+                        match record.failure {
+                            None => DownloadStatus::Complete,
+                            Some(failure) => DownloadStatus::Failed(failure),
                         },
                     );
 
-                    if success {
+                    match &record.error {
+                        Some(error) => {
+                            self.last_errors
+                                .insert(record.mod_id.clone(), error.clone());
+                        }
+                        None => {
+                            self.last_errors.remove(&record.mod_id);
+                        }
+                    }
+
+                    if record.failure.is_none() {
+                        for updates in self.available_updates.values_mut() {
+                            updates.remove(&record.mod_id);
+                        }
                         let download_dir = self.get_effective_download_dir();
+                        // The first download may have just created the directory
+                        effects.push(Effect::WatchDownloadDir {
+                            download_dir: download_dir.clone(),
+                        });
                         effects.push(Effect::ValidateMetadata { download_dir });
                     }
+
+                    self.download_history.push(record.clone());
+                    effects.push(Effect::RecordDownload { record });
+
+                    if self
+                        .batch_summary()
+                        .is_some_and(|(done, total, _)| done == total)
+                    {
+                        self.batch_progress = None;
+                    }
+                }
+                Event::ChangelogLoaded {
+                    version_id,
+                    changelog,
+                } => {
+                    self.changelogs_loading.remove(&version_id);
+                    self.changelogs.insert(version_id, changelog);
+                }
+                Event::ProjectVersionsLoaded { mod_id, versions } => {
+                    if self.project_versions_loading.remove(&mod_id) {
+                        self.project_versions.insert(mod_id, versions);
+                    }
+                }
+                Event::ChosenVersionLoaded {
+                    mod_id,
+                    version_id,
+                    mod_info,
+                } => {
+                    // Ignore results for a choice that was changed or reset in the meantime
+                    if let Some((chosen_id, chosen_info)) = self.chosen_versions.get_mut(&mod_id)
+                        && *chosen_id == version_id
+                    {
+                        match mod_info {
+                            Some(info) => *chosen_info = Some(info),
+                            None => {
+                                self.chosen_versions.remove(&mod_id);
+                            }
+                        }
+                    }
                 }
+                Event::DiskSpaceChecked { mod_ids, space } => match space {
+                    Some(space) if !space.is_sufficient() => {
+                        self.low_disk_space = Some(LowDiskSpace {
+                            mod_ids,
+                            needed: space.needed,
+                            available: space.available,
+                        });
+                    }
+                    _ => effects.extend(self.start_download_batch(&mod_ids)),
+                },
                 Event::LegacyListProgress {
                     current,
                     total,
@@ -234,31 +829,180 @@ impl AppState {
                         is_import: is_importable,
                     };
                 }
+                Event::LegacyMatchesResolved { matches } => {
+                    self.legacy_matches = matches;
+                }
+                Event::LegacyCandidatesLoaded { index, candidates } => {
+                    self.legacy_candidates_loading = None;
+                    if let Some(legacy_match) = self.legacy_matches.get_mut(index) {
+                        for candidate in candidates {
+                            if !legacy_match.candidates.iter().any(|c| c.id == candidate.id) {
+                                legacy_match.candidates.push(candidate);
+                            }
+                        }
+                    }
+                }
+                Event::LegacyImportPreviewLoaded {
+                    suggested_name,
+                    entries,
+                } => {
+                    self.legacy_state = LegacyState::Idle;
+                    self.pending_legacy_preview = Some((suggested_name, entries));
+                }
                 Event::LegacyListFailed {
                     error,
                     is_import: is_importable,
-                } => {
-                    self.pending_legacy_mods = None;
-                    self.legacy_state = LegacyState::Complete {
-                        suggested_name: "".parse().unwrap(),
-                        successful: Vec::new(),
-                        failed: Vec::new(),
-                        warnings: vec![error],
-                        is_import: is_importable,
-                    };
-                }
+                } => self.report_legacy_failure(error, is_importable),
                 Event::MetadataLoaded {
                     download_dir,
                     metadata,
                 } => {
                     self.metadata_cache.insert(download_dir, metadata);
+                    self.compat_cache.get_mut().clear();
+                }
+                Event::DroppedFileProcessed {
+                    file_name,
+                    mod_info,
+                } => {
+                    self.dropped_files_pending = self.dropped_files_pending.saturating_sub(1);
+
+                    match mod_info {
+                        Some(mod_info) => {
+                            log::info!("Matched dropped file {file_name} to {}", mod_info.name);
+                            effects.extend(self.add_mod_to_current_list(mod_info));
+                        }
+                        None => log::info!("No match found for dropped file {file_name}"),
+                    }
+
+                    if self.dropped_files_pending == 0 {
+                        effects.push(Effect::ValidateMetadata {
+                            download_dir: self.get_effective_download_dir(),
+                        });
+                    }
                 }
+                Event::UpdateCheckFinished { updates } => {
+                    self.update_check_running = false;
+                    self.available_updates = updates
+                        .into_iter()
+                        .map(|(list_id, mod_ids)| (list_id, mod_ids.into_iter().collect()))
+                        .collect();
+                    effects.extend(self.reload_outdated_details());
+                }
+                Event::SlugResolved {
+                    list_id,
+                    slug,
+                    result,
+                } => {
+                    effects.extend(self.handle_slug_resolved(&list_id, slug, result));
+                }
+                Event::HttpClientConfigured { error } => {
+                    self.http_client_error = error;
+                }
+                Event::RateLimitWaiting { waiting } => {
+                    self.is_rate_limited = waiting;
+                }
+                Event::ConnectivityChanged { offline } => {
+                    self.is_offline = offline;
+                    if !offline {
+                        self.mods_failed_loading.clear();
+                    }
+                }
+                Event::LockfileProgress {
+                    current,
+                    total,
+                    message,
+                } => {
+                    if let Some(install) = &mut self.lockfile_install {
+                        install.progress = (current, total, message);
+                    }
+                }
+                Event::LockfileInstalled {
+                    installed,
+                    unchanged,
+                    errors,
+                } => {
+                    if let Some(install) = &mut self.lockfile_install {
+                        install.result = Some((installed, unchanged, errors));
+                    }
+                }
+                Event::VerifyProgress {
+                    list_id,
+                    checked,
+                    total,
+                } => {
+                    if let Some(verification) = &mut self.verification
+                        && verification.list_id == list_id
+                        && verification.results.is_none()
+                    {
+                        verification.progress = (checked, total);
+                    }
+                }
+                Event::ListVerified { list_id, results } => {
+                    if let Some(verification) = &mut self.verification
+                        && verification.list_id == list_id
+                    {
+                        // Missing files suggest an interrupted download, so the rest gets hashed too
+                        let escalate = !verification.full
+                            && results.iter().any(|r| r.status == VerifyStatus::Missing);
+                        verification.results = Some(results);
+                        if escalate {
+                            effects.extend(self.verify_current_list(true));
+                        }
+                    }
+                }
+                Event::UnknownFilesDeleted { removed, failed } => {
+                    self.unknown_cleanup_result = Some((removed, failed));
+                }
+                Event::OrphanedFilesDeleted { removed, failed } => {
+                    self.orphan_cleanup_result = Some((removed, failed));
+                }
+                Event::DownloadDirChanged { download_dir } => {
+                    effects.push(Effect::ValidateMetadata { download_dir });
+                }
+            }
+        }
+
+        if !self.initial_loading && self.saved_list_id != self.current_list_id {
+            self.saved_list_id = self.current_list_id.clone();
+            effects.push(Effect::SaveConfig {
+                config: self.app_config(),
+            });
+        }
+
+        if self.current_list_id.is_some() {
+            // Re-points the watcher when the list or its directory changes, no-op otherwise
+            let download_dir = self.get_effective_download_dir();
+            if self.watched_dir.as_ref() != Some(&download_dir) {
+                self.watched_dir = Some(download_dir.clone());
+                effects.push(Effect::WatchDownloadDir { download_dir });
             }
         }
 
         effects
     }
 
+    pub fn app_config(&self) -> AppConfig {
+        AppConfig {
+            current_list_id: self.current_list_id.clone(),
+            default_list_name: self.default_list_name.clone(),
+            curseforge_api_key: self.curseforge_api_key.clone(),
+            modrinth_api_url: self.modrinth_api_url.clone(),
+            user_agent_contact: self.user_agent_contact.clone(),
+            proxy: self.proxy.clone(),
+            timeouts: self.timeouts,
+            max_parallel_downloads: self.max_parallel_downloads,
+            download_retries: self.download_retries,
+            clean_replaced_files: self.clean_replaced_files,
+            update_check_hours: self.update_check_hours,
+            cache_ttl: self.cache_ttl,
+            compat_policy: self.compat_policy,
+            default_dirs: self.default_dirs.clone(),
+            bundles: self.bundles.clone(),
+            window: self.window_geometry,
+            theme: self.theme,
+        }
+    }
+
     pub fn get_current_list(&self) -> Option<&ModList> {
         self.current_list_id
             .as_ref()
@@ -276,15 +1020,134 @@ impl AppState {
             .and_then(|id| self.mod_lists.iter_mut().find(|l| &l.id == id))
     }
 
+    // Slugs change when a project is renamed, so entries that were saved with one switch to the stable project id
+    fn migrate_mod_id(&mut self, old_id: &str, mod_info: &ModInfo) -> Vec<Effect> {
+        self.mods_being_loaded.remove(old_id);
+        self.mods_failed_loading.remove(old_id);
+
+        let mut effects = Vec::new();
+        for list in &mut self.mod_lists {
+            let Some(index) = list
+                .mods
+                .iter()
+                .position(|e| e.mod_id == old_id && e.provider == mod_info.provider)
+            else {
+                continue;
+            };
+
+            if list.contains_mod(&mod_info.id) {
+                list.mods.remove(index);
+            } else {
+                list.mods[index].mod_id = mod_info.id.clone();
+            }
+            log::info!(
+                "Migrated {old_id} to project id {} (slug {}) in list {}",
+                mod_info.id,
+                mod_info.slug,
+                list.name
+            );
+            effects.push(Effect::SaveList { list: list.clone() });
+        }
+        effects
+    }
+
+    pub fn get_mod_provider(&self, mod_id: &str) -> ProviderKind {
+        self.get_current_list()
+            .and_then(|l| l.mods.iter().find(|e| e.mod_id == mod_id))
+            .map(|e| e.provider)
+            .unwrap_or_default()
+    }
+
+    pub fn get_pinned_version(&self, mod_id: &str) -> Option<String> {
+        self.get_current_list()
+            .and_then(|l| l.mods.iter().find(|e| e.mod_id == mod_id))
+            .and_then(|e| e.pinned_version.clone())
+    }
+
+    pub fn get_filename_override(&self, mod_id: &str) -> Option<String> {
+        self.get_current_list()
+            .and_then(|l| l.mods.iter().find(|e| e.mod_id == mod_id))
+            .and_then(|e| e.filename_override.clone())
+    }
+
+    pub fn set_filename_override(&mut self, mod_id: &str, filename: Option<String>) -> Vec<Effect> {
+        if let Some(list) = self.get_current_list_mut()
+            && let Some(entry) = list.mods.iter_mut().find(|e| e.mod_id == mod_id)
+            && entry.filename_override != filename
+        {
+            entry.filename_override = filename;
+            return vec![Effect::SaveList { list: list.clone() }];
+        }
+        Vec::new()
+    }
+
+    pub fn is_mod_disabled(&self, mod_id: &str) -> bool {
+        self.get_current_list()
+            .and_then(|l| l.mods.iter().find(|e| e.mod_id == mod_id))
+            .is_some_and(|e| e.disabled)
+    }
+
+    pub fn is_mod_locked(&self, mod_id: &str) -> bool {
+        self.get_current_list()
+            .and_then(|l| l.mods.iter().find(|e| e.mod_id == mod_id))
+            .is_some_and(|e| e.locked)
+    }
+
+    pub fn set_mod_locked(&mut self, mod_id: &str, locked: bool) -> Vec<Effect> {
+        if let Some(list) = self.get_current_list_mut()
+            && let Some(entry) = list.mods.iter_mut().find(|e| e.mod_id == mod_id)
+            && entry.locked != locked
+        {
+            entry.locked = locked;
+            return vec![Effect::SaveList { list: list.clone() }];
+        }
+        Vec::new()
+    }
+
+    // Renames the downloaded file to or from .disabled, the entry stays in the list either way
+    pub fn set_mod_enabled(&mut self, mod_id: &str, enabled: bool) -> Vec<Effect> {
+        let download_dir = self.get_effective_download_dir();
+        let Some(list) = self.get_current_list_mut() else {
+            return Vec::new();
+        };
+        let Some(entry) = list
+            .mods
+            .iter_mut()
+            .find(|e| e.mod_id == mod_id && e.disabled == enabled)
+        else {
+            return Vec::new();
+        };
+
+        entry.disabled = !enabled;
+        vec![
+            Effect::SaveList { list: list.clone() },
+            Effect::SetModFileEnabled {
+                download_dir,
+                mod_id: mod_id.to_string(),
+                enabled,
+            },
+        ]
+    }
+
     pub fn get_current_list_type(&self) -> ProjectType {
         self.get_current_list()
             .map(|l| l.content_type)
             .unwrap_or(ProjectType::Mod)
     }
 
-    fn default_version_fallback(&self) -> String {
+    pub fn selectable_versions(&self, include_snapshots: bool) -> Vec<&MinecraftVersion> {
         self.minecraft_versions
-            .first()
+            .iter()
+            .filter(|v| include_snapshots || v.is_release())
+            .collect()
+    }
+
+    pub fn latest_release(&self) -> Option<&MinecraftVersion> {
+        self.minecraft_versions.iter().find(|v| v.is_release())
+    }
+
+    fn default_version_fallback(&self) -> String {
+        self.latest_release()
             .map(|v| v.id.clone())
             .unwrap_or_default()
     }
@@ -296,7 +1159,7 @@ impl AppState {
             .unwrap_or_default()
     }
 
-    fn default_dir_fallback(&self) -> String {
+    pub fn default_dir_fallback(&self) -> String {
         dirs::download_dir()
             .unwrap_or_else(|| std::path::PathBuf::from("."))
             .join("minecraft")
@@ -304,7 +1167,22 @@ impl AppState {
             .to_string()
     }
 
-    fn compute_effective_settings_for_list(&self, list: &ModList) -> (String, String, String) {
+    // Raw folder new lists of this type start with, empty when only the built-in fallback applies
+    pub fn default_dir_for(&self, project_type: ProjectType) -> String {
+        self.default_dirs
+            .get(&project_type)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // Type defaults only apply when a list is added, so changing them never moves existing lists
+    fn fill_default_dir(&self, list: &mut ModList) {
+        if list.download_dir.trim().is_empty() {
+            list.download_dir = self.default_dir_for(list.content_type);
+        }
+    }
+
+    fn compute_effective_settings_for_list(&self, list: &ModList) -> (String, String, String) {
         let version = if list.version.is_empty() {
             self.default_version_fallback()
         } else {
@@ -317,15 +1195,38 @@ impl AppState {
             list.loader.id.clone()
         };
 
-        let dir = if list.download_dir.is_empty() {
-            self.default_dir_fallback()
-        } else {
-            list.download_dir.clone()
-        };
+        // Lists keep the raw path, placeholders are expanded for the current user here
+        let raw_dir = list.download_dir.clone();
+        let mut dir = crate::infra::resolve_path(&raw_dir)
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|e| {
+                if !raw_dir.trim().is_empty() {
+                    log::warn!(
+                        "Download directory {raw_dir} could not be expanded ({e}), \
+                         using the default"
+                    );
+                }
+                self.default_dir_fallback()
+            });
+
+        if list.server_layout
+            && let Some(folder) = list.content_type.server_folder()
+        {
+            dir = std::path::Path::new(&dir)
+                .join(folder)
+                .to_string_lossy()
+                .to_string();
+        }
 
         (version, loader, dir)
     }
 
+    // Call after anything a list's effective version, loader or folder depends on changed
+    pub fn invalidate_effective_settings(&mut self) {
+        self.effective_settings_cache.clear();
+        self.list_dirs.get_mut().clear();
+    }
+
     fn get_cached_effective_settings(&self, list_id: &str) -> Option<(String, String, String)> {
         self.effective_settings_cache.get(list_id).cloned()
     }
@@ -383,6 +1284,11 @@ impl AppState {
 
         self.mods_being_loaded.clear();
         self.mods_failed_loading.clear();
+        self.compat_cache.get_mut().clear();
+        // Version lists are filtered by loader and choices are one-off, both start over
+        self.project_versions.clear();
+        self.project_versions_loading.clear();
+        self.chosen_versions.clear();
         // Don't clear cached_mods! It holds data for multiple version/loader combinations. This allows switching between versions without refetching.
 
         let mod_ids: Vec<String> = self
@@ -426,7 +1332,8 @@ impl AppState {
         let key = (mod_id.to_string(), version.clone(), loader.clone());
 
         if let Some(info) = self.cached_mods.get(&key) {
-            if !info.version.is_empty() {
+            let refresh_stale = !self.is_offline && self.stale_mods.contains(mod_id);
+            if !info.version.is_empty() && !refresh_stale {
                 log::debug!(
                     "Mod {mod_id} has complete cached info for {version}/{loader}, skipping fetch"
                 );
@@ -441,34 +1348,358 @@ impl AppState {
         log::debug!("Triggering fetch for mod {mod_id} with version={version} loader={loader}");
 
         vec![Effect::FetchModDetails {
+            provider: self.get_mod_provider(mod_id),
             mod_id: mod_id.to_string(),
+            pinned_version: self.get_pinned_version(mod_id),
             version,
             loader,
+            channel: self.get_allowed_channel(),
+            offline: self.is_offline,
         }]
     }
 
+    pub fn get_allowed_channel(&self) -> ReleaseChannel {
+        self.get_current_list()
+            .map(|l| l.allowed_channel)
+            .unwrap_or(ReleaseChannel::Alpha)
+    }
+
+    // Drops the current list's resolved details so they are fetched again with new settings
+    pub fn forget_current_details(&mut self) {
+        let version = self.get_effective_version();
+        let loader = self.get_effective_loader();
+        let mod_ids: Vec<String> = self
+            .get_current_list()
+            .map(|l| l.mods.iter().map(|e| e.mod_id.clone()).collect())
+            .unwrap_or_default();
+
+        for mod_id in mod_ids {
+            self.forget_mod_info(&(mod_id, version.clone(), loader.clone()));
+        }
+    }
+
     pub fn force_reload_mod(&mut self, mod_id: &str) -> Vec<Effect> {
         self.mods_failed_loading.remove(mod_id);
         self.mods_being_loaded.remove(mod_id);
         self.load_mod_details_if_needed(mod_id)
     }
 
+    pub fn match_dropped_files(&mut self, paths: Vec<std::path::PathBuf>) -> Vec<Effect> {
+        if paths.is_empty() || self.current_list_id.is_none() {
+            return Vec::new();
+        }
+
+        self.dropped_files_pending += paths.len();
+
+        vec![Effect::MatchDroppedFiles {
+            paths,
+            download_dir: self.get_effective_download_dir(),
+        }]
+    }
+
     pub fn start_download(&mut self, mod_id: &str) -> Vec<Effect> {
-        self.download_status
-            .insert(mod_id.to_string(), DownloadStatus::Queued);
-        self.download_progress.insert(mod_id.to_string(), 0.0);
+        if self.is_offline {
+            return Vec::new();
+        }
 
-        if let Some(mod_info) = self.get_cached_mod(mod_id) {
+        self.jar_version_mismatches.remove(mod_id);
+        if self.pack_format_mismatches.remove(mod_id).is_some() {
+            self.compat_cache.get_mut().clear();
+        }
+        self.deduped_downloads.remove(mod_id);
+
+        let chosen = self
+            .chosen_versions
+            .get(mod_id)
+            .and_then(|(_, info)| info.clone());
+        if let Some(mod_info) = chosen.or_else(|| self.get_cached_mod(mod_id)) {
+            // Only marked queued when a download is really started, quitting waits on these
+            self.download_status
+                .insert(mod_id.to_string(), DownloadStatus::Queued);
+            self.download_progress.insert(mod_id.to_string(), 0.0);
             return vec![Effect::DownloadMod {
                 mod_info,
+                filename_override: self.get_filename_override(mod_id),
+                disabled: self.is_mod_disabled(mod_id),
                 download_dir: self.get_effective_download_dir(),
+                list_id: self.current_list_id.clone(),
+                game_version: self.get_effective_version(),
+                max_retries: self.download_retries,
+                clean_replaced: self.clean_replaced_files,
             }];
         }
 
         Vec::new()
     }
 
-    pub fn perform_search(&self, query: &str) -> Vec<Effect> {
+    pub fn active_download_count(&self) -> usize {
+        self.download_status
+            .values()
+            .filter(|status| {
+                matches!(
+                    status,
+                    DownloadStatus::Queued
+                        | DownloadStatus::Downloading
+                        | DownloadStatus::Retrying { .. }
+                )
+            })
+            .count()
+    }
+
+    // Checks free space on the download volume before queueing several downloads at once
+    pub fn start_bulk_download(&mut self, mod_ids: Vec<String>) -> Vec<Effect> {
+        if self.is_offline || mod_ids.is_empty() {
+            return Vec::new();
+        }
+
+        let needed: u64 = mod_ids
+            .iter()
+            .filter_map(|id| self.get_cached_mod(id))
+            .map(|info| info.file_size)
+            .sum();
+
+        if needed == 0 {
+            return self.start_download_batch(&mod_ids);
+        }
+
+        vec![Effect::CheckDiskSpace {
+            download_dir: self.get_effective_download_dir(),
+            needed,
+            mod_ids,
+        }]
+    }
+
+    // Starts the downloads and tracks them as one batch, joining a batch that's still running
+    pub fn start_download_batch(&mut self, mod_ids: &[String]) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let mut sizes = HashMap::new();
+        for id in mod_ids {
+            let started = self.start_download(id);
+            // Skipped items never report back, tracking them would keep the batch open
+            if started
+                .iter()
+                .any(|effect| matches!(effect, Effect::DownloadMod { .. }))
+            {
+                let size = self.get_cached_mod(id).map_or(0, |info| info.file_size);
+                sizes.insert(id.clone(), size);
+            }
+            effects.extend(started);
+        }
+
+        if !sizes.is_empty() {
+            self.batch_progress
+                .get_or_insert_with(BatchProgress::default)
+                .sizes
+                .extend(sizes);
+        } else if self
+            .batch_progress
+            .as_ref()
+            .is_some_and(|batch| batch.sizes.is_empty())
+        {
+            self.batch_progress = None;
+        }
+
+        effects
+    }
+
+    // Returns (finished, total, overall fraction) of the running batch, failures count as finished
+    pub fn batch_summary(&self) -> Option<(usize, usize, f32)> {
+        let batch = self.batch_progress.as_ref()?;
+
+        let mut finished = 0;
+        let mut total_bytes = 0u64;
+        let mut done_bytes = 0.0f64;
+        let mut done_fraction = 0.0f64;
+        for (mod_id, &size) in &batch.sizes {
+            let progress = match self.download_status.get(mod_id) {
+                Some(
+                    DownloadStatus::Queued
+                    | DownloadStatus::Downloading
+                    | DownloadStatus::Retrying { .. },
+                ) => self.download_progress.get(mod_id).copied().unwrap_or(0.0) as f64,
+                // Finished, failed, or removed from the list in the meantime
+                _ => {
+                    finished += 1;
+                    1.0
+                }
+            };
+            total_bytes += size;
+            done_bytes += progress * size as f64;
+            done_fraction += progress;
+        }
+
+        let total = batch.sizes.len();
+        // Falls back to counting items when sizes aren't known
+        let fraction = if total_bytes > 0 {
+            done_bytes / total_bytes as f64
+        } else {
+            done_fraction / total.max(1) as f64
+        };
+        Some((finished, total, fraction as f32))
+    }
+
+    // Time left for the running batch at the recent transfer speed
+    pub fn batch_eta(&self) -> Option<std::time::Duration> {
+        let batch = self.batch_progress.as_ref()?;
+        let speed = batch.speed()?;
+
+        let mut remaining = 0.0f64;
+        for (mod_id, &size) in &batch.sizes {
+            if !matches!(
+                self.download_status.get(mod_id),
+                Some(
+                    DownloadStatus::Queued
+                        | DownloadStatus::Downloading
+                        | DownloadStatus::Retrying { .. }
+                )
+            ) {
+                continue;
+            }
+            // An unknown size would make the estimate meaningless
+            if size == 0 {
+                return None;
+            }
+            let progress = self.download_progress.get(mod_id).copied().unwrap_or(0.0) as f64;
+            remaining += size as f64 * (1.0 - progress.min(1.0));
+        }
+        Some(std::time::Duration::from_secs_f64(remaining / speed))
+    }
+
+    pub fn find_alternatives(&mut self, entry: &ModEntry) -> Vec<Effect> {
+        let project_type = self.get_current_list_type();
+        let uses_loader = matches!(
+            project_type,
+            ProjectType::Mod | ProjectType::Shader | ProjectType::Plugin
+        );
+
+        self.alternatives = Some(Alternatives {
+            mod_id: entry.mod_id.clone(),
+            mod_name: entry.mod_name.clone(),
+            results: None,
+        });
+
+        vec![Effect::FindAlternatives {
+            mod_id: entry.mod_id.clone(),
+            query: entry.mod_name.clone(),
+            version: self.get_effective_version(),
+            loader: if uses_loader {
+                self.get_effective_loader()
+            } else {
+                String::new()
+            },
+            project_type,
+            provider: entry.provider,
+        }]
+    }
+
+    pub fn verify_current_list(&mut self, full: bool) -> Vec<Effect> {
+        let Some(list) = self.get_current_list().cloned() else {
+            return Vec::new();
+        };
+
+        self.verification = Some(Verification {
+            list_id: list.id.clone(),
+            full,
+            progress: (0, 0),
+            results: None,
+        });
+
+        vec![Effect::VerifyList {
+            list,
+            download_dir: self.get_effective_download_dir(),
+            full,
+        }]
+    }
+
+    // Starts with the list's own version and the newest releases as upgrade candidates
+    pub fn open_compatibility_matrix(&mut self) -> Vec<Effect> {
+        let Some(list_id) = self.current_list_id.clone() else {
+            return Vec::new();
+        };
+
+        let current = self.get_effective_version();
+        let mut versions: Vec<String> = self
+            .selectable_versions(false)
+            .into_iter()
+            .take(COMPATIBILITY_MATRIX_DEFAULT_VERSIONS)
+            .map(|v| v.id.clone())
+            .collect();
+        if !versions.contains(&current) {
+            versions.push(current);
+        }
+
+        self.compatibility_matrix = Some(CompatibilityMatrix {
+            list_id,
+            loader: self.get_effective_loader(),
+            channel: self.get_allowed_channel(),
+            versions,
+            cells: HashMap::new(),
+        });
+
+        self.check_compatibility()
+    }
+
+    // Queries only the cells that have no result yet
+    pub fn check_compatibility(&mut self) -> Vec<Effect> {
+        if self.is_offline {
+            return Vec::new();
+        }
+
+        let Some(list) = self.get_current_list() else {
+            return Vec::new();
+        };
+        let mods: Vec<(ProviderKind, String)> = list
+            .mods
+            .iter()
+            .filter(|e| !e.archived)
+            .map(|e| (e.provider, e.mod_id.clone()))
+            .collect();
+
+        let Some(matrix) = &mut self.compatibility_matrix else {
+            return Vec::new();
+        };
+
+        let mut cells = Vec::new();
+        for version in &matrix.versions {
+            for (provider, mod_id) in &mods {
+                let key = (mod_id.clone(), version.clone());
+                if let std::collections::hash_map::Entry::Vacant(cell) = matrix.cells.entry(key) {
+                    cell.insert(MatrixCell::Loading);
+                    cells.push((*provider, mod_id.clone(), version.clone()));
+                }
+            }
+        }
+
+        if cells.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Effect::CheckCompatibility {
+            cells,
+            loader: matrix.loader.clone(),
+            channel: matrix.channel,
+        }]
+    }
+
+    // Adds the replacement where the old entry was and drops the old one, even if other
+    // entries depended on it since the replacement takes over that role
+    pub fn replace_mod(&mut self, old_id: &str, replacement: Arc<ModInfo>) -> Vec<Effect> {
+        let new_id = replacement.id.clone();
+        let mut effects = self.add_mod_to_current_list(replacement);
+        self.move_mod(&new_id, old_id);
+        effects.extend(self.remove_mod_entry(old_id));
+
+        // Only the final state of the list should be written
+        effects.retain(|e| !matches!(e, Effect::SaveList { .. }));
+        if let Some(list) = self.get_current_list() {
+            effects.insert(0, Effect::SaveList { list: list.clone() });
+        }
+
+        effects
+    }
+
+    // A forced refresh bypasses the cached results of an identical search
+    pub fn perform_search(&self, query: &str, force_refresh: bool) -> Vec<Effect> {
         if query.is_empty() {
             return Vec::new();
         }
@@ -482,46 +1713,108 @@ impl AppState {
             } else {
                 String::new()
             },
-            loader: if self.search_filter_exact
-                && (current_type == ProjectType::Mod
-                    || current_type == ProjectType::Shader
-                    || current_type == ProjectType::Plugin)
-            {
+            loader: if self.search_filter_exact && current_type.has_loader() {
                 self.get_effective_loader()
             } else {
                 String::new()
             },
             project_type: current_type,
+            provider: self.search_provider,
+            categories: self
+                .search_categories
+                .iter()
+                .filter(|id| {
+                    self.get_search_categories()
+                        .is_none_or(|known| known.iter().any(|c| &c.id == *id))
+                })
+                .cloned()
+                .collect(),
+            sort: self.search_sort,
+            force_refresh,
         }]
     }
 
     pub fn add_mod_to_current_list(&mut self, mod_info: Arc<ModInfo>) -> Vec<Effect> {
-        let mut list_to_save = None;
+        let mut effects = self.add_mod_with_dependencies(mod_info.clone(), Vec::new());
 
-        if let Some(current_list) = self.get_current_list_mut()
-            && !current_list.mods.iter().any(|e| e.mod_id == mod_info.id)
-        {
-            current_list.mods.push(ModEntry {
-                mod_id: mod_info.id.clone(),
-                mod_name: mod_info.name.clone(),
-                added_at: Utc::now(),
-                archived: false,
-                compatibility_override: false,
+        if !effects.is_empty() {
+            let known_ids = self
+                .get_current_list()
+                .map(|l| l.mods.iter().map(|e| e.mod_id.clone()).collect())
+                .unwrap_or_default();
+
+            effects.push(Effect::ResolveDependencies {
+                mod_info,
+                version: self.get_effective_version(),
+                loader: self.get_effective_loader(),
+                channel: self.get_allowed_channel(),
+                known_ids,
             });
-            list_to_save = Some(current_list.clone());
         }
 
-        if list_to_save.is_some() {
+        effects
+    }
+
+    pub fn add_mod_with_dependencies(
+        &mut self,
+        mod_info: Arc<ModInfo>,
+        dependencies: Vec<Arc<ModInfo>>,
+    ) -> Vec<Effect> {
+        let mut list_to_save = None;
+        let mut added = Vec::new();
+
+        if let Some(current_list) = self.get_current_list_mut() {
+            added = current_list.add_with_dependencies(&mod_info, &dependencies);
+            if !added.is_empty() {
+                list_to_save = Some(current_list.clone());
+            }
+        }
+
+        let mut effects = Vec::new();
+        for mod_id in &added {
             self.download_status
-                .insert(mod_info.id.clone(), DownloadStatus::Idle);
+                .insert(mod_id.clone(), DownloadStatus::Idle);
+            effects.extend(self.load_mod_details_if_needed(mod_id));
         }
 
-        list_to_save
-            .map(|list| vec![Effect::SaveList { list }])
-            .unwrap_or_default()
+        if let Some(list) = list_to_save {
+            effects.insert(0, Effect::SaveList { list });
+        }
+
+        effects
     }
 
     pub fn delete_mod(&mut self, mod_id: &str) -> Vec<Effect> {
+        if self.has_dependents(mod_id) {
+            return Vec::new();
+        }
+
+        self.record_removed_entries(&[mod_id.to_string()]);
+        self.remove_mod_entry(mod_id)
+    }
+
+    fn record_removed_entries(&mut self, mod_ids: &[String]) {
+        let Some(list) = self.get_current_list() else {
+            return;
+        };
+
+        let entries: Vec<(usize, ModEntry)> = list
+            .mods
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| mod_ids.contains(&e.mod_id))
+            .map(|(index, e)| (index, e.clone()))
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+
+        let list_id = list.id.clone();
+        self.undo_stack
+            .push(UndoAction::RestoreEntries { list_id, entries });
+    }
+
+    fn remove_mod_entry(&mut self, mod_id: &str) -> Vec<Effect> {
         let mut effects = Vec::new();
 
         let download_dir = self.get_effective_download_dir();
@@ -538,10 +1831,6 @@ impl AppState {
         self.download_progress.remove(mod_id);
         self.download_status.remove(mod_id);
 
-        effects.push(Effect::RemoveFromMetadata {
-            download_dir: download_dir.clone(),
-            mod_id: mod_id.to_string(),
-        });
         effects.push(Effect::DeleteModFile {
             download_dir: download_dir.clone(),
             mod_id: mod_id.to_string(),
@@ -550,119 +1839,870 @@ impl AppState {
         effects
     }
 
-    pub fn toggle_archive_mod(&mut self, mod_id: &str) -> Vec<Effect> {
+    // The part of a selection that can go: locked entries stay, and so does anything an entry
+    // that stays still requires, repeated until a kept entry no longer protects another
+    pub fn deletable_mods(&self, mod_ids: &[String]) -> HashSet<String> {
+        let mut deletable: HashSet<String> = mod_ids
+            .iter()
+            .filter(|id| !self.is_mod_locked(id))
+            .cloned()
+            .collect();
+
+        loop {
+            let protected: Vec<String> = deletable
+                .iter()
+                .filter(|id| {
+                    self.get_dependents(id)
+                        .iter()
+                        .any(|(dependent_id, _)| !deletable.contains(dependent_id))
+                })
+                .cloned()
+                .collect();
+            if protected.is_empty() {
+                return deletable;
+            }
+            for id in protected {
+                deletable.remove(&id);
+            }
+        }
+    }
+
+    // Locked entries are skipped, they can only be deleted one at a time
+    pub fn delete_mods(&mut self, mod_ids: &[String]) -> Vec<Effect> {
+        let deletable = self.deletable_mods(mod_ids);
+
+        if deletable.is_empty() {
+            return Vec::new();
+        }
+
+        self.record_removed_entries(&deletable.iter().cloned().collect::<Vec<_>>());
+
+        let mut effects = Vec::new();
         let download_dir = self.get_effective_download_dir();
 
-        if let Some(list) = self.get_current_list_mut()
-            && let Some(entry) = list.mods.iter_mut().find(|e| e.mod_id == mod_id)
-        {
-            entry.archived = !entry.archived;
-            let is_archived = entry.archived;
+        if let Some(current_list) = self.get_current_list_mut() {
+            current_list.mods.retain(|e| !deletable.contains(&e.mod_id));
+            effects.push(Effect::SaveList {
+                list: current_list.clone(),
+            });
+        }
 
-            let list_clone = list.clone();
-            let mut effects = vec![Effect::SaveList { list: list_clone }];
+        for mod_id in deletable {
+            self.mods_being_loaded.remove(&mod_id);
+            self.mods_failed_loading.remove(&mod_id);
+            self.download_progress.remove(&mod_id);
+            self.download_status.remove(&mod_id);
 
-            if is_archived {
-                effects.push(Effect::ArchiveModFile {
-                    download_dir,
-                    mod_id: mod_id.to_string(),
-                });
-            } else {
-                effects.push(Effect::UnarchiveModFile {
-                    download_dir,
-                    mod_id: mod_id.to_string(),
-                });
-                return effects;
-            }
+            effects.push(Effect::DeleteModFile {
+                download_dir: download_dir.clone(),
+                mod_id,
+            });
         }
-        Vec::new()
+
+        effects
     }
 
-    pub fn toggle_compatibility_override(&mut self, mod_id: &str) -> Vec<Effect> {
-        if let Some(list) = self.get_current_list_mut()
-            && let Some(entry) = list.mods.iter_mut().find(|e| e.mod_id == mod_id)
-        {
-            entry.compatibility_override = !entry.compatibility_override;
-            return vec![Effect::SaveList { list: list.clone() }];
-        }
-        Vec::new()
+    // Returns (mod_id, mod_name) of active list entries that require the given mod
+    pub fn get_dependents(&self, mod_id: &str) -> Vec<(String, String)> {
+        let Some(list) = self.get_current_list() else {
+            return Vec::new();
+        };
+
+        list.mods
+            .iter()
+            .filter(|e| !e.archived && e.mod_id != mod_id)
+            .filter(|e| {
+                self.get_cached_mod(&e.mod_id).is_some_and(|info| {
+                    info.dependencies.iter().any(|d| {
+                        d.mod_id == mod_id && d.dependency_type == DependencyType::Required
+                    })
+                })
+            })
+            .map(|e| (e.mod_id.clone(), e.mod_name.clone()))
+            .collect()
     }
 
-    pub fn has_compatibility_override(&self, mod_id: &str) -> bool {
-        if let Some(list) = self.get_current_list()
-            && let Some(entry) = list.mods.iter().find(|e| e.mod_id == mod_id)
-        {
-            return entry.compatibility_override;
-        }
-        false
+    pub fn has_dependents(&self, mod_id: &str) -> bool {
+        !self.get_dependents(mod_id).is_empty()
     }
 
-    pub fn delete_current_list(&mut self) -> Vec<Effect> {
-        if let Some(list_id) = self.current_list_id.clone() {
-            self.mod_lists.retain(|l| l.id != list_id);
-            self.current_list_id = None;
-            return vec![Effect::DeleteList { list_id }];
+    pub fn list_stats(&self) -> ListStats {
+        let mut stats = ListStats::default();
+        let Some(list) = self.get_current_list() else {
+            return stats;
+        };
+
+        for entry in list.mods.iter().filter(|e| !e.archived) {
+            stats.active += 1;
+
+            if self.is_mod_downloaded(&entry.mod_id) {
+                stats.downloaded += 1;
+            } else {
+                stats.missing += 1;
+            }
+
+            let Some(info) = self.get_cached_mod(&entry.mod_id) else {
+                stats.unknown += 1;
+                continue;
+            };
+            stats.total_downloads += u64::from(info.download_count);
+
+            match self.is_mod_compatible(&entry.mod_id) {
+                Some(false) => stats.incompatible += 1,
+                _ => stats.compatible += 1,
+            }
         }
-        Vec::new()
+
+        stats
+    }
+
+    pub fn move_mod(&mut self, mod_id: &str, target_id: &str) -> Vec<Effect> {
+        let Some(list) = self.get_current_list_mut() else {
+            return Vec::new();
+        };
+
+        let Some(from) = list.mods.iter().position(|e| e.mod_id == mod_id) else {
+            return Vec::new();
+        };
+        let Some(to) = list.mods.iter().position(|e| e.mod_id == target_id) else {
+            return Vec::new();
+        };
+        if from == to {
+            return Vec::new();
+        }
+
+        let entry = list.mods.remove(from);
+        list.mods.insert(to, entry);
+
+        vec![Effect::SaveList { list: list.clone() }]
+    }
+
+    pub fn set_mods_archived(&mut self, mod_ids: &[String], archived: bool) -> Vec<Effect> {
+        let (effects, changed) = self.apply_archived(mod_ids, archived);
+
+        if !changed.is_empty()
+            && let Some(list_id) = self.current_list_id.clone()
+        {
+            self.undo_stack.push(UndoAction::SetArchived {
+                list_id,
+                mod_ids: changed,
+                archived: !archived,
+            });
+        }
+
+        effects
+    }
+
+    // Returns the effects and the ids of entries that actually changed
+    fn apply_archived(&mut self, mod_ids: &[String], archived: bool) -> (Vec<Effect>, Vec<String>) {
+        let download_dir = self.get_effective_download_dir();
+
+        let Some(list) = self.get_current_list_mut() else {
+            return (Vec::new(), Vec::new());
+        };
+
+        let mut changed = Vec::new();
+        for entry in list
+            .mods
+            .iter_mut()
+            .filter(|e| e.archived != archived && mod_ids.contains(&e.mod_id))
+        {
+            entry.archived = archived;
+            changed.push(entry.mod_id.clone());
+        }
+
+        if changed.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+
+        let mut effects = vec![Effect::SaveList { list: list.clone() }];
+        effects.extend(changed.iter().map(|mod_id| {
+            if archived {
+                Effect::ArchiveModFile {
+                    download_dir: download_dir.clone(),
+                    mod_id: mod_id.clone(),
+                }
+            } else {
+                Effect::UnarchiveModFile {
+                    download_dir: download_dir.clone(),
+                    mod_id: mod_id.clone(),
+                }
+            }
+        }));
+        (effects, changed)
+    }
+
+    pub fn toggle_archive_mod(&mut self, mod_id: &str) -> Vec<Effect> {
+        let Some(archived) = self
+            .get_current_list()
+            .and_then(|list| list.mods.iter().find(|e| e.mod_id == mod_id))
+            .map(|e| e.archived)
+        else {
+            return Vec::new();
+        };
+
+        self.set_mods_archived(&[mod_id.to_string()], !archived)
+    }
+
+    pub fn toggle_compatibility_override(&mut self, mod_id: &str) -> Vec<Effect> {
+        if let Some(list) = self.get_current_list_mut()
+            && let Some(entry) = list.mods.iter_mut().find(|e| e.mod_id == mod_id)
+        {
+            entry.compatibility_override = !entry.compatibility_override;
+            return vec![Effect::SaveList { list: list.clone() }];
+        }
+        Vec::new()
+    }
+
+    pub fn toggle_pin_version(&mut self, mod_id: &str) -> Vec<Effect> {
+        let version = self.get_effective_version();
+        let loader = self.get_effective_loader();
+        let current_version_id = self
+            .get_cached_mod(mod_id)
+            .map(|info| info.version_id.clone())
+            .filter(|id| !id.is_empty());
+
+        let Some(list) = self.get_current_list_mut() else {
+            return Vec::new();
+        };
+
+        if list.unpin_version(mod_id) {
+            let list_clone = list.clone();
+            self.forget_mod_info(&(mod_id.to_string(), version, loader));
+            let mut effects = vec![Effect::SaveList { list: list_clone }];
+            effects.extend(self.force_reload_mod(mod_id));
+            return effects;
+        }
+
+        if let Some(version_id) = current_version_id
+            && list.pin_version(mod_id, version_id)
+        {
+            return vec![Effect::SaveList { list: list.clone() }];
+        }
+
+        Vec::new()
+    }
+
+    // Active entries that are incompatible and not overruled yet
+    pub fn get_overrulable_mod_ids(&self) -> Vec<String> {
+        let Some(list) = self.get_current_list() else {
+            return Vec::new();
+        };
+        list.mods
+            .iter()
+            .filter(|e| !e.archived && !e.compatibility_override)
+            .filter(|e| self.is_mod_compatible_raw(&e.mod_id) == Some(false))
+            .map(|e| e.mod_id.clone())
+            .collect()
+    }
+
+    pub fn count_compatibility_overrides(&self) -> usize {
+        self.get_current_list().map_or(0, |l| {
+            l.mods.iter().filter(|e| e.compatibility_override).count()
+        })
+    }
+
+    // Overrules every currently incompatible entry, or revokes every override
+    pub fn set_all_incompatible_overruled(&mut self, overruled: bool) -> Vec<Effect> {
+        let targets: HashSet<String> = if overruled {
+            self.get_overrulable_mod_ids().into_iter().collect()
+        } else {
+            HashSet::new()
+        };
+
+        let Some(list) = self.get_current_list_mut() else {
+            return Vec::new();
+        };
+        let mut changed = 0;
+        for entry in &mut list.mods {
+            let target = if overruled {
+                entry.compatibility_override || targets.contains(&entry.mod_id)
+            } else {
+                false
+            };
+            if entry.compatibility_override != target {
+                entry.compatibility_override = target;
+                changed += 1;
+            }
+        }
+
+        if changed == 0 {
+            return Vec::new();
+        }
+        log::info!(
+            "{} compatibility override on {changed} entries of {}",
+            if overruled { "Set" } else { "Revoked" },
+            list.name
+        );
+        vec![Effect::SaveList { list: list.clone() }]
+    }
+
+    pub fn has_compatibility_override(&self, mod_id: &str) -> bool {
+        if let Some(list) = self.get_current_list()
+            && let Some(entry) = list.mods.iter().find(|e| e.mod_id == mod_id)
+        {
+            return entry.compatibility_override;
+        }
+        false
+    }
+
+    // Plain-text summary of a failing entry for pasting into a bug report
+    pub fn diagnostics_report(&self, mod_id: &str) -> Option<String> {
+        let list = self.get_current_list()?;
+        let entry = list.mods.iter().find(|e| e.mod_id == mod_id)?;
+        let info = self.get_cached_mod(mod_id);
+
+        let mut lines = vec![
+            format!("Project: {} ({})", entry.mod_name, entry.mod_id),
+            format!(
+                "Slug: {}",
+                info.as_ref().map_or("unknown", |info| info.slug.as_str())
+            ),
+            format!("Provider: {}", entry.provider.display_name()),
+            format!("Content type: {}", list.content_type.display_name()),
+            format!("Minecraft version: {}", self.get_effective_version()),
+            format!("Loader: {}", self.get_effective_loader()),
+            format!(
+                "Release channel: {}",
+                self.get_allowed_channel().display_name()
+            ),
+        ];
+        if let Some(pinned) = &entry.pinned_version {
+            lines.push(format!("Pinned version: {pinned}"));
+        }
+        if let Some(info) = info.as_ref().filter(|info| !info.version.is_empty()) {
+            lines.push(format!(
+                "Resolved version: {} ({})",
+                info.version, info.version_id
+            ));
+        }
+        if let Some(DownloadStatus::Failed(failure)) = self.download_status.get(mod_id) {
+            lines.push(format!("Download status: failed, {}", failure.reason()));
+        }
+        lines.push(format!(
+            "Last error: {}",
+            self.last_errors
+                .get(mod_id)
+                .map_or("none recorded", String::as_str)
+        ));
+        lines.push(format!("App version: {}", env!("CARGO_PKG_VERSION")));
+        Some(lines.join("\n"))
+    }
+
+    pub fn delete_current_list(&mut self) -> Vec<Effect> {
+        if let Some(list_id) = self.current_list_id.clone()
+            && let Some(index) = self.mod_lists.iter().position(|l| l.id == list_id)
+        {
+            let list = self.mod_lists.remove(index);
+            self.undo_stack.push(UndoAction::RestoreList {
+                list: Box::new(list),
+                index,
+            });
+            self.current_list_id = None;
+            return vec![Effect::DeleteList { list_id }];
+        }
+        Vec::new()
+    }
+
+    pub fn toggle_list_favorite(&mut self, list_id: &str) -> Vec<Effect> {
+        let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) else {
+            return Vec::new();
+        };
+        list.is_favorite = !list.is_favorite;
+        vec![Effect::SaveList { list: list.clone() }]
+    }
+
+    pub fn rename_current_list(&mut self, name: String) -> Vec<Effect> {
+        let Some(list) = self.get_current_list_mut() else {
+            return Vec::new();
+        };
+        if list.name == name {
+            return Vec::new();
+        }
+
+        let previous = std::mem::replace(&mut list.name, name);
+        let effects = vec![Effect::SaveList { list: list.clone() }];
+        let list_id = list.id.clone();
+        self.undo_stack.push(UndoAction::RenameList {
+            list_id,
+            name: previous,
+        });
+        effects
+    }
+
+    // Name of the operation Ctrl+Z would revert
+    pub fn undo_description(&self) -> Option<&'static str> {
+        self.undo_stack.peek().map(UndoAction::description)
+    }
+
+    pub fn undo(&mut self) -> Vec<Effect> {
+        let Some(action) = self.undo_stack.pop() else {
+            return Vec::new();
+        };
+
+        match action {
+            UndoAction::RestoreEntries { list_id, entries } => {
+                let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) else {
+                    return Vec::new();
+                };
+                for (index, entry) in entries {
+                    if !list.contains_mod(&entry.mod_id) {
+                        list.mods.insert(index.min(list.mods.len()), entry);
+                    }
+                }
+                let list = list.clone();
+
+                let mut effects = self.select_list(&list_id);
+                effects.push(Effect::SaveList { list });
+                effects
+            }
+            UndoAction::RestoreList { list, index } => {
+                if self.mod_lists.iter().any(|l| l.id == list.id) {
+                    return Vec::new();
+                }
+                let list_id = list.id.clone();
+                self.mod_lists
+                    .insert(index.min(self.mod_lists.len()), (*list).clone());
+
+                let mut effects = self.select_list(&list_id);
+                effects.push(Effect::SaveList { list: *list });
+                effects
+            }
+            UndoAction::SetArchived {
+                list_id,
+                mod_ids,
+                archived,
+            } => {
+                if self.get_list_by_id(&list_id).is_none() {
+                    return Vec::new();
+                }
+                let mut effects = self.select_list(&list_id);
+                effects.extend(self.apply_archived(&mod_ids, archived).0);
+                effects
+            }
+            UndoAction::RenameList { list_id, name } => {
+                let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) else {
+                    return Vec::new();
+                };
+                list.name = name;
+                vec![Effect::SaveList { list: list.clone() }]
+            }
+        }
+    }
+
+    // Switches to the list an undone operation belongs to, so the change is visible
+    fn select_list(&mut self, list_id: &str) -> Vec<Effect> {
+        if self.current_list_id.as_deref() == Some(list_id) {
+            return Vec::new();
+        }
+
+        self.current_list_id = Some(list_id.to_string());
+        let mut effects = self.invalidate_and_reload();
+        effects.push(Effect::ValidateMetadata {
+            download_dir: self.get_effective_download_dir(),
+        });
+        effects
+    }
+
+    // With a subset, only those entries are exported, as a new list named after the source
+    pub fn export_current_list(
+        &mut self,
+        path: std::path::PathBuf,
+        overwrite: OverwritePolicy,
+        subset: Option<&[String]>,
+    ) -> Vec<Effect> {
+        let export_info = self.get_current_list().map(|list| {
+            let mut list = list.clone();
+            if let Some(subset) = subset {
+                let now = Utc::now();
+                list.mods.retain(|m| subset.contains(&m.mod_id));
+                list.id = format!("list_{}", now.timestamp_millis());
+                list.name = format!("{} (subset)", list.name);
+                list.created_at = now;
+            }
+            (
+                list.mods
+                    .iter()
+                    .map(|m| (m.mod_id.clone(), m.provider))
+                    .collect::<Vec<_>>(),
+                list,
+            )
+        });
+
+        let (mod_ids, current_list_obj) = match export_info {
+            Some(data) => data,
+            None => return Vec::new(),
+        };
+
+        match path.extension().and_then(|s| s.to_str()) {
+            Some("mods") => {
+                self.legacy_state = LegacyState::InProgress {
+                    current: 0,
+                    total: mod_ids.len(),
+                    message: "Initializing export...".into(),
+                };
+
+                vec![Effect::LegacyListExport {
+                    path,
+                    mod_ids,
+                    version: self.get_effective_version(),
+                    loader: self.get_effective_loader(),
+                    overwrite,
+                }]
+            }
+            Some("mrpack") => {
+                self.legacy_state = LegacyState::InProgress {
+                    current: 0,
+                    total: mod_ids.len(),
+                    message: "Initializing export...".into(),
+                };
+
+                vec![Effect::ExportMrpack {
+                    path,
+                    name: current_list_obj.name.clone(),
+                    parts: vec![MrpackPart {
+                        list: current_list_obj,
+                        loader: self.get_effective_loader(),
+                    }],
+                    version: self.get_effective_version(),
+                    overwrite,
+                }]
+            }
+            Some("json") => {
+                self.legacy_state = LegacyState::InProgress {
+                    current: 0,
+                    total: mod_ids.len(),
+                    message: "Initializing export...".into(),
+                };
+
+                vec![Effect::ExportLockfile {
+                    path,
+                    list: current_list_obj,
+                    version: self.get_effective_version(),
+                    loader: self.get_effective_loader(),
+                    overwrite,
+                }]
+            }
+            Some("txt") => {
+                self.legacy_state = LegacyState::InProgress {
+                    current: 0,
+                    total: mod_ids.len(),
+                    message: "Initializing export...".into(),
+                };
+
+                vec![Effect::ExportUrlList {
+                    path,
+                    list: current_list_obj,
+                    version: self.get_effective_version(),
+                    loader: self.get_effective_loader(),
+                    overwrite,
+                }]
+            }
+            _ => vec![Effect::ExportListToml {
+                path,
+                list: current_list_obj,
+                overwrite,
+            }],
+        }
+    }
+
+    pub fn install_lockfile(
+        &mut self,
+        path: std::path::PathBuf,
+        download_dir: std::path::PathBuf,
+    ) -> Vec<Effect> {
+        if self.is_offline {
+            return Vec::new();
+        }
+
+        self.lockfile_install = Some(LockfileInstall {
+            download_dir: download_dir.clone(),
+            progress: (0, 0, "Reading lockfile...".into()),
+            result: None,
+        });
+        vec![Effect::InstallLockfile { path, download_dir }]
+    }
+
+    // Resolves Modrinth slugs and links, one per input, and adds them to the list as they arrive
+    pub fn add_many_by_slug(&mut self, list_id: &str, inputs: Vec<String>) -> Vec<Effect> {
+        let Some(list) = self.mod_lists.iter().find(|l| l.id == list_id) else {
+            return Vec::new();
+        };
+        let (version, loader, _) = self.compute_effective_settings_for_list(list);
+        let channel = list.allowed_channel;
+
+        let mut slugs: Vec<String> = Vec::new();
+        let mut failed = Vec::new();
+        for input in inputs {
+            match parse_project_slug(&input) {
+                Ok(slug) if !slugs.contains(&slug) => slugs.push(slug),
+                Ok(_) => {}
+                Err(e) => failed.push((input, e)),
+            }
+        }
+
+        self.bulk_add = Some(BulkAdd {
+            list_id: list_id.to_string(),
+            total: slugs.len() + failed.len(),
+            added: Vec::new(),
+            already_present: Vec::new(),
+            failed,
+        });
+
+        if slugs.is_empty() {
+            return Vec::new();
+        }
+        vec![Effect::ResolveSlugs {
+            list_id: list_id.to_string(),
+            slugs,
+            version,
+            loader,
+            channel,
+        }]
+    }
+
+    fn handle_slug_resolved(
+        &mut self,
+        list_id: &str,
+        slug: String,
+        result: Result<Arc<ModInfo>, String>,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let mod_info = match result {
+            Ok(mod_info) => mod_info,
+            Err(e) => {
+                if let Some(bulk) = self.bulk_add.as_mut().filter(|b| b.list_id == list_id) {
+                    bulk.failed.push((slug, e));
+                }
+                return effects;
+            }
+        };
+
+        let already_present = self
+            .mod_lists
+            .iter()
+            .find(|l| l.id == list_id)
+            .is_some_and(|l| l.contains_mod(&mod_info.id));
+
+        if !already_present {
+            // The dependencies are only resolved for the list that is open
+            if self.current_list_id.as_deref() == Some(list_id) {
+                effects.extend(self.add_mod_to_current_list(mod_info.clone()));
+            } else if let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) {
+                list.add_with_dependencies(&mod_info, &[]);
+                effects.push(Effect::SaveList { list: list.clone() });
+            }
+        }
+
+        if let Some(bulk) = self.bulk_add.as_mut().filter(|b| b.list_id == list_id) {
+            if already_present {
+                bulk.already_present.push(mod_info.name.clone());
+            } else {
+                bulk.added.push(mod_info.name.clone());
+            }
+        }
+        effects
+    }
+
+    pub fn start_legacy_import(&mut self, path: std::path::PathBuf) -> Vec<Effect> {
+        vec![Effect::PreviewLegacyImport { path }]
+    }
+
+    pub fn start_directory_import(&mut self, path: std::path::PathBuf) -> Vec<Effect> {
+        self.legacy_state = LegacyState::InProgress {
+            current: 0,
+            total: 0,
+            message: "Reading folder...".into(),
+        };
+
+        vec![Effect::ImportDirectory { path }]
+    }
+
+    pub fn start_collection_import(&mut self, collection_id: String) -> Vec<Effect> {
+        self.legacy_state = LegacyState::InProgress {
+            current: 0,
+            total: 0,
+            message: "Loading collection...".into(),
+        };
+
+        vec![Effect::PreviewCollectionImport { collection_id }]
+    }
+
+    pub fn resolve_legacy_import(
+        &mut self,
+        suggested_name: String,
+        slugs: Vec<String>,
+        version: String,
+        loader: String,
+    ) -> Vec<Effect> {
+        self.legacy_state = LegacyState::InProgress {
+            current: 0,
+            total: slugs.len(),
+            message: "Preparing import...".into(),
+        };
+        self.legacy_matches.clear();
+        self.legacy_import_target = (version.clone(), loader.clone());
+
+        vec![Effect::LegacyListImport {
+            suggested_name,
+            slugs,
+            version,
+            loader,
+        }]
+    }
+
+    // Shows the error in the import/export results window
+    pub fn report_legacy_failure(&mut self, error: String, is_import: bool) {
+        self.pending_legacy_mods = None;
+        self.legacy_state = LegacyState::Complete {
+            suggested_name: String::new(),
+            successful: Vec::new(),
+            failed: Vec::new(),
+            warnings: vec![error],
+            is_import,
+        };
+    }
+
+    pub fn find_legacy_candidates(&mut self, index: usize) -> Vec<Effect> {
+        let Some(legacy_match) = self.legacy_matches.get(index) else {
+            return Vec::new();
+        };
+        self.legacy_candidates_loading = Some(index);
+
+        let (version, loader) = self.legacy_import_target.clone();
+        vec![Effect::FindLegacyCandidates {
+            index,
+            query: legacy_match.query.trim().to_string(),
+            version,
+            loader,
+        }]
+    }
+
+    // Swaps the project an entry resolved to before the import is finalized
+    pub fn pick_legacy_match(&mut self, index: usize, candidate: Arc<ModInfo>) {
+        if let Some(mods) = &mut self.pending_legacy_mods
+            && let Some(slot) = mods.get_mut(index)
+        {
+            *slot = candidate;
+        }
+    }
+
+    pub fn clear_legacy_matches(&mut self) {
+        self.legacy_matches.clear();
+        self.legacy_candidates_loading = None;
+    }
+
+    pub fn finalize_import(&mut self, mut list: ModList) -> Vec<Effect> {
+        self.fill_default_dir(&mut list);
+        self.current_list_id = Some(list.id.clone());
+        self.mod_lists.push(list.clone());
+
+        vec![Effect::SaveList { list }]
+    }
+
+    // An existing list of the same type with exactly the same active mods
+    pub fn find_duplicate_list(&self, list: &ModList) -> Option<String> {
+        let key = list.content_key();
+        if key.is_empty() {
+            return None;
+        }
+
+        self.mod_lists
+            .iter()
+            .find(|l| l.content_type == list.content_type && l.content_key() == key)
+            .map(|l| l.id.clone())
+    }
+
+    // An existing list the import is likely a newer version of, matched by name
+    pub fn find_previous_version(&self, list: &ModList) -> Option<String> {
+        let name = list.name.trim();
+        self.mod_lists
+            .iter()
+            .find(|l| {
+                l.content_type == list.content_type && l.name.trim().eq_ignore_ascii_case(name)
+            })
+            .map(|l| l.id.clone())
+    }
+
+    pub fn replace_with_import(&mut self, list_id: &str, imported: ModList) -> Vec<Effect> {
+        let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) else {
+            return Vec::new();
+        };
+        list.apply_update(imported);
+        log::info!("Replaced '{}' with the imported version", list.name);
+        let list = list.clone();
+
+        self.current_list_id = Some(list.id.clone());
+        let mut effects = self.invalidate_and_reload();
+        effects.push(Effect::ValidateMetadata {
+            download_dir: self.get_effective_download_dir(),
+        });
+        effects.push(Effect::SaveList { list });
+        effects
     }
 
-    pub fn export_current_list(&mut self, path: std::path::PathBuf) -> Vec<Effect> {
-        let export_info = self.get_current_list().map(|list| {
-            (
-                list.mods
-                    .iter()
-                    .map(|m| m.mod_id.clone())
-                    .collect::<Vec<String>>(),
-                list.clone(),
-            )
+    pub fn merge_import(&mut self, list_id: &str, imported: ModList) -> Vec<Effect> {
+        let Some(list) = self.mod_lists.iter_mut().find(|l| l.id == list_id) else {
+            return Vec::new();
+        };
+        let added = list.merge_from(imported);
+        log::info!("Merged import into '{}', {added} mods added", list.name);
+        let list = list.clone();
+
+        self.current_list_id = Some(list.id.clone());
+        let mut effects = self.invalidate_and_reload();
+        effects.push(Effect::ValidateMetadata {
+            download_dir: self.get_effective_download_dir(),
         });
+        effects.push(Effect::SaveList { list });
+        effects
+    }
 
-        let (mod_ids, current_list_obj) = match export_info {
-            Some(data) => data,
-            None => return Vec::new(),
-        };
+    pub fn duplicate_retargeted(
+        &mut self,
+        mut list: ModList,
+        version: String,
+        loader: String,
+    ) -> Vec<Effect> {
+        let (old_version, old_loader, _) = self.compute_effective_settings_for_list(&list);
 
-        match path.extension().and_then(|s| s.to_str()) {
-            Some("mods") => {
-                self.legacy_state = LegacyState::InProgress {
-                    current: 0,
-                    total: mod_ids.len(),
-                    message: "Initializing export...".into(),
-                };
+        list.loader = self.resolve_loader(list.content_type, &loader);
+        list.version = version;
+        self.retargeted_lists
+            .insert(list.id.clone(), (old_version, old_loader));
 
-                vec![Effect::LegacyListExport {
-                    path,
-                    mod_ids,
-                    version: self.get_effective_version(),
-                    loader: self.get_effective_loader(),
-                }]
-            }
-            _ => vec![Effect::ExportListToml {
-                path,
-                list: current_list_obj,
-            }],
+        self.finalize_import(list)
+    }
+
+    pub fn retarget_origin(&self) -> Option<&(String, String)> {
+        self.retargeted_lists.get(self.current_list_id.as_ref()?)
+    }
+
+    pub fn dismiss_retarget_report(&mut self) {
+        if let Some(list_id) = &self.current_list_id {
+            self.retargeted_lists.remove(list_id);
         }
     }
 
-    pub fn start_legacy_import(&mut self, path: std::path::PathBuf) -> Vec<Effect> {
-        self.legacy_state = LegacyState::InProgress {
-            current: 0,
-            total: 0,
-            message: "Preparing import...".into(),
+    // Incompatible with the list's target but not known to be incompatible with the
+    // target of the list it was duplicated from
+    pub fn is_newly_incompatible(&self, mod_id: &str) -> bool {
+        let Some((old_version, old_loader)) = self.retarget_origin() else {
+            return false;
         };
 
-        vec![Effect::LegacyListImport {
-            path,
-            version: self.get_effective_version(),
-            loader: self.get_effective_loader(),
-        }]
+        self.is_mod_compatible_raw(mod_id) == Some(false)
+            && self.is_mod_compatible_with_context(mod_id, old_version, old_loader) != Some(false)
     }
 
-    pub fn finalize_import(&mut self, list: ModList) -> Vec<Effect> {
-        self.current_list_id = Some(list.id.clone());
-        self.mod_lists.push(list.clone());
-
-        vec![Effect::SaveList { list }]
+    fn resolve_loader(&self, content_type: ProjectType, loader: &str) -> crate::domain::ModLoader {
+        self.loaders_for_type(content_type)
+            .and_then(|loaders| loaders.iter().find(|l| l.matches(loader)).cloned())
+            .or_else(|| self.mod_loaders.iter().find(|l| l.matches(loader)).cloned())
+            .unwrap_or(crate::domain::ModLoader {
+                id: loader.to_string(),
+                name: loader.to_string(),
+            })
     }
 
     pub fn create_new_list(
@@ -674,17 +2714,9 @@ impl AppState {
         download_dir: String,
     ) -> Vec<Effect> {
         let list_name = new_name.to_string();
+        let loader_obj = self.resolve_loader(content_type, &loader);
 
-        let loader_obj = self
-            .loaders_for_type(content_type)
-            .and_then(|loaders| loaders.iter().find(|l| l.id == loader).cloned())
-            .or_else(|| self.mod_loaders.iter().find(|l| l.id == loader).cloned())
-            .unwrap_or(crate::domain::ModLoader {
-                id: loader.clone(),
-                name: loader.clone(),
-            });
-
-        let new_list = ModList {
+        let mut new_list = ModList {
             id: format!("list_{}", Utc::now().timestamp()),
             name: list_name,
             created_at: Utc::now(),
@@ -693,7 +2725,16 @@ impl AppState {
             loader: loader_obj,
             download_dir,
             content_type,
+            include_snapshots: false,
+            server_layout: false,
+            server_mode: false,
+            loader_version: None,
+            allowed_channel: ReleaseChannel::Alpha,
+            notes: String::new(),
+            tags: Vec::new(),
+            is_favorite: false,
         };
+        self.fill_default_dir(&mut new_list);
 
         self.current_list_id = Some(new_list.id.clone());
         self.mod_lists.push(new_list.clone());
@@ -701,6 +2742,213 @@ impl AppState {
         vec![Effect::SaveList { list: new_list }]
     }
 
+    pub fn get_bundle(&self, bundle_id: &str) -> Option<&Bundle> {
+        self.bundles.iter().find(|b| b.id == bundle_id)
+    }
+
+    // Member lists that still exist, in bundle order
+    pub fn bundle_lists(&self, bundle: &Bundle) -> Vec<&ModList> {
+        bundle
+            .list_ids
+            .iter()
+            .filter_map(|id| self.mod_lists.iter().find(|l| &l.id == id))
+            .collect()
+    }
+
+    // Creates the bundle when bundle_id is None, member lists are moved to the bundle's version
+    pub fn save_bundle(
+        &mut self,
+        bundle_id: Option<&str>,
+        name: String,
+        version: String,
+        list_ids: Vec<String>,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let mut current_changed = false;
+        for list in self
+            .mod_lists
+            .iter_mut()
+            .filter(|l| list_ids.contains(&l.id) && l.version != version)
+        {
+            list.version = version.clone();
+            current_changed |= self.current_list_id.as_ref() == Some(&list.id);
+            effects.push(Effect::SaveList { list: list.clone() });
+        }
+
+        match bundle_id.and_then(|id| self.bundles.iter_mut().find(|b| b.id == id)) {
+            Some(bundle) => {
+                bundle.name = name;
+                bundle.version = version;
+                bundle.list_ids = list_ids;
+            }
+            None => self.bundles.push(Bundle {
+                id: format!("bundle_{}", Utc::now().timestamp_millis()),
+                name,
+                version,
+                list_ids,
+            }),
+        }
+
+        if current_changed {
+            self.invalidate_effective_settings();
+            effects.extend(self.invalidate_and_reload());
+        }
+        effects.push(Effect::SaveConfig {
+            config: self.app_config(),
+        });
+        effects
+    }
+
+    // Only the grouping goes away, the lists stay
+    pub fn delete_bundle(&mut self, bundle_id: &str) -> Vec<Effect> {
+        self.bundles.retain(|b| b.id != bundle_id);
+        vec![Effect::SaveConfig {
+            config: self.app_config(),
+        }]
+    }
+
+    // Downloads every member list into its own folder, fetching details the list isn't showing yet
+    pub fn download_bundle(&mut self, bundle_id: &str) -> Vec<Effect> {
+        let Some(bundle) = self.get_bundle(bundle_id) else {
+            return Vec::new();
+        };
+        if self.is_offline {
+            return Vec::new();
+        }
+
+        let mut ready = Vec::new();
+        let mut fetches = Vec::new();
+        for list in self.bundle_lists(bundle) {
+            let (version, loader, download_dir) = self.compute_effective_settings_for_list(list);
+            let downloaded = self.metadata_cache.get(&download_dir);
+            for entry in &list.mods {
+                if entry.archived
+                    || (entry.locked
+                        && downloaded.is_some_and(|m| m.get_entry(&entry.mod_id).is_some()))
+                {
+                    continue;
+                }
+
+                let key = (entry.mod_id.clone(), version.clone(), loader.clone());
+                let target = BundleTarget {
+                    list_id: list.id.clone(),
+                    download_dir: download_dir.clone(),
+                    game_version: version.clone(),
+                    filename_override: entry.filename_override.clone(),
+                    disabled: entry.disabled,
+                };
+                match self.cached_mods.get(&key) {
+                    Some(info) if !info.version.is_empty() => {
+                        ready.push((info.clone(), target));
+                    }
+                    _ => fetches.push((
+                        key,
+                        target,
+                        Effect::FetchModDetails {
+                            provider: entry.provider,
+                            mod_id: entry.mod_id.clone(),
+                            pinned_version: entry.pinned_version.clone(),
+                            version: version.clone(),
+                            loader: loader.clone(),
+                            channel: list.allowed_channel,
+                            offline: false,
+                        },
+                    )),
+                }
+            }
+        }
+
+        let mut effects: Vec<Effect> = ready
+            .into_iter()
+            .map(|(info, target)| self.bundle_download(info, target))
+            .collect();
+        for (key, target, fetch) in fetches {
+            self.pending_bundle_downloads.insert(key, target);
+            effects.push(fetch);
+        }
+        effects
+    }
+
+    fn bundle_download(&mut self, mod_info: Arc<ModInfo>, target: BundleTarget) -> Effect {
+        self.download_status
+            .insert(mod_info.id.clone(), DownloadStatus::Queued);
+        Effect::DownloadMod {
+            mod_info,
+            filename_override: target.filename_override,
+            disabled: target.disabled,
+            download_dir: target.download_dir,
+            list_id: Some(target.list_id),
+            game_version: target.game_version,
+            max_retries: self.download_retries,
+            clean_replaced: self.clean_replaced_files,
+        }
+    }
+
+    // .mrpack packs every type into one modpack, anything else writes a bundle .mmd
+    pub fn export_bundle(
+        &mut self,
+        bundle_id: &str,
+        path: std::path::PathBuf,
+        overwrite: OverwritePolicy,
+    ) -> Vec<Effect> {
+        let Some(bundle) = self.get_bundle(bundle_id) else {
+            return Vec::new();
+        };
+        let lists = self.bundle_lists(bundle);
+
+        if path.extension().and_then(|s| s.to_str()) == Some("mrpack") {
+            let parts: Vec<MrpackPart> = lists
+                .iter()
+                .map(|list| MrpackPart {
+                    list: (*list).clone(),
+                    loader: self.compute_effective_settings_for_list(list).1,
+                })
+                .collect();
+            let name = bundle.name.clone();
+            let version = bundle.version.clone();
+            self.legacy_state = LegacyState::InProgress {
+                current: 0,
+                total: parts.iter().map(|p| p.list.mods.len()).sum(),
+                message: "Initializing export...".into(),
+            };
+            return vec![Effect::ExportMrpack {
+                path,
+                name,
+                parts,
+                version,
+                overwrite,
+            }];
+        }
+
+        vec![Effect::ExportBundleToml {
+            path,
+            bundle: BundleFile {
+                name: bundle.name.clone(),
+                version: bundle.version.clone(),
+                lists: lists.into_iter().cloned().collect(),
+            },
+            overwrite,
+        }]
+    }
+
+    // Imported lists get fresh IDs so they never replace existing ones
+    pub fn import_bundle(&mut self, file: BundleFile) -> Vec<Effect> {
+        let now = Utc::now().timestamp_millis();
+        let mut effects = Vec::new();
+        let mut list_ids = Vec::new();
+        for (index, mut list) in file.lists.into_iter().enumerate() {
+            list.id = format!("list_{now}_{index}");
+            list.created_at = Utc::now();
+            list.drop_invalid_filename_overrides();
+            self.fill_default_dir(&mut list);
+            list_ids.push(list.id.clone());
+            effects.push(Effect::SaveList { list: list.clone() });
+            self.mod_lists.push(list);
+        }
+        effects.extend(self.save_bundle(None, file.name, file.version, list_ids));
+        effects
+    }
+
     pub fn is_mod_compatible(&self, mod_id: &str) -> Option<bool> {
         if let Some(list) = self.get_current_list()
             && let Some(entry) = list.mods.iter().find(|e| e.mod_id == mod_id)
@@ -726,14 +2974,62 @@ impl AppState {
         version: &str,
         loader: &str,
     ) -> Option<bool> {
-        let info = self.get_cached_mod_with_context(mod_id, version, loader)?;
+        let key = (mod_id.to_string(), version.to_string(), loader.to_string());
+        if let Some(&compatible) = self.compat_cache.borrow().get(&key) {
+            return Some(compatible);
+        }
+
+        let info = self.cached_mods.get(&key)?;
+        // Only known for resource packs, once the downloaded file has been checked
+        let pack_format_ok = self
+            .pack_format_mismatches
+            .contains_key(mod_id)
+            .then_some(false);
+        let compatible = compatibility_for(
+            self.get_current_list_type(),
+            info,
+            version,
+            loader,
+            self.compat_policy,
+            pack_format_ok,
+        );
+        self.compat_cache.borrow_mut().insert(key, compatible);
+        Some(compatible)
+    }
 
-        let version_ok = info.supported_versions.is_empty()
-            || info.supported_versions.iter().any(|v| v == version);
-        let loader_ok =
-            info.supported_loaders.is_empty() || info.supported_loaders.iter().any(|l| l == loader);
+    // Client-only mods aren't downloaded for lists in server mode
+    pub fn is_skipped_in_server_mode(&self, mod_id: &str) -> bool {
+        self.get_current_list().is_some_and(|l| l.server_mode)
+            && self
+                .get_cached_mod(mod_id)
+                .is_some_and(|info| info.environment == ModEnvironment::ClientOnly)
+    }
 
-        Some(version_ok && loader_ok)
+    // Locked entries are only included while they haven't been downloaded yet
+    pub fn get_downloadable_mod_ids(&self, entries: &[ModEntry]) -> Vec<String> {
+        entries
+            .iter()
+            .filter(|entry| {
+                !entry.archived
+                    && (!entry.locked || !self.has_download_metadata(&entry.mod_id))
+                    && !self.mods_being_loaded.contains(&entry.mod_id)
+                    && !self.is_skipped_in_server_mode(&entry.mod_id)
+                    && self
+                        .download_status
+                        .get(&entry.mod_id)
+                        .map(|s| {
+                            matches!(
+                                s,
+                                DownloadStatus::Idle
+                                    | DownloadStatus::Complete
+                                    | DownloadStatus::Failed(_)
+                            )
+                        })
+                        .unwrap_or(true)
+                    && self.is_mod_compatible(&entry.mod_id).unwrap_or(false)
+            })
+            .map(|e| e.mod_id.clone())
+            .collect()
     }
 
     pub fn get_filtered_mods(
@@ -743,10 +3039,11 @@ impl AppState {
         order_mode: OrderMode,
         filter_mode: FilterMode,
     ) -> Vec<ModEntry> {
-        let query = query.to_lowercase();
+        let query = query.trim().to_lowercase();
         let effective_version = self.get_effective_version();
         let effective_loader = self.get_effective_loader();
 
+        let mut scores: HashMap<String, i64> = HashMap::new();
         let mut mods: Vec<ModEntry> = self
             .get_current_list()
             .map(|l| l.mods.clone())
@@ -757,21 +3054,29 @@ impl AppState {
                     return true;
                 }
 
-                if entry.mod_name.to_lowercase().contains(&query) {
-                    return true;
-                }
+                // Names match fuzzily, descriptions only by substring to avoid noise
+                let score = crate::domain::fuzzy_score(&query, &entry.mod_name).or_else(|| {
+                    self.get_cached_mod(&entry.mod_id)
+                        .filter(|info| info.description.to_lowercase().contains(&query))
+                        .map(|_| 0)
+                });
 
-                if let Some(info) = self.get_cached_mod(&entry.mod_id) {
-                    info.description.to_lowercase().contains(&query)
-                } else {
-                    false
+                match score {
+                    Some(score) => {
+                        scores.insert(entry.mod_id.clone(), score);
+                        true
+                    }
+                    None => false,
                 }
             })
             .collect();
 
         if matches!(
             filter_mode,
-            FilterMode::CompatibleOnly | FilterMode::IncompatibleOnly | FilterMode::MissingOnly
+            FilterMode::CompatibleOnly
+                | FilterMode::IncompatibleOnly
+                | FilterMode::MissingOnly
+                | FilterMode::UpdatableOnly
         ) {
             mods.retain(|entry| {
                 let comp = self
@@ -788,6 +3093,10 @@ impl AppState {
 
                 match filter_mode {
                     FilterMode::MissingOnly => missing,
+                    FilterMode::UpdatableOnly => {
+                        self.is_mod_updateable(&entry.mod_id)
+                            || self.has_available_update(&entry.mod_id)
+                    }
                     FilterMode::CompatibleOnly => comp,
                     FilterMode::IncompatibleOnly => !comp,
                     FilterMode::All => true,
@@ -798,26 +3107,31 @@ impl AppState {
         mods.sort_by(|a, b| match sort_mode {
             SortMode::Name => a.mod_name.to_lowercase().cmp(&b.mod_name.to_lowercase()),
             SortMode::DateAdded => a.added_at.cmp(&b.added_at),
+            // The list keeps its entries in manual order already
+            SortMode::Manual => std::cmp::Ordering::Equal,
         });
 
         if matches!(order_mode, OrderMode::Descending) {
             mods.reverse();
         }
 
+        if !query.is_empty() {
+            mods.sort_by_key(|entry| std::cmp::Reverse(scores.get(&entry.mod_id).copied()));
+        }
+
         mods
     }
 
     pub fn is_mod_downloaded(&self, mod_id: &str) -> bool {
-        let download_dir = self.get_effective_download_dir();
+        self.get_downloaded_file(mod_id).is_some()
+    }
 
-        if let Some(metadata) = self.metadata_cache.get(&download_dir)
-            && let Some(entry) = metadata.get_entry(mod_id)
-        {
-            let file_path = std::path::Path::new(&download_dir).join(&entry.file);
-            return file_path.exists();
-        }
+    pub fn get_downloaded_file(&self, mod_id: &str) -> Option<std::path::PathBuf> {
+        let download_dir = self.get_effective_download_dir();
 
-        false
+        let entry = self.metadata_cache.get(&download_dir)?.get_entry(mod_id)?;
+        let file_path = std::path::Path::new(&download_dir).join(&entry.file);
+        file_path.exists().then_some(file_path)
     }
 
     pub fn has_download_metadata(&self, mod_id: &str) -> bool {
@@ -846,6 +3160,152 @@ impl AppState {
         false
     }
 
+    // Starts the background update check once the configured interval has passed
+    pub fn poll_update_check(&mut self) -> Vec<Effect> {
+        if self.initial_loading
+            || self.update_check_hours == 0
+            || self.update_check_running
+            || self.is_offline
+            || self.next_update_check_in().is_some_and(|d| !d.is_zero())
+        {
+            return Vec::new();
+        }
+
+        let targets: Vec<UpdateCheckTarget> = self
+            .mod_lists
+            .iter()
+            .map(|list| {
+                let (version, loader, download_dir) =
+                    self.compute_effective_settings_for_list(list);
+                UpdateCheckTarget {
+                    list_id: list.id.clone(),
+                    download_dir,
+                    version,
+                    loader,
+                    channel: list.allowed_channel,
+                    mods: list
+                        .mods
+                        .iter()
+                        .filter(|e| !e.archived && !e.locked && e.pinned_version.is_none())
+                        .map(|e| (e.provider, e.mod_id.clone()))
+                        .collect(),
+                }
+            })
+            .filter(|target| !target.mods.is_empty())
+            .collect();
+
+        self.last_update_check = Some(std::time::Instant::now());
+        if targets.is_empty() {
+            return Vec::new();
+        }
+        self.update_check_running = true;
+        vec![Effect::CheckForUpdates { targets }]
+    }
+
+    pub fn next_update_check_in(&self) -> Option<std::time::Duration> {
+        if self.update_check_hours == 0 {
+            return None;
+        }
+        let interval = std::time::Duration::from_secs(self.update_check_hours * 60 * 60);
+        Some(
+            self.last_update_check
+                .map(|last| interval.saturating_sub(last.elapsed()))
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn has_available_update(&self, mod_id: &str) -> bool {
+        self.current_list_id
+            .as_ref()
+            .and_then(|id| self.available_updates.get(id))
+            .is_some_and(|ids| ids.contains(mod_id))
+    }
+
+    // Entries with a newer version found by the background check, over all lists
+    pub fn available_update_count(&self) -> usize {
+        self.mod_lists
+            .iter()
+            .filter_map(|list| {
+                let ids = self.available_updates.get(&list.id)?;
+                Some(list.mods.iter().filter(|e| ids.contains(&e.mod_id)).count())
+            })
+            .sum()
+    }
+
+    // Opens the current list if it has updates, otherwise the first list that does
+    pub fn show_available_updates(&mut self) -> Vec<Effect> {
+        let current_has_updates = self
+            .current_list_id
+            .as_ref()
+            .and_then(|id| self.available_updates.get(id))
+            .is_some_and(|ids| !ids.is_empty());
+        if current_has_updates {
+            return Vec::new();
+        }
+
+        let list_id = self
+            .mod_lists
+            .iter()
+            .find(|l| {
+                self.available_updates
+                    .get(&l.id)
+                    .is_some_and(|ids| l.mods.iter().any(|e| ids.contains(&e.mod_id)))
+            })
+            .map(|l| l.id.clone());
+        let Some(list_id) = list_id else {
+            return Vec::new();
+        };
+        let mut effects = self.select_list(&list_id);
+        effects.extend(self.reload_outdated_details());
+        effects
+    }
+
+    // Fetches the newer details of the current list's outdated mods, so they can update
+    fn reload_outdated_details(&mut self) -> Vec<Effect> {
+        let version = self.get_effective_version();
+        let loader = self.get_effective_loader();
+        let outdated: Vec<String> = self
+            .current_list_id
+            .as_ref()
+            .and_then(|id| self.available_updates.get(id))
+            .map(|ids| ids.iter().cloned().collect())
+            .unwrap_or_default();
+
+        let mut effects = Vec::new();
+        for mod_id in outdated {
+            self.forget_mod_info(&(mod_id.clone(), version.clone(), loader.clone()));
+            self.mods_being_loaded.remove(&mod_id);
+            effects.extend(self.load_mod_details_if_needed(&mod_id));
+        }
+        effects
+    }
+
+    pub fn scan_updates(&self) -> Vec<String> {
+        let Some(list) = self.get_current_list() else {
+            return Vec::new();
+        };
+
+        list.mods
+            .iter()
+            .filter(|entry| !entry.archived && !entry.locked)
+            .filter(|entry| !self.mods_being_loaded.contains(&entry.mod_id))
+            .filter(|entry| {
+                !matches!(
+                    self.download_status.get(&entry.mod_id),
+                    Some(
+                        DownloadStatus::Queued
+                            | DownloadStatus::Downloading
+                            | DownloadStatus::Retrying { .. }
+                    )
+                )
+            })
+            .filter(|entry| self.is_mod_compatible(&entry.mod_id).unwrap_or(false))
+            .filter(|entry| !self.is_skipped_in_server_mode(&entry.mod_id))
+            .filter(|entry| self.is_mod_updateable(&entry.mod_id))
+            .map(|entry| entry.mod_id.clone())
+            .collect()
+    }
+
     pub fn get_missing_mod_ids(&self, filtered_mods: &[ModEntry]) -> Vec<String> {
         let download_dir = self.get_effective_download_dir();
         let effective_version = self.get_effective_version();
@@ -856,13 +3316,19 @@ impl AppState {
         filtered_mods
             .iter()
             .filter(|entry| !entry.archived)
+            .filter(|entry| !self.is_skipped_in_server_mode(&entry.mod_id))
             .filter(|entry| {
                 if self.mods_being_loaded.contains(&entry.mod_id) {
                     return false;
                 }
 
                 if let Some(status) = self.download_status.get(&entry.mod_id)
-                    && matches!(status, DownloadStatus::Queued | DownloadStatus::Downloading)
+                    && matches!(
+                        status,
+                        DownloadStatus::Queued
+                            | DownloadStatus::Downloading
+                            | DownloadStatus::Retrying { .. }
+                    )
                 {
                     return false;
                 }
@@ -881,13 +3347,22 @@ impl AppState {
 
                 if let Some(mod_info) = self.get_cached_mod(&entry.mod_id) {
                     if let Some(meta) = metadata
-                        && let Some(entry) = meta.get_entry(&mod_info.id)
+                        && let Some(recorded) = meta.get_entry(&mod_info.id)
                     {
-                        let file_path = std::path::Path::new(&download_dir).join(&entry.file);
-                        return !file_path.exists() || entry.version != mod_info.version;
+                        let file_path = std::path::Path::new(&download_dir).join(&recorded.file);
+                        let recorded_name = recorded
+                            .file
+                            .strip_suffix(DISABLED_SUFFIX)
+                            .unwrap_or(&recorded.file);
+                        return !file_path.exists()
+                            || recorded.version != mod_info.version
+                            || entry
+                                .filename_override
+                                .as_ref()
+                                .is_some_and(|name| name != recorded_name);
                     }
 
-                    let filename = generate_mod_filename(&mod_info);
+                    let filename = entry.disk_filename_for(&mod_info);
                     let file_path = std::path::Path::new(&download_dir).join(&filename);
                     !file_path.exists()
                 } else {
@@ -898,6 +3373,73 @@ impl AppState {
             .collect()
     }
 
+    // Only deletes names that are still unknown, so files that became known in the meantime are kept
+    pub fn delete_unknown_files(&mut self, filenames: &[String]) -> Vec<Effect> {
+        let unknown: HashSet<String> = self.get_unknown_mod_files().into_iter().collect();
+        let download_dir = self.get_effective_download_dir();
+        let paths: Vec<std::path::PathBuf> = filenames
+            .iter()
+            .filter(|name| unknown.contains(*name))
+            .map(|name| std::path::Path::new(&download_dir).join(name))
+            .collect();
+
+        self.unknown_cleanup_result = None;
+        if paths.is_empty() {
+            return Vec::new();
+        }
+        vec![Effect::DeleteUnknownFiles { paths }]
+    }
+
+    // Files that still have metadata but whose mod was removed from every list using the folder
+    pub fn get_orphaned_files(&self) -> Vec<(String, String)> {
+        let download_dir = self.get_effective_download_dir();
+        let Some(metadata) = self.metadata_cache.get(&download_dir) else {
+            return Vec::new();
+        };
+
+        let mut list_dirs = self.list_dirs.borrow_mut();
+        let project_ids: HashSet<&str> = self
+            .mod_lists
+            .iter()
+            .filter(|list| {
+                let dir = list_dirs
+                    .entry(list.id.clone())
+                    .or_insert_with(|| self.compute_effective_settings_for_list(list).2);
+                *dir == download_dir
+            })
+            .flat_map(|list| list.mods.iter().map(|e| e.mod_id.as_str()))
+            .collect();
+
+        metadata
+            .orphaned_entries(&project_ids)
+            .into_iter()
+            .map(|(mod_id, entry)| (mod_id.clone(), entry.file.clone()))
+            .collect()
+    }
+
+    // Only cleans up mods that are still orphaned, so re-added mods keep their files
+    pub fn clean_up_orphaned_files(&mut self, mod_ids: &[String]) -> Vec<Effect> {
+        let orphaned: HashSet<String> = self
+            .get_orphaned_files()
+            .into_iter()
+            .map(|(mod_id, _)| mod_id)
+            .collect();
+        let mod_ids: Vec<String> = mod_ids
+            .iter()
+            .filter(|id| orphaned.contains(*id))
+            .cloned()
+            .collect();
+
+        self.orphan_cleanup_result = None;
+        if mod_ids.is_empty() {
+            return Vec::new();
+        }
+        vec![Effect::DeleteOrphanedFiles {
+            download_dir: self.get_effective_download_dir(),
+            mod_ids,
+        }]
+    }
+
     pub fn get_unknown_mod_files(&self) -> Vec<String> {
         let download_dir = self.get_effective_download_dir();
         let download_path = std::path::Path::new(&download_dir);
@@ -917,11 +3459,23 @@ impl AppState {
 
         let current_list = self.get_current_list();
 
-        let known_filenames: HashSet<String> = if let Some(meta) = metadata {
+        let mut known_filenames: HashSet<String> = if let Some(meta) = metadata {
             meta.mods.values().map(|entry| entry.file.clone()).collect()
         } else {
             HashSet::new()
         };
+        if let Some(list) = current_list {
+            known_filenames.extend(list.mods.iter().filter_map(|e| {
+                let mut name = e.filename_override.clone()?;
+                if e.disabled {
+                    name.push_str(DISABLED_SUFFIX);
+                }
+                if e.archived {
+                    name.push_str(".archived");
+                }
+                Some(name)
+            }));
+        }
 
         log::debug!(
             "Unknown file detection: metadata has {} entries with {} unique filenames",
@@ -944,11 +3498,18 @@ impl AppState {
                     let current_type = current_list.map(|l| l.content_type).unwrap_or_default();
                     let expected_ext = current_type.fileext();
                     let base_filename = filename.strip_suffix(".archived").unwrap_or(filename);
+                    let base_filename = base_filename
+                        .strip_suffix(DISABLED_SUFFIX)
+                        .unwrap_or(base_filename);
                     if !base_filename.ends_with(&format!(".{expected_ext}")) {
                         continue;
                     }
 
-                    let is_known = known_filenames.contains(filename);
+                    // A known file disabled by hand, e.g. in a launcher, still belongs to its entry
+                    let is_known = known_filenames.contains(filename)
+                        || filename
+                            .strip_suffix(DISABLED_SUFFIX)
+                            .is_some_and(|base| known_filenames.contains(base));
 
                     if !is_known {
                         log::debug!("Found unknown file: {filename}");
@@ -1,30 +1,129 @@
-use crate::domain::{ModInfo, ModList, ProjectType};
+use crate::app::UpdateCheckTarget;
+use crate::domain::{
+    AppConfig, BundleFile, DownloadRecord, ModInfo, ModList, OverwritePolicy, ProjectType,
+    ProviderKind, ReleaseChannel, SearchSort,
+};
+use crate::infra::MrpackPart;
 use std::path::PathBuf;
 use std::sync::Arc;
 #[derive(Clone)]
 pub enum Effect {
     LoadInitialData,
+    // Stops waiting on the network during startup and continues from cache
+    SkipInitialLoad,
 
     LoadLoadersForType {
         project_type: ProjectType,
     },
 
+    LoadLoaderVersions {
+        version: String,
+        loader: String,
+    },
+
+    RefreshGameData {
+        project_types: Vec<ProjectType>,
+    },
+
     SearchMods {
         query: String,
         version: String,
         loader: String,
         project_type: ProjectType,
+        provider: ProviderKind,
+        categories: Vec<String>,
+        sort: SearchSort,
+        // Skips the cached results of an identical search
+        force_refresh: bool,
+    },
+
+    FindAlternatives {
+        mod_id: String,
+        query: String,
+        version: String,
+        loader: String,
+        project_type: ProjectType,
+        provider: ProviderKind,
+    },
+
+    CheckCompatibility {
+        // (provider, mod id, Minecraft version) per matrix cell
+        cells: Vec<(ProviderKind, String, String)>,
+        loader: String,
+        channel: ReleaseChannel,
+    },
+    CheckForUpdates {
+        targets: Vec<UpdateCheckTarget>,
+    },
+    ResolveSlugs {
+        list_id: String,
+        slugs: Vec<String>,
+        version: String,
+        loader: String,
+        channel: ReleaseChannel,
+    },
+
+    LoadCategories {
+        provider: ProviderKind,
+        project_type: ProjectType,
     },
 
     FetchModDetails {
+        provider: ProviderKind,
         mod_id: String,
+        pinned_version: Option<String>,
         version: String,
         loader: String,
+        channel: ReleaseChannel,
+        offline: bool,
+    },
+
+    ResolveDependencies {
+        mod_info: Arc<ModInfo>,
+        version: String,
+        loader: String,
+        channel: ReleaseChannel,
+        known_ids: Vec<String>,
     },
 
     DownloadMod {
         mod_info: Arc<ModInfo>,
+        filename_override: Option<String>,
+        disabled: bool,
+        download_dir: String,
+        list_id: Option<String>,
+        game_version: String,
+        max_retries: u32,
+        // Delete the previously recorded file once the new one is in place
+        clean_replaced: bool,
+    },
+
+    FetchChangelog {
+        provider: ProviderKind,
+        mod_id: String,
+        version_id: String,
+    },
+
+    FetchProjectVersions {
+        provider: ProviderKind,
+        mod_id: String,
+        loader: String,
+    },
+
+    LoadChosenVersion {
+        provider: ProviderKind,
+        mod_id: String,
+        version_id: String,
+    },
+
+    CheckDiskSpace {
         download_dir: String,
+        needed: u64,
+        mod_ids: Vec<String>,
+    },
+
+    RecordDownload {
+        record: DownloadRecord,
     },
 
     SaveList {
@@ -35,31 +134,84 @@ pub enum Effect {
     },
 
     SaveConfig {
-        current_list_id: Option<String>,
-        default_list_name: String,
+        config: AppConfig,
     },
 
     ExportListToml {
         path: PathBuf,
         list: ModList,
+        overwrite: OverwritePolicy,
+    },
+    ExportBundleToml {
+        path: PathBuf,
+        bundle: BundleFile,
+        overwrite: OverwritePolicy,
     },
 
-    LegacyListImport {
+    PreviewLegacyImport {
         path: PathBuf,
+    },
+
+    FindLegacyCandidates {
+        index: usize,
+        query: String,
+        version: String,
+        loader: String,
+    },
+
+    PreviewCollectionImport {
+        collection_id: String,
+    },
+
+    ImportDirectory {
+        path: PathBuf,
+    },
+
+    LegacyListImport {
+        suggested_name: String,
+        slugs: Vec<String>,
         version: String,
         loader: String,
     },
 
     LegacyListExport {
         path: PathBuf,
-        mod_ids: Vec<String>,
+        mod_ids: Vec<(String, ProviderKind)>,
         version: String,
         loader: String,
+        overwrite: OverwritePolicy,
+    },
+
+    ExportMrpack {
+        path: PathBuf,
+        name: String,
+        parts: Vec<MrpackPart>,
+        version: String,
+        overwrite: OverwritePolicy,
     },
 
-    RemoveFromMetadata {
+    ExportLockfile {
+        path: PathBuf,
+        list: ModList,
+        version: String,
+        loader: String,
+        overwrite: OverwritePolicy,
+    },
+    InstallLockfile {
+        path: PathBuf,
+        download_dir: PathBuf,
+    },
+    ExportUrlList {
+        path: PathBuf,
+        list: ModList,
+        version: String,
+        loader: String,
+        overwrite: OverwritePolicy,
+    },
+
+    MatchDroppedFiles {
+        paths: Vec<PathBuf>,
         download_dir: String,
-        mod_id: String,
     },
 
     DeleteModFile {
@@ -72,6 +224,15 @@ pub enum Effect {
         filename: String,
     },
 
+    DeleteUnknownFiles {
+        paths: Vec<PathBuf>,
+    },
+
+    DeleteOrphanedFiles {
+        download_dir: String,
+        mod_ids: Vec<String>,
+    },
+
     ArchiveModFile {
         download_dir: String,
         mod_id: String,
@@ -82,7 +243,22 @@ pub enum Effect {
         mod_id: String,
     },
 
+    SetModFileEnabled {
+        download_dir: String,
+        mod_id: String,
+        enabled: bool,
+    },
+
     ValidateMetadata {
         download_dir: String,
     },
+    VerifyList {
+        list: ModList,
+        download_dir: String,
+        // Hash every file instead of only checking that it exists
+        full: bool,
+    },
+    WatchDownloadDir {
+        download_dir: String,
+    },
 }
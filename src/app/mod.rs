@@ -2,16 +2,25 @@ mod app_state;
 mod effect;
 mod runtime;
 
+use crate::domain::{
+    DownloadFailure, DownloadRecord, ModEntry, ModInfo, ModList, ProviderKind, ReleaseChannel,
+    TransferProgress,
+};
+use crate::infra::VerifyResult;
 pub use app_state::AppState;
 pub use effect::Effect;
 pub use runtime::AppRuntime;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(PartialEq, Default)]
 pub enum ListAction {
     #[default]
     Import,
     Duplicate,
+    ShareLink,
 }
 
 #[derive(PartialEq)]
@@ -31,13 +40,256 @@ pub enum LegacyState {
     },
 }
 
+#[derive(Clone)]
+pub struct LowDiskSpace {
+    pub mod_ids: Vec<String>,
+    pub needed: u64,
+    pub available: u64,
+}
+
+// How far back the transfer speed for the ETA is averaged
+const SPEED_WINDOW: Duration = Duration::from_secs(10);
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+// Downloads started together by Download All, with the expected size of each (0 when unknown)
+#[derive(Default)]
+pub struct BatchProgress {
+    pub sizes: HashMap<String, u64>,
+    // Last byte count reported per download, progress reports are turned into deltas
+    received: HashMap<String, u64>,
+    received_total: u64,
+    // (time, bytes received by the batch so far), trimmed to SPEED_WINDOW
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl BatchProgress {
+    pub fn record(&mut self, mod_id: &str, progress: TransferProgress) {
+        let Some(size) = self.sizes.get_mut(mod_id) else {
+            return;
+        };
+        if *size == 0 {
+            *size = progress.total;
+        }
+
+        // The first report is only a baseline so resumed bytes don't count as speed,
+        // and a restarted download reporting fewer bytes starts a new one
+        if let Some(previous) = self
+            .received
+            .insert(mod_id.to_string(), progress.downloaded)
+        {
+            self.received_total += progress.downloaded.saturating_sub(previous);
+        }
+
+        let now = Instant::now();
+        if self
+            .samples
+            .back()
+            .is_some_and(|(at, _)| now.duration_since(*at) < SPEED_SAMPLE_INTERVAL)
+        {
+            return;
+        }
+        self.samples.push_back((now, self.received_total));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > SPEED_WINDOW)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    // Bytes per second over the recent window, None until there's enough to go on or when stalled
+    pub fn speed(&self) -> Option<f64> {
+        let (first_at, first_bytes) = *self.samples.front()?;
+        let (last_at, last_bytes) = *self.samples.back()?;
+        if last_at.elapsed() > SPEED_WINDOW {
+            return None;
+        }
+        let elapsed = last_at.duration_since(first_at).as_secs_f64();
+        if elapsed < 1.0 || last_bytes <= first_bytes {
+            return None;
+        }
+        Some((last_bytes - first_bytes) as f64 / elapsed)
+    }
+}
+
+// Where a bundle member goes once its details for the list's version and loader are known
+pub struct BundleTarget {
+    pub list_id: String,
+    pub download_dir: String,
+    pub game_version: String,
+    pub filename_override: Option<String>,
+    pub disabled: bool,
+}
+
+pub struct Alternatives {
+    pub mod_id: String,
+    pub mod_name: String,
+    // None while the search is still running
+    pub results: Option<Vec<Arc<ModInfo>>>,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum MatrixCell {
+    Loading,
+    // Holds the project version that would be picked for the target
+    Available(String),
+    Unavailable,
+    Failed,
+}
+
+pub struct CompatibilityMatrix {
+    pub list_id: String,
+    pub loader: String,
+    pub channel: ReleaseChannel,
+    pub versions: Vec<String>,
+    // Keyed by (mod id, Minecraft version)
+    pub cells: HashMap<(String, String), MatrixCell>,
+}
+
+impl CompatibilityMatrix {
+    pub fn cell(&self, mod_id: &str, version: &str) -> Option<&MatrixCell> {
+        self.cells.get(&(mod_id.to_string(), version.to_string()))
+    }
+
+    pub fn available_count(&self, version: &str) -> usize {
+        self.cells
+            .iter()
+            .filter(|((_, v), cell)| v == version && matches!(cell, MatrixCell::Available(_)))
+            .count()
+    }
+}
+
+const UNDO_STACK_LIMIT: usize = 20;
+
+// The inverse of a destructive list operation
+pub enum UndoAction {
+    // Removed entries with their former positions, ascending. Deleted files are not restored.
+    RestoreEntries {
+        list_id: String,
+        entries: Vec<(usize, ModEntry)>,
+    },
+    RestoreList {
+        list: Box<ModList>,
+        index: usize,
+    },
+    SetArchived {
+        list_id: String,
+        mod_ids: Vec<String>,
+        archived: bool,
+    },
+    RenameList {
+        list_id: String,
+        name: String,
+    },
+}
+
+impl UndoAction {
+    pub fn description(&self) -> &'static str {
+        match self {
+            UndoAction::RestoreEntries { .. } => "delete",
+            UndoAction::RestoreList { .. } => "list delete",
+            UndoAction::SetArchived { archived: true, .. } => "unarchive",
+            UndoAction::SetArchived {
+                archived: false, ..
+            } => "archive",
+            UndoAction::RenameList { .. } => "rename",
+        }
+    }
+}
+
+// In-memory only, so it starts empty on every launch
+#[derive(Default)]
+pub struct UndoStack {
+    actions: VecDeque<UndoAction>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, action: UndoAction) {
+        if self.actions.len() == UNDO_STACK_LIMIT {
+            self.actions.pop_front();
+        }
+        self.actions.push_back(action);
+    }
+
+    pub fn pop(&mut self) -> Option<UndoAction> {
+        self.actions.pop_back()
+    }
+
+    pub fn peek(&self) -> Option<&UndoAction> {
+        self.actions.back()
+    }
+}
+
+// One list's installed, unpinned mods for the background update check
+#[derive(Clone)]
+pub struct UpdateCheckTarget {
+    pub list_id: String,
+    pub download_dir: String,
+    pub version: String,
+    pub loader: String,
+    pub channel: ReleaseChannel,
+    pub mods: Vec<(ProviderKind, String)>,
+}
+
+// Mods added from a pasted list of slugs and links
+pub struct BulkAdd {
+    pub list_id: String,
+    pub total: usize,
+    pub added: Vec<String>,
+    pub already_present: Vec<String>,
+    // Input and why it couldn't be added
+    pub failed: Vec<(String, String)>,
+}
+
+impl BulkAdd {
+    pub fn finished(&self) -> usize {
+        self.added.len() + self.already_present.len() + self.failed.len()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.finished() >= self.total
+    }
+}
+
+pub struct LockfileInstall {
+    pub download_dir: std::path::PathBuf,
+    // (current, total, message) while files are being installed
+    pub progress: (usize, usize, String),
+    // Installed, already present and the errors once done
+    pub result: Option<(usize, usize, Vec<String>)>,
+}
+
+pub struct Verification {
+    pub list_id: String,
+    // Quick checks only look for missing files
+    pub full: bool,
+    // Files checked so far and total, while running
+    pub progress: (usize, usize),
+    // None while files are still being hashed
+    pub results: Option<Vec<VerifyResult>>,
+}
+
+#[derive(Default)]
+pub struct ListStats {
+    pub active: usize,
+    pub compatible: usize,
+    pub incompatible: usize,
+    pub downloaded: usize,
+    pub missing: usize,
+    pub total_downloads: u64,
+    // Entries whose details haven't loaded yet
+    pub unknown: usize,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DownloadStatus {
     Idle,
     Queued,
     Downloading,
+    Retrying { attempt: u32, max_attempts: u32 },
     Complete,
-    Failed,
+    Failed(DownloadFailure),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -45,6 +297,7 @@ pub enum SortMode {
     #[default]
     Name,
     DateAdded,
+    Manual,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -54,6 +307,7 @@ pub enum FilterMode {
     CompatibleOnly,
     IncompatibleOnly,
     MissingOnly,
+    UpdatableOnly,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -62,3 +316,37 @@ pub enum OrderMode {
     Ascending,
     Descending,
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum HistoryStatusFilter {
+    #[default]
+    All,
+    Succeeded,
+    Failed,
+}
+
+impl HistoryStatusFilter {
+    pub fn all() -> &'static [HistoryStatusFilter] {
+        &[
+            HistoryStatusFilter::All,
+            HistoryStatusFilter::Succeeded,
+            HistoryStatusFilter::Failed,
+        ]
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            HistoryStatusFilter::All => "All",
+            HistoryStatusFilter::Succeeded => "Succeeded",
+            HistoryStatusFilter::Failed => "Failed",
+        }
+    }
+
+    pub fn matches(&self, record: &DownloadRecord) -> bool {
+        match self {
+            HistoryStatusFilter::All => true,
+            HistoryStatusFilter::Succeeded => record.failure.is_none(),
+            HistoryStatusFilter::Failed => record.failure.is_some(),
+        }
+    }
+}
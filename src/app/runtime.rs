@@ -1,8 +1,23 @@
 use crate::app::Effect;
-use crate::domain::{AppConfig, Event, ModService, ProjectType};
-use crate::infra::{ApiService, ConfigManager, IconService, IconWorker, LegacyListService};
+use crate::domain::{
+    AppConfig, CacheTtl, CompatPolicy, DEFAULT_DOWNLOAD_RETRIES, DEFAULT_MAX_PARALLEL_DOWNLOADS,
+    DEFAULT_UPDATE_CHECK_HOURS, DISABLED_SUFFIX, DownloadCancelled, DownloadFailure,
+    DownloadRecord, Event, HashMismatch, InitialLoadStep, MAX_PARALLEL_DOWNLOADS_LIMIT, ModInfo,
+    ModProvider, ModService, NetworkTimeouts, ProjectType, ProviderKind, ProxySettings, SearchKey,
+    SearchSort, Theme,
+};
+use crate::infra::{
+    ApiService, ConfigManager, DirWatcher, DownloadHistory, GameDataService, IconResult,
+    IconService, IconWorker, LegacyListService, LockfileService, MrpackService, UrlListService,
+    is_retriable_error, retry_backoff, retry_rate_limited,
+};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::sync::{Notify, mpsc};
+
+const COMPATIBILITY_CHECK_CONCURRENCY: usize = 8;
+const SLUG_RESOLVE_CONCURRENCY: usize = 8;
+const UPDATE_CHECK_CONCURRENCY: usize = 8;
 
 pub struct AppRuntime {
     pub mod_service: Arc<ModService>,
@@ -14,6 +29,15 @@ pub struct AppRuntime {
 
     api_service: Arc<ApiService>,
     legacy_service: Arc<LegacyListService>,
+    mrpack_service: Arc<MrpackService>,
+    url_list_service: Arc<UrlListService>,
+    lockfile_service: Arc<LockfileService>,
+    game_data_service: Arc<GameDataService>,
+    download_history: Arc<DownloadHistory>,
+    dir_watcher: std::sync::Mutex<DirWatcher>,
+    skip_initial_load: Arc<Notify>,
+    // Set when quitting with downloads still running
+    downloads_cancelled: tokio::sync::watch::Sender<bool>,
 }
 
 impl AppRuntime {
@@ -28,8 +52,19 @@ impl AppRuntime {
             config_manager.get_cache_dir().to_path_buf(),
         ));
         let legacy_service = Arc::new(LegacyListService::new(mod_service.clone()));
+        let mrpack_service = Arc::new(MrpackService::new(mod_service.clone()));
+        let url_list_service = Arc::new(UrlListService::new(mod_service.clone()));
+        let lockfile_service = Arc::new(LockfileService::new(mod_service.clone()));
+        let game_data_service = Arc::new(GameDataService::new(
+            api_service.clone(),
+            config_manager.get_cache_dir(),
+        ));
+
+        let download_history = Arc::new(DownloadHistory::new(
+            config_manager.get_download_history_path(),
+        ));
 
-        let (icon_tx, icon_rx) = mpsc::channel::<(String, Vec<u8>)>(100);
+        let (icon_tx, icon_rx) = mpsc::channel::<IconResult>(100);
         let (icon_url_tx, icon_url_rx) = mpsc::channel::<String>(100);
 
         let icon_worker = IconWorker::new(
@@ -41,6 +76,49 @@ impl AppRuntime {
         rt_handle.spawn(icon_worker.run());
 
         let icon_service = IconService::new(icon_rx, icon_url_tx);
+        let dir_watcher = std::sync::Mutex::new(DirWatcher::new(event_tx.clone()));
+
+        let mut offline_rx = api_service.connectivity.subscribe();
+        let probe_api = api_service.clone();
+        let connectivity_tx = event_tx.clone();
+        rt_handle.spawn(async move {
+            let mut last_sent = false;
+            loop {
+                let offline = *offline_rx.borrow_and_update();
+                if offline != last_sent {
+                    last_sent = offline;
+                    let _ = connectivity_tx
+                        .send(Event::ConnectivityChanged { offline })
+                        .await;
+                }
+
+                if offline {
+                    tokio::select! {
+                        changed = offline_rx.changed() => if changed.is_err() { break },
+                        _ = tokio::time::sleep(tokio::time::Duration::from_secs(30)) => {
+                            probe_api.track(probe_api.modrinth.get_minecraft_versions().await).ok();
+                        }
+                    }
+                } else if offline_rx.changed().await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut rate_limited_rx = api_service.modrinth.subscribe_rate_limited();
+        let rate_limit_tx = event_tx.clone();
+        rt_handle.spawn(async move {
+            while rate_limited_rx.changed().await.is_ok() {
+                let waiting = *rate_limited_rx.borrow_and_update();
+                if rate_limit_tx
+                    .send(Event::RateLimitWaiting { waiting })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
 
         let disk_cache = mod_service.get_disk_cache();
         rt_handle.spawn(async move {
@@ -59,17 +137,71 @@ impl AppRuntime {
                 event_tx,
                 api_service,
                 legacy_service,
+                mrpack_service,
+                url_list_service,
+                lockfile_service,
+                game_data_service,
+                download_history,
+                dir_watcher,
+                skip_initial_load: Arc::new(Notify::new()),
+                downloads_cancelled: tokio::sync::watch::Sender::new(false),
             },
             event_rx,
         )
     }
 
-    pub fn get_project_link(&self, project_type: &ProjectType, mod_id: &str) -> String {
+    // An invalid proxy keeps the previous client and is reported instead of silently ignored
+    async fn apply_http_settings(
+        api_svc: &ApiService,
+        config: &AppConfig,
+        tx: &mpsc::Sender<Event>,
+    ) {
+        let error = match crate::infra::build_client(
+            &config.proxy,
+            config.timeouts,
+            &config.user_agent_contact,
+        ) {
+            Ok(client) => {
+                api_svc.set_client(client);
+                None
+            }
+            Err(e) => {
+                log::error!("Failed to apply proxy settings: {e:#}");
+                Some(format!("{e:#}"))
+            }
+        };
+        let _ = tx.send(Event::HttpClientConfigured { error }).await;
+    }
+
+    async fn match_dropped_file(
+        api_svc: &ApiService,
+        path: &std::path::Path,
+    ) -> anyhow::Result<Option<ModInfo>> {
+        use sha1::{Digest, Sha1};
+
+        let bytes = tokio::fs::read(path).await?;
+        let sha1 = crate::domain::to_hex(&Sha1::digest(&bytes));
+
+        let _permit = api_svc.limiter.acquire(1).await;
+        api_svc.track(retry_rate_limited(|| api_svc.modrinth.find_by_hash(&sha1)).await)
+    }
+
+    pub fn get_project_link(
+        &self,
+        provider: ProviderKind,
+        project_type: &ProjectType,
+        mod_id: &str,
+    ) -> String {
         self.api_service
-            .provider
+            .provider(provider)
             .get_project_link(project_type, mod_id)
     }
 
+    // Running downloads stop and report as cancelled, new ones are cancelled right away
+    pub fn cancel_downloads(&self, cancel: bool) {
+        self.downloads_cancelled.send_replace(cancel);
+    }
+
     pub fn enqueue(&self, effect: Effect) {
         self.run_effect(effect);
     }
@@ -84,24 +216,81 @@ impl AppRuntime {
         match effect {
             Effect::LoadInitialData => {
                 let cm = self.config_manager.clone();
-                let prov = self.api_service.provider.clone();
+                let game_data = self.game_data_service.clone();
+                let curseforge = self.api_service.curseforge.clone();
+                let api_service = self.api_service.clone();
+                let modrinth = self.api_service.modrinth.clone();
+                let download_limiter = self.api_service.download_limiter.clone();
+                let history = self.download_history.clone();
+                let mod_service = self.mod_service.clone();
+                let skip = self.skip_initial_load.clone();
                 let tx = self.event_tx.clone();
 
                 self.rt_handle.spawn(async move {
+                    let progress = |step| {
+                        let tx = tx.clone();
+                        async move {
+                            let _ = tx.send(Event::InitialLoadProgress { step }).await;
+                        }
+                    };
+
+                    progress(InitialLoadStep::Config).await;
                     let _ = cm.ensure_dirs().await;
 
                     let config = if cm.config_exists() {
                         cm.load_config().await.unwrap_or_else(|_| AppConfig {
                             current_list_id: None,
                             default_list_name: "New List".to_string(),
+                            curseforge_api_key: String::new(),
+                            modrinth_api_url: String::new(),
+                            user_agent_contact: String::new(),
+                            proxy: ProxySettings::default(),
+                            timeouts: NetworkTimeouts::default(),
+                            max_parallel_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+                            download_retries: DEFAULT_DOWNLOAD_RETRIES,
+                            clean_replaced_files: false,
+                            update_check_hours: DEFAULT_UPDATE_CHECK_HOURS,
+                            cache_ttl: CacheTtl::default(),
+                            compat_policy: CompatPolicy::default(),
+                            default_dirs: HashMap::new(),
+                            bundles: Vec::new(),
+                            window: None,
+                            theme: Theme::default(),
                         })
                     } else {
                         cm.create_default_config().await.unwrap_or(AppConfig {
                             current_list_id: None,
                             default_list_name: "New List".to_string(),
+                            curseforge_api_key: String::new(),
+                            modrinth_api_url: String::new(),
+                            user_agent_contact: String::new(),
+                            proxy: ProxySettings::default(),
+                            timeouts: NetworkTimeouts::default(),
+                            max_parallel_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+                            download_retries: DEFAULT_DOWNLOAD_RETRIES,
+                            clean_replaced_files: false,
+                            update_check_hours: DEFAULT_UPDATE_CHECK_HOURS,
+                            cache_ttl: CacheTtl::default(),
+                            compat_policy: CompatPolicy::default(),
+                            default_dirs: HashMap::new(),
+                            bundles: Vec::new(),
+                            window: None,
+                            theme: Theme::default(),
                         })
                     };
 
+                    curseforge.set_api_key(config.curseforge_api_key.clone());
+                    modrinth.set_base_url(&config.modrinth_api_url);
+                    Self::apply_http_settings(&api_service, &config, &tx).await;
+                    download_limiter.set_max_connections(
+                        config
+                            .max_parallel_downloads
+                            .clamp(1, MAX_PARALLEL_DOWNLOADS_LIMIT),
+                    );
+
+                    mod_service.set_cache_ttl(config.cache_ttl).await;
+
+                    progress(InitialLoadStep::Lists).await;
                     let lists = cm.load_all_lists().await.unwrap_or_default();
 
                     let current_list_id = config
@@ -109,17 +298,35 @@ impl AppRuntime {
                         .clone()
                         .filter(|id| lists.iter().any(|l| &l.id == id));
 
-                    let versions = prov.get_minecraft_versions().await.unwrap_or_else(|_| {
-                        vec![crate::domain::MinecraftVersion {
-                            id: "1.20.1".to_string(),
-                            name: "1.20.1".to_string(),
-                        }]
-                    });
+                    // Once skipped, the remaining network steps read whatever is cached
+                    let mut skipped = false;
 
-                    let loaders = prov
-                        .get_mod_loaders_for_type(ProjectType::Mod)
-                        .await
-                        .unwrap_or_default();
+                    progress(InitialLoadStep::Versions).await;
+                    let versions = tokio::select! {
+                        versions = game_data.minecraft_versions(false) => versions,
+                        _ = skip.notified() => {
+                            skipped = true;
+                            game_data.cached_minecraft_versions().await
+                        }
+                    };
+
+                    progress(InitialLoadStep::Loaders).await;
+                    let loaders = if skipped {
+                        game_data.cached_loaders_for_type(ProjectType::Mod).await
+                    } else {
+                        tokio::select! {
+                            loaders = game_data.loaders_for_type(ProjectType::Mod, false) => loaders,
+                            _ = skip.notified() => {
+                                game_data.cached_loaders_for_type(ProjectType::Mod).await
+                            }
+                        }
+                    };
+
+                    progress(InitialLoadStep::History).await;
+                    let download_history = history.load().await.unwrap_or_else(|e| {
+                        log::warn!("Failed to load download history: {e}");
+                        Vec::new()
+                    });
 
                     let _ = tx
                         .send(Event::InitialDataLoaded {
@@ -128,21 +335,54 @@ impl AppRuntime {
                             minecraft_versions: versions,
                             mod_loaders: loaders,
                             default_list_name: config.default_list_name,
+                            curseforge_api_key: config.curseforge_api_key,
+                            modrinth_api_url: config.modrinth_api_url,
+                            user_agent_contact: config.user_agent_contact,
+                            proxy: Box::new(config.proxy),
+                            timeouts: config.timeouts,
+                            max_parallel_downloads: config.max_parallel_downloads,
+                            download_retries: config.download_retries,
+                            clean_replaced_files: config.clean_replaced_files,
+                            update_check_hours: config.update_check_hours,
+                            cache_ttl: Box::new(config.cache_ttl),
+                            compat_policy: config.compat_policy,
+                            default_dirs: config.default_dirs,
+                            bundles: config.bundles,
+                            download_history,
+                        })
+                        .await;
+                });
+            }
+
+            Effect::SkipInitialLoad => {
+                log::info!("Skipping initial network load, working offline");
+                self.api_service.connectivity.mark_offline();
+                self.skip_initial_load.notify_one();
+            }
+
+            Effect::LoadLoaderVersions { version, loader } => {
+                let game_data = self.game_data_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let versions = game_data.loader_versions(&version, &loader).await;
+
+                    let _ = tx
+                        .send(Event::LoaderVersionsLoaded {
+                            version,
+                            loader,
+                            versions,
                         })
                         .await;
                 });
             }
 
             Effect::LoadLoadersForType { project_type } => {
-                let api_svc = self.api_service.clone();
+                let game_data = self.game_data_service.clone();
                 let tx = self.event_tx.clone();
 
                 self.rt_handle.spawn(async move {
-                    let loaders = api_svc
-                        .provider
-                        .get_mod_loaders_for_type(project_type)
-                        .await
-                        .unwrap_or_default();
+                    let loaders = game_data.loaders_for_type(project_type, false).await;
 
                     let _ = tx
                         .send(Event::LoadersForTypeLoaded {
@@ -153,11 +393,68 @@ impl AppRuntime {
                 });
             }
 
+            Effect::LoadCategories {
+                provider,
+                project_type,
+            } => {
+                let api_svc = self.api_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let categories = api_svc
+                        .track(
+                            api_svc
+                                .provider(provider)
+                                .fetch_categories(project_type)
+                                .await,
+                        )
+                        .unwrap_or_else(|e| {
+                            log::warn!(
+                                "Failed to load {} categories: {e}",
+                                provider.display_name()
+                            );
+                            Vec::new()
+                        });
+
+                    let _ = tx
+                        .send(Event::CategoriesLoaded {
+                            provider,
+                            project_type,
+                            categories,
+                        })
+                        .await;
+                });
+            }
+
+            Effect::RefreshGameData { project_types } => {
+                let game_data = self.game_data_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let versions = game_data.minecraft_versions(true).await;
+                    let _ = tx.send(Event::MinecraftVersionsLoaded(versions)).await;
+
+                    for project_type in project_types {
+                        let loaders = game_data.loaders_for_type(project_type, true).await;
+                        let _ = tx
+                            .send(Event::LoadersForTypeLoaded {
+                                project_type,
+                                loaders,
+                            })
+                            .await;
+                    }
+                });
+            }
+
             Effect::SearchMods {
                 query,
                 version,
                 loader,
                 project_type,
+                provider,
+                categories,
+                sort,
+                force_refresh,
             } => {
                 let api_svc = self.api_service.clone();
                 let mod_svc = self.mod_service.clone();
@@ -166,104 +463,716 @@ impl AppRuntime {
                 let loader_clone = loader.clone();
 
                 self.rt_handle.spawn(async move {
+                    let key = SearchKey::new(
+                        provider,
+                        &query,
+                        project_type,
+                        &version,
+                        &loader,
+                        &categories,
+                        sort,
+                    );
+                    if force_refresh {
+                        mod_svc.forget_search(&key).await;
+                    } else if let Some(cached) = mod_svc.get_search(&key).await {
+                        log::debug!("Returning cached search results for {query}");
+                        let _ = tx.send(Event::SearchResults(cached)).await;
+                        return;
+                    }
+
                     let _permit = api_svc.limiter.acquire(1).await;
+                    let mod_provider = api_svc.provider(provider);
 
-                    match api_svc
-                        .provider
-                        .search_mods(&query, &version, &loader, &project_type)
-                        .await
-                    {
+                    match api_svc.track(
+                        retry_rate_limited(|| {
+                            mod_provider.search_mods(
+                                &query,
+                                &version,
+                                &loader,
+                                &project_type,
+                                &categories,
+                                sort,
+                            )
+                        })
+                        .await,
+                    ) {
                         Ok(results) => {
                             let cached = mod_svc
                                 .cache_search_results(results, ver_clone, loader_clone)
                                 .await;
+                            mod_svc.remember_search(key, cached.clone()).await;
                             let _ = tx.send(Event::SearchResults(cached)).await;
                         }
-                        Err(_) => {
-                            log::warn!("Failed to search: {query}");
+                        Err(e) => {
+                            log::warn!(
+                                "Failed to search {}: {query} ({e})",
+                                provider.display_name()
+                            );
                         }
                     }
                 });
             }
 
+            Effect::FindAlternatives {
+                mod_id,
+                query,
+                version,
+                loader,
+                project_type,
+                provider,
+            } => {
+                let api_svc = self.api_service.clone();
+                let mod_svc = self.mod_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let _permit = api_svc.limiter.acquire(1).await;
+                    let mod_provider = api_svc.provider(provider);
+
+                    let results = api_svc
+                        .track(
+                            retry_rate_limited(|| {
+                                mod_provider.search_mods(
+                                    &query,
+                                    &version,
+                                    &loader,
+                                    &project_type,
+                                    &[],
+                                    SearchSort::Relevance,
+                                )
+                            })
+                            .await,
+                        )
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to search alternatives for {query}: {e}");
+                            Vec::new()
+                        });
+
+                    let results = mod_svc.cache_search_results(results, version, loader).await;
+                    let _ = tx.send(Event::AlternativesLoaded { mod_id, results }).await;
+                });
+            }
+
+            Effect::CheckCompatibility {
+                cells,
+                loader,
+                channel,
+            } => {
+                use futures_util::StreamExt;
+
+                let mod_svc = self.mod_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    // The API limiter bounds requests, this only caps the number of waiting tasks
+                    futures_util::stream::iter(cells)
+                        .for_each_concurrent(
+                            COMPATIBILITY_CHECK_CONCURRENCY,
+                            |(provider, mod_id, version)| {
+                                let mod_svc = &mod_svc;
+                                let tx = &tx;
+                                let loader = &loader;
+                                async move {
+                                    let info = mod_svc
+                                        .get_mod_by_id(provider, &mod_id, &version, loader, channel)
+                                        .await
+                                        .inspect_err(|e| {
+                                            log::debug!("No details for {mod_id} on {version}: {e}")
+                                        })
+                                        .ok();
+
+                                    let _ = tx
+                                        .send(Event::CompatibilityChecked {
+                                            mod_id,
+                                            version,
+                                            info,
+                                        })
+                                        .await;
+                                }
+                            },
+                        )
+                        .await;
+                });
+            }
+
+            Effect::CheckForUpdates { targets } => {
+                use futures_util::StreamExt;
+
+                let mod_svc = self.mod_service.clone();
+                let api_svc = self.api_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let mut updates: HashMap<String, Vec<String>> = HashMap::new();
+
+                    for target in targets {
+                        if api_svc.connectivity.is_offline() {
+                            break;
+                        }
+
+                        let download_dir = std::path::Path::new(&target.download_dir);
+                        let Ok(metadata) = crate::infra::read_download_metadata(download_dir).await
+                        else {
+                            continue;
+                        };
+                        let installed: Vec<_> = target
+                            .mods
+                            .iter()
+                            .filter_map(|(provider, mod_id)| {
+                                let entry = metadata.get_entry(mod_id)?;
+                                download_dir
+                                    .join(&entry.file)
+                                    .exists()
+                                    .then(|| (*provider, mod_id.clone(), entry.version.clone()))
+                            })
+                            .collect();
+
+                        let outdated: Vec<String> = futures_util::stream::iter(installed)
+                            .map(|(provider, mod_id, installed_version)| {
+                                let api_svc = &api_svc;
+                                let target = &target;
+                                async move {
+                                    let _permit = api_svc.limiter.acquire(1).await;
+                                    let mod_provider = api_svc.provider(provider);
+                                    let versions = api_svc
+                                        .track(
+                                            retry_rate_limited(|| {
+                                                mod_provider
+                                                    .fetch_project_versions(&mod_id, &target.loader)
+                                            })
+                                            .await,
+                                        )
+                                        .inspect_err(|e| {
+                                            log::debug!("Update check failed for {mod_id}: {e}")
+                                        })
+                                        .ok()?;
+
+                                    let latest = versions.iter().find(|v| {
+                                        target.channel.allows(v.channel)
+                                            && v.game_versions.contains(&target.version)
+                                    })?;
+                                    (latest.name != installed_version).then_some(mod_id)
+                                }
+                            })
+                            .buffer_unordered(UPDATE_CHECK_CONCURRENCY)
+                            .filter_map(std::future::ready)
+                            .collect()
+                            .await;
+
+                        for mod_id in &outdated {
+                            mod_svc
+                                .forget_mod(mod_id, &target.version, &target.loader, target.channel)
+                                .await;
+                        }
+                        if !outdated.is_empty() {
+                            updates.insert(target.list_id, outdated);
+                        }
+                    }
+
+                    let _ = tx.send(Event::UpdateCheckFinished { updates }).await;
+                });
+            }
+
+            Effect::ResolveSlugs {
+                list_id,
+                slugs,
+                version,
+                loader,
+                channel,
+            } => {
+                use futures_util::StreamExt;
+
+                let mod_svc = self.mod_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    futures_util::stream::iter(slugs)
+                        .for_each_concurrent(SLUG_RESOLVE_CONCURRENCY, |slug| {
+                            let mod_svc = &mod_svc;
+                            let tx = &tx;
+                            let list_id = list_id.clone();
+                            let version = &version;
+                            let loader = &loader;
+                            async move {
+                                let result = mod_svc
+                                    .get_mod_by_slug(
+                                        ProviderKind::Modrinth,
+                                        &slug,
+                                        version,
+                                        loader,
+                                        channel,
+                                    )
+                                    .await
+                                    .map_err(|e| {
+                                        log::warn!("Failed to resolve slug '{slug}': {e}");
+                                        format!("{e:#}")
+                                    });
+
+                                let _ = tx
+                                    .send(Event::SlugResolved {
+                                        list_id,
+                                        slug,
+                                        result,
+                                    })
+                                    .await;
+                            }
+                        })
+                        .await;
+                });
+            }
+
             Effect::FetchModDetails {
+                provider,
                 mod_id,
+                pinned_version,
                 version,
                 loader,
+                channel,
+                offline,
             } => {
                 let mod_svc = self.mod_service.clone();
+                let api_svc = self.api_service.clone();
                 let tx = self.event_tx.clone();
-                let version_clone = version.clone();
-                let loader_clone = loader.clone();
 
                 self.rt_handle.spawn(async move {
-                    match mod_svc.get_mod_by_id(&mod_id, &version, &loader).await {
-                        Ok(info) => {
+                    // Show what was cached last time right away, the fetch below replaces it in place
+                    let expired = if offline {
+                        None
+                    } else {
+                        mod_svc
+                            .get_expired_mod(
+                                &mod_id,
+                                pinned_version.as_deref(),
+                                &version,
+                                &loader,
+                                channel,
+                            )
+                            .await
+                    };
+                    if let Some(info) = &expired {
+                        let _ = tx
+                            .send(Event::ModDetails {
+                                requested_id: mod_id.clone(),
+                                info: info.clone(),
+                                version: version.clone(),
+                                loader: loader.clone(),
+                                stale: true,
+                                revalidating: true,
+                            })
+                            .await;
+                    }
+
+                    let result = if offline {
+                        Err(anyhow::anyhow!("offline"))
+                    } else {
+                        match &pinned_version {
+                            Some(version_id) => {
+                                mod_svc.get_pinned_mod(provider, &mod_id, version_id).await
+                            }
+                            None => {
+                                mod_svc
+                                    .get_mod_by_id(provider, &mod_id, &version, &loader, channel)
+                                    .await
+                            }
+                        }
+                    };
+
+                    let (info, stale, error) = match result {
+                        Ok(info) => (Some(info), false, None),
+                        Err(e) if offline || api_svc.connectivity.is_offline() => {
+                            log::debug!("Serving cached details for {mod_id} ({e})");
+                            let cached = mod_svc
+                                .get_cached_mod(
+                                    &mod_id,
+                                    pinned_version.as_deref(),
+                                    &version,
+                                    &loader,
+                                    channel,
+                                )
+                                .await;
+                            (cached, true, Some(format!("{e:#}")))
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to fetch details for {mod_id}: {e}");
+                            (expired, true, Some(format!("{e:#}")))
+                        }
+                    };
+
+                    match info {
+                        Some(info) => {
                             let _ = tx
                                 .send(Event::ModDetails {
+                                    requested_id: mod_id,
                                     info,
-                                    version: version_clone,
-                                    loader: loader_clone,
+                                    version,
+                                    loader,
+                                    stale,
+                                    revalidating: false,
                                 })
                                 .await;
                         }
-                        Err(e) => {
-                            log::warn!("Failed to fetch details for {mod_id}: {e}");
-                            let _ = tx.send(Event::ModDetailsFailed { mod_id }).await;
+                        None => {
+                            let _ = tx
+                                .send(Event::ModDetailsFailed {
+                                    mod_id,
+                                    error: error.unwrap_or_default(),
+                                })
+                                .await;
                         }
                     }
                 });
             }
 
+            Effect::ResolveDependencies {
+                mod_info,
+                version,
+                loader,
+                channel,
+                known_ids,
+            } => {
+                let mod_svc = self.mod_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let dependencies = mod_svc
+                        .resolve_required_dependencies(
+                            &mod_info,
+                            &version,
+                            &loader,
+                            channel,
+                            known_ids.into_iter().collect(),
+                        )
+                        .await;
+
+                    if !dependencies.is_empty() {
+                        let _ = tx
+                            .send(Event::DependenciesResolved {
+                                mod_info,
+                                dependencies,
+                            })
+                            .await;
+                    }
+                });
+            }
+
             Effect::DownloadMod {
                 mod_info,
+                filename_override,
+                disabled,
                 download_dir,
+                list_id,
+                game_version,
+                max_retries,
+                clean_replaced,
             } => {
                 let api_svc = self.api_service.clone();
                 let tx = self.event_tx.clone();
+                let mut cancelled = self.downloads_cancelled.subscribe();
 
                 self.rt_handle.spawn(async move {
-                    let _permit = api_svc.limiter.acquire(3).await;
-
                     let mod_id = mod_info.id.clone();
                     let mod_version = mod_info.version.clone();
-                    let filename = crate::domain::generate_mod_filename(&mod_info);
+
+                    let dir = std::path::PathBuf::from(&download_dir);
+                    let hashes = mod_info.hashes.clone();
+                    let file_size = mod_info.file_size;
+                    let identical_file = tokio::task::spawn_blocking(move || {
+                        crate::infra::find_identical_file(&dir, &hashes, file_size)
+                    })
+                    .await
+                    .ok()
+                    .flatten()
+                    // An identical copy only counts if it already has the requested name
+                    .filter(|file| filename_override.as_ref().is_none_or(|name| name == file))
+                    // Disabled files are never picked up, so a disabled mod always gets its own copy
+                    .filter(|_| !disabled);
+
+                    let filename = identical_file.clone().unwrap_or_else(|| {
+                        let filename = filename_override
+                            .clone()
+                            .unwrap_or_else(|| crate::domain::generate_mod_filename(&mod_info));
+                        if disabled {
+                            format!("{filename}{DISABLED_SUFFIX}")
+                        } else {
+                            filename
+                        }
+                    });
+                    let base_name = filename
+                        .strip_suffix(DISABLED_SUFFIX)
+                        .unwrap_or(&filename)
+                        .to_string();
                     let destination = std::path::Path::new(&download_dir).join(&filename);
 
-                    let tx_progress = tx.clone();
-                    let mod_id_clone = mod_id.clone();
+                    // Only a file recorded for this entry alone is ever cleaned up
+                    let previous_file = if clean_replaced {
+                        crate::infra::read_download_metadata(std::path::Path::new(&download_dir))
+                            .await
+                            .ok()
+                            .and_then(|m| {
+                                let file = m.get_entry(&mod_id)?.file.clone();
+                                let shared = m
+                                    .mods
+                                    .iter()
+                                    .any(|(id, entry)| *id != mod_id && entry.file == file);
+                                (file != filename && !shared).then_some(file)
+                            })
+                    } else {
+                        None
+                    };
 
-                    let result = api_svc
-                        .provider
-                        .download_mod(
-                            &mod_info.download_url,
+                    let result = if let Some(existing) = identical_file {
+                        log::info!("{mod_id} is already present as {existing}, skipping download");
+                        let _ = tx
+                            .send(Event::DownloadDeduped {
+                                mod_id: mod_id.clone(),
+                                file: existing,
+                            })
+                            .await;
+                        Ok(())
+                    } else {
+                        let transfer = download_with_retries(
+                            &api_svc,
+                            &mod_info,
                             &destination,
-                            Box::new(move |progress| {
-                                let _ = tx_progress.try_send(Event::DownloadProgress {
-                                    mod_id: mod_id_clone.clone(),
-                                    progress,
-                                });
-                            }),
-                        )
-                        .await;
+                            max_retries,
+                            &tx,
+                        );
+                        tokio::select! {
+                            result = transfer => result,
+                            // Dropping the transfer keeps its .part file for resuming later,
+                            // only verified files are ever renamed into place
+                            Ok(_) = cancelled.wait_for(|cancelled| *cancelled) => {
+                                log::info!("Download of {mod_id} cancelled");
+                                Err(anyhow::Error::new(DownloadCancelled))
+                            }
+                        }
+                    };
 
-                    let success = result.is_ok();
+                    let failure = match &result {
+                        Ok(()) => None,
+                        Err(e) if e.is::<DownloadCancelled>() => Some(DownloadFailure::Cancelled),
+                        Err(e) if e.is::<HashMismatch>() => {
+                            log::error!("Download of {mod_id} failed verification: {e}");
+                            Some(DownloadFailure::HashMismatch)
+                        }
+                        Err(e) => {
+                            log::error!("Download of {mod_id} failed: {e}");
+                            Some(DownloadFailure::Network)
+                        }
+                    };
 
-                    if success {
+                    if failure.is_none() {
                         let download_path = std::path::Path::new(&download_dir);
+
+                        if let Some(previous_file) = previous_file {
+                            let previous_path = download_path.join(&previous_file);
+                            if previous_path.exists() {
+                                if let Err(e) = tokio::fs::remove_file(&previous_path).await {
+                                    log::warn!(
+                                        "Failed to remove replaced file {previous_file}: {e}"
+                                    );
+                                } else {
+                                    log::info!("Removed replaced file: {previous_file}");
+                                }
+                            }
+                        }
+
                         if let Err(e) = crate::infra::update_metadata_entry(
                             download_path,
                             mod_id.clone(),
-                            filename,
-                            mod_version,
+                            filename.clone(),
+                            mod_version.clone(),
+                            mod_info.hashes.clone(),
                         )
                         .await
                         {
                             log::warn!("Failed to update download metadata: {e}");
                         }
-                    }
 
-                    let _ = tx.send(Event::DownloadComplete { mod_id, success }).await;
+                        if base_name.ends_with(".jar") {
+                            let jar_path = destination.clone();
+                            let target = game_version.clone();
+                            let mismatch = tokio::task::spawn_blocking(move || {
+                                crate::infra::find_version_mismatch(&jar_path, &target)
+                            })
+                            .await;
+
+                            match mismatch {
+                                Ok(Ok(Some(requirement))) => {
+                                    log::warn!(
+                                        "{filename} declares Minecraft {requirement}, list targets {game_version}"
+                                    );
+                                    let _ = tx
+                                        .send(Event::JarVersionMismatch {
+                                            mod_id: mod_id.clone(),
+                                            requirement,
+                                        })
+                                        .await;
+                                }
+                                Ok(Ok(None)) => {}
+                                Ok(Err(e)) => {
+                                    log::warn!("Failed to read mod metadata from {filename}: {e}")
+                                }
+                                Err(e) => log::warn!("Jar validation task failed: {e}"),
+                            }
+                        } else if base_name.ends_with(".zip")
+                            && mod_info.project_type == ProjectType::ResourcePack
+                        {
+                            let pack_path = destination.clone();
+                            let target = game_version.clone();
+                            let mismatch = tokio::task::spawn_blocking(move || {
+                                crate::infra::validate_resource_pack(&pack_path, &target)
+                            })
+                            .await;
+
+                            match mismatch {
+                                Ok(Ok(Some(declared))) => {
+                                    log::warn!(
+                                        "{filename} declares pack {declared}, list targets {game_version}"
+                                    );
+                                    let _ = tx
+                                        .send(Event::PackFormatMismatch {
+                                            mod_id: mod_id.clone(),
+                                            declared,
+                                        })
+                                        .await;
+                                }
+                                Ok(Ok(None)) => {}
+                                Ok(Err(e)) => {
+                                    log::warn!("Failed to read pack.mcmeta from {filename}: {e}")
+                                }
+                                Err(e) => log::warn!("Resource pack validation task failed: {e}"),
+                            }
+                        }
+                    }
+
+                    let record = DownloadRecord {
+                        mod_id,
+                        mod_name: mod_info.name.clone(),
+                        list_id,
+                        file_name: filename,
+                        version: mod_version,
+                        timestamp: chrono::Utc::now(),
+                        failure,
+                        error: result.err().map(|e| format!("{e:#}")),
+                    };
+                    let _ = tx.send(Event::DownloadComplete { record }).await;
+                });
+            }
+
+            Effect::FetchChangelog {
+                provider,
+                mod_id,
+                version_id,
+            } => {
+                let api_svc = self.api_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let changelog = api_svc
+                        .track(
+                            api_svc
+                                .provider(provider)
+                                .fetch_changelog(&mod_id, &version_id)
+                                .await,
+                        )
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to load changelog for {mod_id}: {e}");
+                            None
+                        });
+
+                    let _ = tx
+                        .send(Event::ChangelogLoaded {
+                            version_id,
+                            changelog,
+                        })
+                        .await;
+                });
+            }
+
+            Effect::FetchProjectVersions {
+                provider,
+                mod_id,
+                loader,
+            } => {
+                let api_svc = self.api_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let versions = api_svc
+                        .track(
+                            api_svc
+                                .provider(provider)
+                                .fetch_project_versions(&mod_id, &loader)
+                                .await,
+                        )
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to load versions for {mod_id}: {e}");
+                            Vec::new()
+                        });
+
+                    let _ = tx
+                        .send(Event::ProjectVersionsLoaded { mod_id, versions })
+                        .await;
+                });
+            }
+
+            Effect::LoadChosenVersion {
+                provider,
+                mod_id,
+                version_id,
+            } => {
+                let mod_service = self.mod_service.clone();
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let mod_info = mod_service
+                        .get_pinned_mod(provider, &mod_id, &version_id)
+                        .await
+                        .inspect_err(|e| {
+                            log::warn!("Failed to load version {version_id} of {mod_id}: {e}")
+                        })
+                        .ok();
+
+                    let _ = tx
+                        .send(Event::ChosenVersionLoaded {
+                            mod_id,
+                            version_id,
+                            mod_info,
+                        })
+                        .await;
+                });
+            }
+
+            Effect::CheckDiskSpace {
+                download_dir,
+                needed,
+                mod_ids,
+            } => {
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let space = match crate::infra::check_disk_space(
+                        std::path::Path::new(&download_dir),
+                        needed,
+                    )
+                    .await
+                    {
+                        Ok(space) => Some(space),
+                        Err(e) => {
+                            log::warn!("Could not check disk space for {download_dir}: {e}");
+                            None
+                        }
+                    };
+                    let _ = tx.send(Event::DiskSpaceChecked { mod_ids, space }).await;
+                });
+            }
+
+            Effect::RecordDownload { record } => {
+                let history = self.download_history.clone();
+                self.rt_handle.spawn(async move {
+                    if let Err(e) = history.append(&record).await {
+                        log::warn!("Failed to record download history: {e}");
+                    }
                 });
             }
 
@@ -281,29 +1190,146 @@ impl AppRuntime {
                 });
             }
 
-            Effect::SaveConfig {
-                current_list_id,
-                default_list_name,
-            } => {
+            Effect::SaveConfig { config } => {
                 let cm = self.config_manager.clone();
-                let config = AppConfig {
-                    current_list_id,
-                    default_list_name,
-                };
+                let download_limiter = self.api_service.download_limiter.clone();
+                let mod_service = self.mod_service.clone();
+                self.api_service
+                    .curseforge
+                    .set_api_key(config.curseforge_api_key.clone());
+                self.api_service
+                    .modrinth
+                    .set_base_url(&config.modrinth_api_url);
+                let api_service = self.api_service.clone();
+                let tx = self.event_tx.clone();
                 self.rt_handle.spawn(async move {
+                    Self::apply_http_settings(&api_service, &config, &tx).await;
+                    download_limiter.set_max_connections(config.max_parallel_downloads);
+                    mod_service.set_cache_ttl(config.cache_ttl).await;
                     let _ = cm.save_config(&config).await;
                 });
             }
 
-            Effect::ExportListToml { path, list } => {
+            Effect::ExportListToml {
+                path,
+                list,
+                overwrite,
+            } => {
+                let tx = self.event_tx.clone();
                 self.rt_handle.spawn(async move {
-                    let toml_string = toml::to_string_pretty(&list).unwrap_or_default();
-                    let _ = tokio::fs::write(path, toml_string).await;
+                    let result = async {
+                        crate::infra::backup_existing_file(&path, overwrite).await?;
+                        let toml_string = toml::to_string_pretty(&list)?;
+                        tokio::fs::write(&path, toml_string).await?;
+                        anyhow::Ok(())
+                    }
+                    .await;
+
+                    if let Err(e) = result {
+                        log::error!("Failed to export list {}: {e:#}", path.display());
+                        let _ = tx
+                            .send(Event::LegacyListFailed {
+                                error: format!("Failed to export list: {e:#}"),
+                                is_import: false,
+                            })
+                            .await;
+                    }
                 });
             }
 
-            Effect::LegacyListImport {
+            Effect::ExportBundleToml {
                 path,
+                bundle,
+                overwrite,
+            } => {
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let result = async {
+                        crate::infra::backup_existing_file(&path, overwrite).await?;
+                        let toml_string = toml::to_string_pretty(&bundle)?;
+                        tokio::fs::write(&path, toml_string).await?;
+                        anyhow::Ok(())
+                    }
+                    .await;
+
+                    if let Err(e) = result {
+                        log::error!("Failed to export bundle {}: {e:#}", bundle.name);
+                        let _ = tx
+                            .send(Event::LegacyListFailed {
+                                error: format!("Failed to export bundle: {e:#}"),
+                                is_import: false,
+                            })
+                            .await;
+                    }
+                });
+            }
+
+            Effect::PreviewLegacyImport { path } => {
+                let legacy_svc = self.legacy_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let event = match legacy_svc.preview_import(&path).await {
+                        Ok(entries) => Event::LegacyImportPreviewLoaded {
+                            suggested_name: path
+                                .file_stem()
+                                .map(|s| s.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                            entries,
+                        },
+                        Err(e) => Event::LegacyListFailed {
+                            error: format!("{e:#}"),
+                            is_import: true,
+                        },
+                    };
+                    let _ = tx.send(event).await;
+                });
+            }
+
+            Effect::PreviewCollectionImport { collection_id } => {
+                let legacy_svc = self.legacy_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let event = match legacy_svc.preview_collection(&collection_id, &tx).await {
+                        Ok((name, entries)) => Event::LegacyImportPreviewLoaded {
+                            suggested_name: if name.is_empty() { collection_id } else { name },
+                            entries,
+                        },
+                        Err(e) => Event::LegacyListFailed {
+                            error: format!("{e:#}"),
+                            is_import: true,
+                        },
+                    };
+                    let _ = tx.send(event).await;
+                });
+            }
+
+            Effect::ImportDirectory { path } => {
+                let legacy_svc = self.legacy_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let event = match legacy_svc.import_directory(&path, &tx).await {
+                        Ok((successful, failed)) => Event::LegacyListComplete {
+                            suggested_name: path
+                                .file_name()
+                                .map(|s| s.to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                            successful,
+                            failed,
+                            warnings: Vec::new(),
+                            is_import: true,
+                        },
+                        Err(e) => Event::LegacyListFailed {
+                            error: format!("{e:#}"),
+                            is_import: true,
+                        },
+                    };
+                    let _ = tx.send(event).await;
+                });
+            }
+
+            Effect::LegacyListImport {
+                suggested_name,
+                slugs,
                 version,
                 loader,
             } => {
@@ -311,7 +1337,29 @@ impl AppRuntime {
                 let tx = self.event_tx.clone();
                 self.rt_handle.spawn(async move {
                     legacy_svc
-                        .import_legacy_list(path, version, loader, tx)
+                        .import_legacy_list(suggested_name, slugs, version, loader, tx)
+                        .await;
+                });
+            }
+
+            Effect::FindLegacyCandidates {
+                index,
+                query,
+                version,
+                loader,
+            } => {
+                let legacy_svc = self.legacy_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let candidates = legacy_svc
+                        .search_candidates(&query, &version, &loader)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log::warn!("Failed to search for '{query}': {e}");
+                            Vec::new()
+                        });
+                    let _ = tx
+                        .send(Event::LegacyCandidatesLoaded { index, candidates })
                         .await;
                 });
             }
@@ -321,26 +1369,129 @@ impl AppRuntime {
                 mod_ids,
                 version,
                 loader,
+                overwrite,
             } => {
                 let legacy_svc = self.legacy_service.clone();
                 let tx = self.event_tx.clone();
                 self.rt_handle.spawn(async move {
                     legacy_svc
-                        .export_legacy_list(path, mod_ids, version, loader, tx)
+                        .export_legacy_list(path, mod_ids, version, loader, overwrite, tx)
+                        .await;
+                });
+            }
+
+            Effect::ExportMrpack {
+                path,
+                name,
+                parts,
+                version,
+                overwrite,
+            } => {
+                let mrpack_svc = self.mrpack_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    mrpack_svc
+                        .export_mrpack(path, name, parts, version, overwrite, tx)
+                        .await;
+                });
+            }
+
+            Effect::ExportLockfile {
+                path,
+                list,
+                version,
+                loader,
+                overwrite,
+            } => {
+                let lockfile_svc = self.lockfile_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    lockfile_svc
+                        .export_lockfile(path, list, version, loader, overwrite, tx)
+                        .await;
+                });
+            }
+
+            Effect::InstallLockfile { path, download_dir } => {
+                let lockfile_svc = self.lockfile_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    lockfile_svc.install_lockfile(path, download_dir, tx).await;
+                });
+            }
+
+            Effect::ExportUrlList {
+                path,
+                list,
+                version,
+                loader,
+                overwrite,
+            } => {
+                let url_list_svc = self.url_list_service.clone();
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    url_list_svc
+                        .export_url_list(path, list, version, loader, overwrite, tx)
                         .await;
                 });
             }
 
-            Effect::RemoveFromMetadata {
+            Effect::MatchDroppedFiles {
+                paths,
                 download_dir,
-                mod_id,
             } => {
+                let api_svc = self.api_service.clone();
+                let tx = self.event_tx.clone();
+
                 self.rt_handle.spawn(async move {
                     let download_path = std::path::Path::new(&download_dir);
-                    if let Err(e) =
-                        crate::infra::remove_metadata_entry(download_path, &mod_id).await
-                    {
-                        log::warn!("Failed to remove metadata entry for {mod_id}: {e}");
+                    let _ = tokio::fs::create_dir_all(download_path).await;
+
+                    for path in paths {
+                        let file_name = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+
+                        let mod_info = match Self::match_dropped_file(&api_svc, &path).await {
+                            Ok(info) => info,
+                            Err(e) => {
+                                log::warn!("Failed to match dropped file {file_name}: {e}");
+                                None
+                            }
+                        };
+
+                        let target_name = mod_info
+                            .as_ref()
+                            .map(crate::domain::generate_mod_filename)
+                            .unwrap_or_else(|| file_name.clone());
+                        let target_path = download_path.join(&target_name);
+
+                        if path != target_path
+                            && let Err(e) = tokio::fs::copy(&path, &target_path).await
+                        {
+                            log::warn!("Failed to copy dropped file {file_name}: {e}");
+                        }
+
+                        if let Some(info) = &mod_info
+                            && let Err(e) = crate::infra::update_metadata_entry(
+                                download_path,
+                                info.id.clone(),
+                                target_name,
+                                info.version.clone(),
+                                info.hashes.clone(),
+                            )
+                            .await
+                        {
+                            log::warn!("Failed to update download metadata: {e}");
+                        }
+
+                        let _ = tx
+                            .send(Event::DroppedFileProcessed {
+                                file_name,
+                                mod_info: mod_info.map(Arc::new),
+                            })
+                            .await;
                     }
                 });
             }
@@ -349,34 +1500,43 @@ impl AppRuntime {
                 download_dir,
                 mod_id,
             } => {
+                let tx = self.event_tx.clone();
+
                 self.rt_handle.spawn(async move {
                     let download_path = std::path::Path::new(&download_dir);
+                    let guard = crate::infra::lock_download_metadata(download_path).await;
 
-                    if let Ok(metadata) = crate::infra::read_download_metadata(download_path).await
-                        && let Some(entry) = metadata.get_entry(&mod_id)
-                    {
-                        let file_path = download_path.join(&entry.file);
-                        if file_path.exists() {
-                            if let Err(e) = tokio::fs::remove_file(&file_path).await {
-                                log::warn!("Failed to delete mod file {}: {}", entry.file, e);
-                            } else {
-                                log::info!("Deleted mod file: {}", entry.file);
-                            }
-                        }
+                    let Ok(mut metadata) = guard.read().await else {
+                        return;
+                    };
+                    let Some(entry) = metadata.get_entry(&mod_id).cloned() else {
+                        return;
+                    };
 
-                        let archived_path = download_path.join(format!("{}.archived", entry.file));
-                        if archived_path.exists() {
-                            if let Err(e) = tokio::fs::remove_file(&archived_path).await {
-                                log::warn!(
-                                    "Failed to delete archived file {}.archived: {}",
-                                    entry.file,
-                                    e
-                                );
-                            } else {
-                                log::info!("Deleted archived file: {}.archived", entry.file);
+                    // A deduped download shares its file with another entry, which keeps it
+                    if metadata.is_shared(&mod_id) {
+                        log::info!("Keeping {}, another entry still uses it", entry.file);
+                    } else {
+                        for name in [entry.file.clone(), format!("{}.archived", entry.file)] {
+                            match tokio::fs::remove_file(download_path.join(&name)).await {
+                                Ok(()) => log::info!("Deleted mod file: {name}"),
+                                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                                Err(e) => log::warn!("Failed to delete mod file {name}: {e}"),
                             }
                         }
                     }
+
+                    metadata.remove_entry(&mod_id);
+                    if let Err(e) = guard.write(&metadata).await {
+                        log::warn!("Failed to remove metadata entry for {mod_id}: {e}");
+                    } else {
+                        let _ = tx
+                            .send(Event::MetadataLoaded {
+                                download_dir,
+                                metadata,
+                            })
+                            .await;
+                    }
                 });
             }
 
@@ -398,6 +1558,125 @@ impl AppRuntime {
                 });
             }
 
+            Effect::DeleteUnknownFiles { paths } => {
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let mut removed = 0;
+                    let mut failed = Vec::new();
+
+                    for path in paths {
+                        let filename = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_default();
+                        match tokio::fs::remove_file(&path).await {
+                            Ok(()) => removed += 1,
+                            // Already gone counts as done
+                            Err(e) if e.kind() == std::io::ErrorKind::NotFound => removed += 1,
+                            Err(e) => {
+                                log::warn!("Failed to delete unknown file {filename}: {e}");
+                                failed.push(format!("{filename}: {e}"));
+                            }
+                        }
+                    }
+                    log::info!("Deleted {removed} unknown files, {} failed", failed.len());
+
+                    let _ = tx
+                        .send(Event::UnknownFilesDeleted { removed, failed })
+                        .await;
+                });
+            }
+
+            Effect::DeleteOrphanedFiles {
+                download_dir,
+                mod_ids,
+            } => {
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let download_path = std::path::Path::new(&download_dir);
+                    let guard = crate::infra::lock_download_metadata(download_path).await;
+                    let mut metadata = match guard.read().await {
+                        Ok(metadata) => metadata,
+                        Err(e) => {
+                            log::warn!("Could not read metadata for cleanup: {e}");
+                            let _ = tx
+                                .send(Event::OrphanedFilesDeleted {
+                                    removed: 0,
+                                    failed: vec![format!("metadata: {e}")],
+                                })
+                                .await;
+                            return;
+                        }
+                    };
+
+                    let mut removed = 0;
+                    let mut failed = Vec::new();
+
+                    for mod_id in &mod_ids {
+                        let Some(entry) = metadata.get_entry(mod_id).cloned() else {
+                            continue;
+                        };
+
+                        // A deduped download shares its file with another entry, which keeps it
+                        let shared = metadata
+                            .mods
+                            .iter()
+                            .any(|(id, other)| !mod_ids.contains(id) && other.file == entry.file);
+                        if shared {
+                            metadata.remove_entry(mod_id);
+                            removed += 1;
+                            continue;
+                        }
+
+                        // The file may sit there disabled or archived as well
+                        let mut errors = Vec::new();
+                        for name in [
+                            entry.file.clone(),
+                            format!("{}{DISABLED_SUFFIX}", entry.file),
+                            format!("{}.archived", entry.file),
+                        ] {
+                            match tokio::fs::remove_file(download_path.join(&name)).await {
+                                Ok(()) => log::info!("Deleted orphaned file: {name}"),
+                                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                                Err(e) => {
+                                    log::warn!("Failed to delete orphaned file {name}: {e}");
+                                    errors.push(format!("{name}: {e}"));
+                                }
+                            }
+                        }
+
+                        // Entries are only dropped once their files are gone
+                        if errors.is_empty() {
+                            metadata.remove_entry(mod_id);
+                            removed += 1;
+                        } else {
+                            failed.extend(errors);
+                        }
+                    }
+                    log::info!(
+                        "Cleaned up {removed} orphaned files, {} failed",
+                        failed.len()
+                    );
+
+                    if let Err(e) = guard.write(&metadata).await {
+                        log::warn!("Failed to write metadata after cleanup: {e}");
+                        failed.push(format!("metadata: {e}"));
+                    }
+
+                    let _ = tx
+                        .send(Event::MetadataLoaded {
+                            download_dir,
+                            metadata,
+                        })
+                        .await;
+                    let _ = tx
+                        .send(Event::OrphanedFilesDeleted { removed, failed })
+                        .await;
+                });
+            }
+
             Effect::ArchiveModFile {
                 download_dir,
                 mod_id,
@@ -407,13 +1686,19 @@ impl AppRuntime {
 
                 self.rt_handle.spawn(async move {
                     let download_path = std::path::Path::new(&download_dir);
+                    let guard = crate::infra::lock_download_metadata(download_path).await;
 
-                    if let Ok(mut metadata) =
-                        crate::infra::read_download_metadata(download_path).await
+                    if let Ok(mut metadata) = guard.read().await
                         && let Some(entry) = metadata.get_entry(&mod_id)
                     {
+                        // Renaming a shared file would leave the other entry without it
+                        if metadata.is_shared(&mod_id) {
+                            log::info!("Not archiving {}, another entry still uses it", entry.file);
+                            return;
+                        }
                         let original_file = entry.file.clone();
                         let version = entry.version.clone();
+                        let hashes = entry.hashes.clone();
                         let file_path = download_path.join(&original_file);
                         let archived_path = download_path.join(format!("{original_file}.archived"));
 
@@ -429,12 +1714,10 @@ impl AppRuntime {
                                     mod_id.clone(),
                                     format!("{original_file}.archived"),
                                     version,
+                                    hashes,
                                 );
 
-                                if let Err(e) =
-                                    crate::infra::write_download_metadata(download_path, &metadata)
-                                        .await
-                                {
+                                if let Err(e) = guard.write(&metadata).await {
                                     log::warn!("Failed to update metadata after archiving: {e}");
                                 } else {
                                     let _ = tx
@@ -459,13 +1742,14 @@ impl AppRuntime {
 
                 self.rt_handle.spawn(async move {
                     let download_path = std::path::Path::new(&download_dir);
+                    let guard = crate::infra::lock_download_metadata(download_path).await;
 
-                    if let Ok(mut metadata) =
-                        crate::infra::read_download_metadata(download_path).await
+                    if let Ok(mut metadata) = guard.read().await
                         && let Some(entry) = metadata.get_entry(&mod_id)
                     {
                         let archived_file = entry.file.clone();
                         let version = entry.version.clone();
+                        let hashes = entry.hashes.clone();
 
                         if let Some(original_file) = archived_file.strip_suffix(".archived") {
                             let archived_path = download_path.join(&archived_file);
@@ -484,14 +1768,10 @@ impl AppRuntime {
                                         mod_id.clone(),
                                         original_file.to_string(),
                                         version,
+                                        hashes,
                                     );
 
-                                    if let Err(e) = crate::infra::write_download_metadata(
-                                        download_path,
-                                        &metadata,
-                                    )
-                                    .await
-                                    {
+                                    if let Err(e) = guard.write(&metadata).await {
                                         log::warn!(
                                             "Failed to update metadata after unarchiving: {e}"
                                         );
@@ -510,27 +1790,155 @@ impl AppRuntime {
                 });
             }
 
+            Effect::SetModFileEnabled {
+                download_dir,
+                mod_id,
+                enabled,
+            } => {
+                let tx = self.event_tx.clone();
+
+                self.rt_handle.spawn(async move {
+                    let download_path = std::path::Path::new(&download_dir);
+
+                    let guard = crate::infra::lock_download_metadata(download_path).await;
+
+                    // Not downloaded yet, the next download picks the right name
+                    let Ok(mut metadata) = guard.read().await else {
+                        return;
+                    };
+                    let Some(entry) = metadata.get_entry(&mod_id) else {
+                        return;
+                    };
+
+                    let current_file = entry.file.clone();
+                    let version = entry.version.clone();
+                    let hashes = entry.hashes.clone();
+
+                    // Archived files keep their suffix last, so only active files are renamed
+                    if current_file.ends_with(".archived") {
+                        return;
+                    }
+                    let new_file = match (enabled, current_file.strip_suffix(DISABLED_SUFFIX)) {
+                        (true, Some(original)) => original.to_string(),
+                        (false, None) => format!("{current_file}{DISABLED_SUFFIX}"),
+                        _ => return,
+                    };
+
+                    let current_path = download_path.join(&current_file);
+                    let new_path = download_path.join(&new_file);
+                    if metadata.is_shared(&mod_id) {
+                        // The other entry keeps the shared file, this one gets its own copy
+                        if new_path.exists() {
+                            log::warn!("Not copying {current_file}, {new_file} already exists");
+                            return;
+                        }
+                        if let Err(e) = tokio::fs::copy(&current_path, &new_path).await {
+                            log::warn!("Failed to copy {current_file} to {new_file}: {e}");
+                            return;
+                        }
+                        log::info!("Copied shared mod file: {current_file} -> {new_file}");
+                    } else {
+                        if current_path.exists()
+                            && let Err(e) = tokio::fs::rename(&current_path, &new_path).await
+                        {
+                            log::warn!("Failed to rename {current_file} to {new_file}: {e}");
+                            return;
+                        }
+                        log::info!("Renamed mod file: {current_file} -> {new_file}");
+                    }
+
+                    metadata.update_entry(mod_id, new_file, version, hashes);
+                    if let Err(e) = guard.write(&metadata).await {
+                        log::warn!("Failed to update metadata after renaming: {e}");
+                    } else {
+                        let _ = tx
+                            .send(Event::MetadataLoaded {
+                                download_dir,
+                                metadata,
+                            })
+                            .await;
+                    }
+                });
+            }
+
+            Effect::WatchDownloadDir { download_dir } => {
+                if let Ok(mut watcher) = self.dir_watcher.lock() {
+                    watcher.watch(download_dir);
+                }
+            }
+
+            Effect::VerifyList {
+                list,
+                download_dir,
+                full,
+            } => {
+                let tx = self.event_tx.clone();
+                self.rt_handle.spawn(async move {
+                    let download_path = std::path::PathBuf::from(&download_dir);
+                    let metadata = crate::infra::read_download_metadata(&download_path)
+                        .await
+                        .unwrap_or_else(|e| {
+                            log::warn!("Could not read metadata for verification: {e}");
+                            crate::infra::DownloadMetadata::new()
+                        });
+
+                    let list_id = list.id.clone();
+                    let progress_tx = tx.clone();
+                    let results = tokio::task::spawn_blocking(move || {
+                        crate::infra::verify_list(
+                            &list,
+                            &download_path,
+                            &metadata,
+                            full,
+                            |checked, total| {
+                                let _ = progress_tx.blocking_send(Event::VerifyProgress {
+                                    list_id: list.id.clone(),
+                                    checked,
+                                    total,
+                                });
+                            },
+                        )
+                    })
+                    .await
+                    .unwrap_or_else(|e| {
+                        log::warn!("Verification task failed: {e}");
+                        Vec::new()
+                    });
+
+                    let _ = tx.send(Event::ListVerified { list_id, results }).await;
+
+                    // Stale entries are only removed from disk here, after they were reported
+                    match crate::infra::prune_download_metadata(std::path::Path::new(&download_dir))
+                        .await
+                    {
+                        Ok(metadata) => {
+                            let _ = tx
+                                .send(Event::MetadataLoaded {
+                                    download_dir,
+                                    metadata,
+                                })
+                                .await;
+                        }
+                        Err(e) => log::warn!("Failed to prune metadata after verification: {e}"),
+                    }
+                });
+            }
+
             Effect::ValidateMetadata { download_dir } => {
                 let tx = self.event_tx.clone();
                 let dir_clone = download_dir.clone();
                 self.rt_handle.spawn(async move {
                     let download_path = std::path::Path::new(&download_dir);
                     match crate::infra::read_download_metadata(download_path).await {
+                        // Read-only, a download may be writing the file at the same time
                         Ok(mut metadata) => {
                             metadata.validate_and_cleanup(download_path);
-                            if let Err(e) =
-                                crate::infra::write_download_metadata(download_path, &metadata)
-                                    .await
-                            {
-                                log::warn!("Failed to write validated metadata: {e}");
-                            } else {
-                                let _ = tx
-                                    .send(Event::MetadataLoaded {
-                                        download_dir: dir_clone,
-                                        metadata,
-                                    })
-                                    .await;
-                            }
+                            let _ = tx
+                                .send(Event::MetadataLoaded {
+                                    download_dir: dir_clone,
+                                    metadata,
+                                })
+                                .await;
                         }
                         Err(e) => {
                             log::debug!("Could not read metadata for validation: {e}");
@@ -547,3 +1955,61 @@ impl AppRuntime {
         }
     }
 }
+
+// Downloads one file, waiting out retriable failures without holding a download slot
+async fn download_with_retries(
+    api_svc: &ApiService,
+    mod_info: &ModInfo,
+    destination: &std::path::Path,
+    max_retries: u32,
+    tx: &mpsc::Sender<Event>,
+) -> anyhow::Result<()> {
+    let mod_id = &mod_info.id;
+    let mut permit = api_svc.download_limiter.acquire(1).await;
+
+    let mut attempt = 0;
+    loop {
+        let tx_progress = tx.clone();
+        let mod_id_clone = mod_id.clone();
+
+        let result = api_svc
+            .provider(mod_info.provider)
+            .download_mod(
+                &mod_info.download_url,
+                &mod_info.hashes,
+                destination,
+                Box::new(move |progress| {
+                    let _ = tx_progress.try_send(Event::DownloadProgress {
+                        mod_id: mod_id_clone.clone(),
+                        progress,
+                    });
+                }),
+            )
+            .await;
+
+        match api_svc.track(result) {
+            Err(e) if attempt < max_retries && is_retriable_error(&e) => {
+                attempt += 1;
+                let delay = retry_backoff(&e, attempt);
+                log::warn!(
+                    "Download of {mod_id} failed ({e}), \
+                     retry {attempt}/{max_retries} in {}s",
+                    delay.as_secs()
+                );
+                let _ = tx
+                    .send(Event::DownloadRetrying {
+                        mod_id: mod_id.clone(),
+                        attempt,
+                        max_attempts: max_retries,
+                    })
+                    .await;
+
+                // Free the slot for other downloads while waiting
+                drop(permit);
+                tokio::time::sleep(delay).await;
+                permit = api_svc.download_limiter.acquire(1).await;
+            }
+            result => return result,
+        }
+    }
+}
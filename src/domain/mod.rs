@@ -1,15 +1,98 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 pub mod mod_source;
 
-pub use mod_source::ModProvider;
+pub use mod_source::{DownloadCancelled, HashMismatch, ModProvider, RateLimited};
 
 pub mod mod_service;
 
-use crate::infra::DownloadMetadata;
-pub use mod_service::ModService;
+use crate::infra::{DiskSpace, DownloadMetadata, VerifyResult};
+pub use mod_service::{ModService, SearchKey};
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+pub fn format_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}K", count as f64 / 1_000.0),
+        1_000_000..1_000_000_000 => format!("{:.1}M", count as f64 / 1_000_000.0),
+        _ => format!("{:.1}B", count as f64 / 1_000_000_000.0),
+    }
+}
+
+// Short form like "2m 15s", only the two largest units
+pub fn format_duration(duration: std::time::Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+// fzf-style subsequence match, higher is better. None when the query doesn't match at all.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0i64;
+    let mut matched = 0;
+    let mut prev: Option<usize> = None;
+
+    for (i, &c) in text.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if c != query[matched] {
+            continue;
+        }
+
+        score += 1;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match prev {
+            Some(p) if p + 1 == i => score += 5,
+            Some(p) => score -= (i - p - 1).min(5) as i64,
+            None => {}
+        }
+        prev = Some(i);
+        matched += 1;
+    }
+
+    if matched < query.len() {
+        return None;
+    }
+
+    let needle: String = query.iter().collect();
+    if text.iter().collect::<String>().contains(&needle) {
+        score += 20;
+    }
+    Some(score)
+}
 
 pub fn sanitize_filename(name: &str) -> String {
     name.chars()
@@ -19,12 +102,75 @@ pub fn sanitize_filename(name: &str) -> String {
         .to_string()
 }
 
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
 pub fn generate_mod_filename(mod_info: &ModInfo) -> String {
     let sanitized_name = sanitize_filename(&mod_info.name);
     let extension = mod_info.project_type.fileext();
     format!("{sanitized_name}.{extension}")
 }
 
+// Checks a user supplied filename and appends the project extension when it's missing.
+// taken holds the names the list's other entries download to.
+pub fn validate_filename_override(
+    name: &str,
+    project_type: ProjectType,
+    taken: &[String],
+) -> Result<String, String> {
+    const RESERVED_NAMES: &[&str] = &[
+        "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+        "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+    ];
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Filename can't be empty".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| {
+        c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+    }) {
+        return Err(format!("Filename can't contain '{}'", c.escape_default()));
+    }
+    // Leading dots hide the file and collide with the downloader's own dotfiles
+    if name.starts_with('.') || name.ends_with('.') {
+        return Err("Filename can't start or end with a dot".to_string());
+    }
+
+    let extension = project_type.fileext();
+    let name = if name
+        .to_ascii_lowercase()
+        .ends_with(&format!(".{extension}"))
+    {
+        name.to_string()
+    } else {
+        format!("{name}.{extension}")
+    };
+
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        return Err(format!("{stem} is a reserved name on Windows"));
+    }
+
+    // Windows and macOS treat names differing only in case as the same file
+    let collides = |other: &String| {
+        if cfg!(any(target_os = "windows", target_os = "macos")) {
+            other.eq_ignore_ascii_case(&name)
+        } else {
+            *other == name
+        }
+    };
+    if taken.iter().any(collides) {
+        return Err(format!("Another item already downloads to {name}"));
+    }
+
+    Ok(name)
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
 pub enum ProjectType {
     #[default]
@@ -41,6 +187,16 @@ pub enum ProjectType {
 }
 
 impl ProjectType {
+    pub fn all() -> [ProjectType; 5] {
+        [
+            ProjectType::Mod,
+            ProjectType::ResourcePack,
+            ProjectType::Shader,
+            ProjectType::Datapack,
+            ProjectType::Plugin,
+        ]
+    }
+
     pub fn id(&self) -> &str {
         match self {
             ProjectType::Mod => "mod",
@@ -71,6 +227,33 @@ impl ProjectType {
         }
     }
 
+    pub fn folder_name(&self) -> &str {
+        match self {
+            ProjectType::Mod => "mods",
+            ProjectType::ResourcePack => "resourcepacks",
+            ProjectType::Shader => "shaderpacks",
+            ProjectType::Datapack => "datapacks",
+            ProjectType::Plugin => "plugins",
+        }
+    }
+
+    // Packs only target a game version, the other types also need a matching (shader) loader
+    pub fn has_loader(&self) -> bool {
+        matches!(
+            self,
+            ProjectType::Mod | ProjectType::Shader | ProjectType::Plugin
+        )
+    }
+
+    // Subfolder a server (or world, for datapacks) expects this content in
+    pub fn server_folder(&self) -> Option<&str> {
+        match self {
+            ProjectType::Plugin => Some("plugins"),
+            ProjectType::Datapack => Some("datapacks"),
+            _ => None,
+        }
+    }
+
     pub fn emoji(&self) -> &str {
         match self {
             ProjectType::Mod => "⚒",
@@ -82,6 +265,128 @@ impl ProjectType {
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum ProviderKind {
+    #[default]
+    #[serde(rename = "modrinth")]
+    Modrinth,
+    #[serde(rename = "curseforge")]
+    CurseForge,
+}
+
+impl ProviderKind {
+    pub fn display_name(&self) -> &str {
+        match self {
+            ProviderKind::Modrinth => "Modrinth",
+            ProviderKind::CurseForge => "CurseForge",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SearchSort {
+    #[default]
+    Relevance,
+    Downloads,
+    Newest,
+    Updated,
+}
+
+impl SearchSort {
+    pub fn all() -> [SearchSort; 4] {
+        [
+            SearchSort::Relevance,
+            SearchSort::Downloads,
+            SearchSort::Newest,
+            SearchSort::Updated,
+        ]
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            SearchSort::Relevance => "Relevance",
+            SearchSort::Downloads => "Downloads",
+            SearchSort::Newest => "Newest",
+            SearchSort::Updated => "Recently Updated",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DependencyType {
+    #[serde(rename = "required")]
+    Required,
+    #[serde(rename = "optional")]
+    Optional,
+    #[serde(rename = "incompatible")]
+    Incompatible,
+    #[serde(rename = "embedded")]
+    Embedded,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModDependency {
+    pub mod_id: String,
+    pub dependency_type: DependencyType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DownloadFailure {
+    Network,
+    HashMismatch,
+    Cancelled,
+}
+
+impl DownloadFailure {
+    pub fn reason(&self) -> &str {
+        match self {
+            DownloadFailure::Network => "network error",
+            DownloadFailure::HashMismatch => "hash mismatch",
+            DownloadFailure::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DownloadRecord {
+    pub mod_id: String,
+    pub mod_name: String,
+    #[serde(default)]
+    pub list_id: Option<String>,
+    pub file_name: String,
+    pub version: String,
+    pub timestamp: DateTime<Utc>,
+    pub failure: Option<DownloadFailure>,
+    // Full error chain of a failed download
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// Bytes received so far and the expected size, total is 0 when the server doesn't send one
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransferProgress {
+    pub downloaded: u64,
+    pub total: u64,
+}
+
+impl TransferProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.downloaded as f32 / self.total as f32
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct FileHashes {
+    #[serde(default)]
+    pub sha1: String,
+    #[serde(default)]
+    pub sha512: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModInfo {
     pub id: String,
@@ -90,19 +395,253 @@ pub struct ModInfo {
     pub icon_url: String,
     pub description: String,
     pub version: String,
+    #[serde(default)]
+    pub version_id: String,
     pub author: String,
     pub download_count: u32,
     pub download_url: String,
+    #[serde(default)]
+    pub hashes: FileHashes,
+    #[serde(default)]
+    pub file_size: u64,
     pub supported_versions: Vec<String>,
     pub supported_loaders: Vec<String>,
     #[serde(default)]
     pub project_type: ProjectType,
+    #[serde(default)]
+    pub provider: ProviderKind,
+    #[serde(default)]
+    pub dependencies: Vec<ModDependency>,
+    #[serde(default)]
+    pub channel: ReleaseChannel,
+    #[serde(default)]
+    pub environment: ModEnvironment,
+}
+
+// Which side a project runs on, Unknown when the provider doesn't say
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum ModEnvironment {
+    #[default]
+    Unknown,
+    Both,
+    ClientOnly,
+    ServerOnly,
+}
+
+impl ModEnvironment {
+    // Takes Modrinth's client_side/server_side values: required, optional, unsupported or unknown
+    pub fn from_sides(client_side: &str, server_side: &str) -> Self {
+        match (client_side, server_side) {
+            ("unknown" | "", _) | (_, "unknown" | "") => ModEnvironment::Unknown,
+            ("unsupported", "unsupported") => ModEnvironment::Unknown,
+            (_, "unsupported") => ModEnvironment::ClientOnly,
+            ("unsupported", _) => ModEnvironment::ServerOnly,
+            _ => ModEnvironment::Both,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            ModEnvironment::Unknown => "Unknown environment",
+            ModEnvironment::Both => "Client & server",
+            ModEnvironment::ClientOnly => "Client only",
+            ModEnvironment::ServerOnly => "Server only",
+        }
+    }
+
+    pub fn emoji(&self) -> &str {
+        match self {
+            ModEnvironment::Unknown => "❔",
+            ModEnvironment::Both => "🔁",
+            ModEnvironment::ClientOnly => "🖥",
+            ModEnvironment::ServerOnly => "🗄",
+        }
+    }
+}
+
+// One entry of a project's version history, newest first when listed
+#[derive(Clone, Debug)]
+pub struct ProjectVersion {
+    pub id: String,
+    pub name: String,
+    pub channel: ReleaseChannel,
+    pub game_versions: Vec<String>,
+}
+
+impl ModInfo {
+    pub fn supports_version(&self, version: &str) -> bool {
+        self.supported_versions.is_empty() || self.supported_versions.iter().any(|v| v == version)
+    }
+
+    pub fn supports_version_with(&self, version: &str, policy: CompatPolicy) -> bool {
+        self.supports_version(version)
+            || self
+                .supported_versions
+                .iter()
+                .any(|v| policy.allows(version, v))
+    }
+
+    // Projects often ship one file for several loaders (e.g. Forge and NeoForge)
+    pub fn supports_loader(&self, loader: &str) -> bool {
+        self.supported_loaders.is_empty()
+            || self
+                .supported_loaders
+                .iter()
+                .any(|l| l.eq_ignore_ascii_case(loader))
+    }
+}
+
+// Whether a project fits a list of the given type. Packs ignore the loader (the "minecraft" and
+// "datapack" loaders say nothing about compatibility) but fail on a known pack_format mismatch.
+pub fn compatibility_for(
+    project_type: ProjectType,
+    info: &ModInfo,
+    version: &str,
+    loader: &str,
+    policy: CompatPolicy,
+    pack_format_ok: Option<bool>,
+) -> bool {
+    let version_ok = info.supports_version_with(version, policy);
+    match project_type {
+        ProjectType::Mod | ProjectType::Shader | ProjectType::Plugin => {
+            version_ok && info.supports_loader(loader)
+        }
+        ProjectType::ResourcePack | ProjectType::Datapack => {
+            version_ok && pack_format_ok != Some(false)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum VersionType {
+    #[default]
+    #[serde(rename = "release")]
+    Release,
+    #[serde(rename = "snapshot")]
+    Snapshot,
+    #[serde(rename = "beta")]
+    Beta,
+    #[serde(rename = "alpha")]
+    Alpha,
+}
+
+// What an export does with a file that already exists at the target path
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverwritePolicy {
+    Overwrite,
+    // Keeps a timestamped .bak copy of the old file
+    Backup,
+}
+
+// Step of the startup load, shown on the loading screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InitialLoadStep {
+    Config,
+    Lists,
+    Versions,
+    Loaders,
+    History,
+}
+
+impl InitialLoadStep {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Config => "Loading settings...",
+            Self::Lists => "Loading lists...",
+            Self::Versions => "Fetching Minecraft versions...",
+            Self::Loaders => "Fetching mod loaders...",
+            Self::History => "Loading download history...",
+        }
+    }
+
+    // Only these steps wait on the network and can be skipped
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::Versions | Self::Loaders)
+    }
+}
+
+// Release channel of a project version, ordered from most to least stable
+#[derive(
+    Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ReleaseChannel {
+    #[default]
+    Release,
+    Beta,
+    Alpha,
+}
+
+impl ReleaseChannel {
+    pub fn all() -> [ReleaseChannel; 3] {
+        [
+            ReleaseChannel::Release,
+            ReleaseChannel::Beta,
+            ReleaseChannel::Alpha,
+        ]
+    }
+
+    pub fn from_id(id: &str) -> Self {
+        match id.to_lowercase().as_str() {
+            "beta" => ReleaseChannel::Beta,
+            "alpha" => ReleaseChannel::Alpha,
+            _ => ReleaseChannel::Release,
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            ReleaseChannel::Release => "Release",
+            ReleaseChannel::Beta => "Beta",
+            ReleaseChannel::Alpha => "Alpha",
+        }
+    }
+
+    // Used as a list's setting: the least stable channel auto-selection may pick
+    pub fn allows(&self, channel: ReleaseChannel) -> bool {
+        channel <= *self
+    }
+
+    // Keeps cached details for restricted channels apart, unrestricted keeps the plain loader
+    pub fn cache_context(&self, loader: &str) -> String {
+        match self {
+            ReleaseChannel::Alpha => loader.to_string(),
+            _ => format!("{loader}@{}", self.display_name().to_lowercase()),
+        }
+    }
+}
+
+fn default_allowed_channel() -> ReleaseChannel {
+    ReleaseChannel::Alpha
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MinecraftVersion {
     pub id: String,
     pub name: String,
+    #[serde(default)]
+    pub version_type: VersionType,
+}
+
+impl MinecraftVersion {
+    pub fn is_release(&self) -> bool {
+        self.version_type == VersionType::Release
+    }
+
+    pub fn display_label(&self) -> String {
+        match self.version_type {
+            VersionType::Release => self.name.clone(),
+            VersionType::Snapshot => format!("🧪 {} (snapshot)", self.name),
+            VersionType::Beta => format!("🧪 {} (beta)", self.name),
+            VersionType::Alpha => format!("🧪 {} (alpha)", self.name),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Category {
+    pub id: String,
+    pub name: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -111,6 +650,17 @@ pub struct ModLoader {
     pub name: String,
 }
 
+impl ModLoader {
+    pub fn matches(&self, loader_id: &str) -> bool {
+        self.id.eq_ignore_ascii_case(loader_id)
+    }
+}
+
+// Loaders whose builds are published per Minecraft version, so a specific one can be pinned
+pub fn has_pinnable_loader_version(loader_id: &str) -> bool {
+    matches!(loader_id, "fabric" | "quilt" | "forge" | "neoforge")
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModEntry {
     pub mod_id: String,
@@ -120,6 +670,112 @@ pub struct ModEntry {
     pub archived: bool,
     #[serde(default)]
     pub compatibility_override: bool,
+    #[serde(default)]
+    pub provider: ProviderKind,
+    #[serde(default)]
+    pub added_as_dependency: bool,
+    #[serde(default)]
+    pub pinned_version: Option<String>,
+    #[serde(default)]
+    pub filename_override: Option<String>,
+    // Kept in the list and on disk, but renamed so the game skips it
+    #[serde(default)]
+    pub disabled: bool,
+    // Left out of bulk updates and deletes, only changed individually after confirming
+    #[serde(default)]
+    pub locked: bool,
+}
+
+// Suffix launchers use to keep a mod file around without loading it
+pub const DISABLED_SUFFIX: &str = ".disabled";
+
+impl ModEntry {
+    pub fn filename_for(&self, mod_info: &ModInfo) -> String {
+        self.filename_override
+            .clone()
+            .unwrap_or_else(|| generate_mod_filename(mod_info))
+    }
+
+    // The name the file has on disk, including the disabled suffix
+    pub fn disk_filename_for(&self, mod_info: &ModInfo) -> String {
+        let filename = self.filename_for(mod_info);
+        if self.disabled {
+            format!("{filename}{DISABLED_SUFFIX}")
+        } else {
+            filename
+        }
+    }
+}
+
+// How a legacy list entry was resolved, the first candidate is the one picked during import
+#[derive(Clone, Debug)]
+pub struct LegacyMatch {
+    pub input: String,
+    pub candidates: Vec<Arc<ModInfo>>,
+    // Found by searching because no project has the entry as its slug
+    pub by_name: bool,
+    // Search text for finding other candidates, starts as the entry itself
+    pub query: String,
+}
+
+// Lists of different types managed as one pack, all targeting the bundle's Minecraft version
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bundle {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    // Deleted lists are skipped, so undoing a delete puts the list back in its bundle
+    pub list_ids: Vec<String>,
+}
+
+// A bundle exported as one .mmd file with its lists inlined
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    pub name: String,
+    pub version: String,
+    pub lists: Vec<ModList>,
+}
+
+// Changes between two versions of a list, entries are matched by project ID
+#[derive(Default)]
+pub struct ListDiff<'a> {
+    pub added: Vec<&'a ModEntry>,
+    pub removed: Vec<&'a ModEntry>,
+    // Pinned version changed, None means the latest compatible version
+    pub version_changed: Vec<(&'a ModEntry, &'a ModEntry)>,
+    // Same project ID from another provider, or the project was renamed
+    pub source_changed: Vec<(&'a ModEntry, &'a ModEntry)>,
+}
+
+impl ListDiff<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.version_changed.is_empty()
+            && self.source_changed.is_empty()
+    }
+}
+
+pub fn diff_lists<'a>(old: &'a ModList, new: &'a ModList) -> ListDiff<'a> {
+    let mut diff = ListDiff {
+        removed: old.mods_missing_from(new),
+        ..Default::default()
+    };
+
+    for entry in &new.mods {
+        let Some(previous) = old.mods.iter().find(|e| e.mod_id == entry.mod_id) else {
+            diff.added.push(entry);
+            continue;
+        };
+        if previous.pinned_version != entry.pinned_version {
+            diff.version_changed.push((previous, entry));
+        }
+        if previous.provider != entry.provider || previous.mod_name != entry.mod_name {
+            diff.source_changed.push((previous, entry));
+        }
+    }
+
+    diff
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -136,6 +792,219 @@ pub struct ModList {
     pub download_dir: String,
     #[serde(default)]
     pub content_type: ProjectType,
+    #[serde(default)]
+    pub include_snapshots: bool,
+    #[serde(default)]
+    pub server_layout: bool,
+    // Skips client-only mods when downloading, for setting up a server
+    #[serde(default)]
+    pub server_mode: bool,
+    // Pinned loader build, the latest (Forge: recommended) one is used when unset
+    #[serde(default)]
+    pub loader_version: Option<String>,
+    #[serde(default = "default_allowed_channel")]
+    pub allowed_channel: ReleaseChannel,
+    #[serde(default)]
+    pub notes: String,
+    // Groups the list in the sidebar under its first tag
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Pinned to the top of the sidebar
+    #[serde(default)]
+    pub is_favorite: bool,
+}
+
+impl ModList {
+    // Trimmed, deduplicated tags from comma separated input
+    pub fn parse_tags(input: &str) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        tags
+    }
+
+    pub fn contains_mod(&self, mod_id: &str) -> bool {
+        self.mods.iter().any(|e| e.mod_id == mod_id)
+    }
+
+    // Lists from files or links come from other people, so only well-formed names that no
+    // earlier entry uses are kept
+    pub fn drop_invalid_filename_overrides(&mut self) {
+        let mut taken_names = Vec::new();
+        for entry in &mut self.mods {
+            entry.filename_override = entry.filename_override.take().and_then(|name| {
+                let valid = validate_filename_override(&name, self.content_type, &taken_names)
+                    .inspect_err(|e| log::warn!("Dropping filename override {name:?}: {e}"))
+                    .ok()?;
+                taken_names.push(valid.clone());
+                Some(valid)
+            });
+        }
+    }
+
+    pub fn pin_version(&mut self, mod_id: &str, version_id: String) -> bool {
+        match self.mods.iter_mut().find(|e| e.mod_id == mod_id) {
+            Some(entry) => {
+                entry.pinned_version = Some(version_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn unpin_version(&mut self, mod_id: &str) -> bool {
+        match self.mods.iter_mut().find(|e| e.mod_id == mod_id) {
+            Some(entry) => entry.pinned_version.take().is_some(),
+            None => false,
+        }
+    }
+
+    // Sorted IDs of the active mods, archived entries are left out like they are in share links
+    pub fn content_key(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .mods
+            .iter()
+            .filter(|e| !e.archived)
+            .map(|e| e.mod_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    pub fn mods_missing_from<'a>(&'a self, other: &ModList) -> Vec<&'a ModEntry> {
+        self.mods
+            .iter()
+            .filter(|e| !other.contains_mod(&e.mod_id))
+            .collect()
+    }
+
+    // Adds the other list's mods, existing pins and filenames win over the imported ones
+    pub fn merge_from(&mut self, other: ModList) -> usize {
+        let mut added = 0;
+        for imported in other.mods {
+            match self.mods.iter_mut().find(|e| e.mod_id == imported.mod_id) {
+                Some(entry) => {
+                    entry.archived &= imported.archived;
+                    if entry.pinned_version.is_none() {
+                        entry.pinned_version = imported.pinned_version;
+                    }
+                    if entry.filename_override.is_none() {
+                        entry.filename_override = imported.filename_override;
+                    }
+                }
+                None => {
+                    self.mods.push(imported);
+                    added += 1;
+                }
+            }
+        }
+        added
+    }
+
+    // Takes over the other list's mods. Local state of kept entries survives and locked entries
+    // are left exactly as they are.
+    pub fn apply_update(&mut self, other: ModList) {
+        let mut mods = Vec::with_capacity(other.mods.len());
+        for mut imported in other.mods {
+            if let Some(entry) = self.mods.iter().find(|e| e.mod_id == imported.mod_id) {
+                if entry.locked {
+                    mods.push(entry.clone());
+                    continue;
+                }
+                imported.added_at = entry.added_at;
+                imported.disabled = entry.disabled;
+                imported.compatibility_override |= entry.compatibility_override;
+                if imported.filename_override.is_none() {
+                    imported.filename_override = entry.filename_override.clone();
+                }
+            }
+            mods.push(imported);
+        }
+        let kept: Vec<ModEntry> = self
+            .mods
+            .iter()
+            .filter(|e| e.locked && !mods.iter().any(|m| m.mod_id == e.mod_id))
+            .cloned()
+            .collect();
+        mods.extend(kept);
+        self.mods = mods;
+    }
+
+    pub fn find_conflicts<F>(&self, lookup: F) -> Vec<(String, String)>
+    where
+        F: Fn(&str) -> Option<Arc<ModInfo>>,
+    {
+        let active: HashSet<&str> = self
+            .mods
+            .iter()
+            .filter(|e| !e.archived)
+            .map(|e| e.mod_id.as_str())
+            .collect();
+
+        let mut conflicts = Vec::new();
+        for entry in self.mods.iter().filter(|e| !e.archived) {
+            let Some(info) = lookup(&entry.mod_id) else {
+                continue;
+            };
+
+            for dependency in &info.dependencies {
+                if dependency.dependency_type != DependencyType::Incompatible
+                    || dependency.mod_id == entry.mod_id
+                    || !active.contains(dependency.mod_id.as_str())
+                {
+                    continue;
+                }
+
+                let pair = if entry.mod_id < dependency.mod_id {
+                    (entry.mod_id.clone(), dependency.mod_id.clone())
+                } else {
+                    (dependency.mod_id.clone(), entry.mod_id.clone())
+                };
+                if !conflicts.contains(&pair) {
+                    conflicts.push(pair);
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    pub fn add_with_dependencies(
+        &mut self,
+        mod_info: &ModInfo,
+        dependencies: &[Arc<ModInfo>],
+    ) -> Vec<String> {
+        let mut added = Vec::new();
+
+        let candidates = std::iter::once((mod_info, false))
+            .chain(dependencies.iter().map(|d| (d.as_ref(), true)));
+
+        for (info, is_dependency) in candidates {
+            if self.contains_mod(&info.id) {
+                continue;
+            }
+            self.mods.push(ModEntry {
+                mod_id: info.id.clone(),
+                mod_name: info.name.clone(),
+                added_at: Utc::now(),
+                archived: false,
+                compatibility_override: false,
+                provider: info.provider,
+                added_as_dependency: is_dependency,
+                pinned_version: None,
+                filename_override: None,
+                disabled: false,
+                locked: false,
+            });
+            added.push(info.id.clone());
+        }
+
+        added
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -143,12 +1012,334 @@ pub struct AppConfig {
     pub current_list_id: Option<String>,
     #[serde(default = "default_list_name")]
     pub default_list_name: String,
+    #[serde(default)]
+    pub curseforge_api_key: String,
+    // Empty uses the official Modrinth API
+    #[serde(default)]
+    pub modrinth_api_url: String,
+    // Sent in the User-Agent so API operators can reach whoever runs this copy
+    #[serde(default)]
+    pub user_agent_contact: String,
+    #[serde(default)]
+    pub proxy: ProxySettings,
+    #[serde(default)]
+    pub timeouts: NetworkTimeouts,
+    #[serde(default = "default_max_parallel_downloads")]
+    pub max_parallel_downloads: usize,
+    #[serde(default = "default_download_retries")]
+    pub download_retries: u32,
+    // Deletes the file an update replaces, only ever files recorded in the download metadata.
+    // Off unless chosen, deleting files is never a surprise
+    #[serde(default)]
+    pub clean_replaced_files: bool,
+    // Hours between background update checks, 0 turns them off
+    #[serde(default = "default_update_check_hours")]
+    pub update_check_hours: u64,
+    #[serde(default)]
+    pub cache_ttl: CacheTtl,
+    #[serde(default)]
+    pub compat_policy: CompatPolicy,
+    // Prefilled for new lists of each type, types without one use the built-in folder
+    #[serde(default)]
+    pub default_dirs: HashMap<ProjectType, String>,
+    #[serde(default)]
+    pub bundles: Vec<Bundle>,
+    #[serde(default)]
+    pub window: Option<WindowGeometry>,
+    #[serde(default)]
+    pub theme: Theme,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    // Follows the OS setting where the platform reports one, dark otherwise
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub fn all() -> [Theme; 3] {
+        [Theme::System, Theme::Light, Theme::Dark]
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Theme::System => "Follow System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+}
+
+// How loosely a mod's listed game versions are matched against the list's version when showing
+// compatibility. Snapshots and other non-release versions always need an exact match.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatPolicy {
+    #[default]
+    Exact,
+    // 1.20.4 counts for 1.20.1
+    SameMinor,
+    // Any 1.x release counts
+    SameMajor,
+}
+
+impl CompatPolicy {
+    pub fn all() -> [CompatPolicy; 3] {
+        [
+            CompatPolicy::Exact,
+            CompatPolicy::SameMinor,
+            CompatPolicy::SameMajor,
+        ]
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            CompatPolicy::Exact => "Exact version",
+            CompatPolicy::SameMinor => "Same minor (1.20.x)",
+            CompatPolicy::SameMajor => "Same major (1.x)",
+        }
+    }
+
+    pub fn allows(&self, target: &str, supported: &str) -> bool {
+        if target == supported {
+            return true;
+        }
+        let (Some(target), Some(supported)) = (release_parts(target), release_parts(supported))
+        else {
+            return false;
+        };
+        match self {
+            CompatPolicy::Exact => false,
+            CompatPolicy::SameMinor => target[..2] == supported[..2],
+            CompatPolicy::SameMajor => target[0] == supported[0],
+        }
+    }
+}
+
+// Numeric components of a release like 1.20 or 1.20.4
+fn release_parts(version: &str) -> Option<Vec<u32>> {
+    let parts = version
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<Vec<u32>>>()?;
+    (parts.len() >= 2).then_some(parts)
+}
+
+// Empty fields fall back to the standard HTTP_PROXY, HTTPS_PROXY and NO_PROXY variables
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProxySettings {
+    #[serde(default)]
+    pub http: String,
+    #[serde(default)]
+    pub https: String,
+    // Comma separated hosts that bypass the proxy
+    #[serde(default)]
+    pub no_proxy: String,
+}
+
+// Main window placement in egui points, restored on the next launch
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub maximized: bool,
+    // Size of the monitor the window was on, used to detect a changed monitor setup
+    #[serde(default)]
+    pub monitor_width: f32,
+    #[serde(default)]
+    pub monitor_height: f32,
+}
+
+// Network timeouts in seconds. There is deliberately no total timeout: a large file on a slow
+// connection may take as long as it needs, only a transfer that stops making progress for
+// `stall_secs` is aborted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkTimeouts {
+    // Time allowed to establish a connection
+    pub connect_secs: u64,
+    // Longest gap between two received chunks before a request is abandoned
+    pub stall_secs: u64,
+}
+
+impl Default for NetworkTimeouts {
+    fn default() -> Self {
+        Self {
+            connect_secs: 15,
+            stall_secs: 30,
+        }
+    }
+}
+
+pub const MAX_CONNECT_TIMEOUT_SECS: u64 = 120;
+pub const MAX_STALL_TIMEOUT_SECS: u64 = 600;
+
+// How long fetched project metadata stays fresh, in hours, per content type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTtl {
+    pub mods: u64,
+    pub resource_packs: u64,
+    pub shaders: u64,
+    pub datapacks: u64,
+    pub plugins: u64,
+}
+
+impl Default for CacheTtl {
+    fn default() -> Self {
+        Self {
+            mods: 1,
+            resource_packs: 24,
+            shaders: 24,
+            datapacks: 24,
+            plugins: 1,
+        }
+    }
+}
+
+impl CacheTtl {
+    pub fn ttl_for(&self, project_type: ProjectType) -> u64 {
+        match project_type {
+            ProjectType::Mod => self.mods,
+            ProjectType::ResourcePack => self.resource_packs,
+            ProjectType::Shader => self.shaders,
+            ProjectType::Datapack => self.datapacks,
+            ProjectType::Plugin => self.plugins,
+        }
+    }
+
+    pub fn ttl_for_mut(&mut self, project_type: ProjectType) -> &mut u64 {
+        match project_type {
+            ProjectType::Mod => &mut self.mods,
+            ProjectType::ResourcePack => &mut self.resource_packs,
+            ProjectType::Shader => &mut self.shaders,
+            ProjectType::Datapack => &mut self.datapacks,
+            ProjectType::Plugin => &mut self.plugins,
+        }
+    }
+}
+
+pub const DEFAULT_MAX_PARALLEL_DOWNLOADS: usize = 3;
+pub const MAX_PARALLEL_DOWNLOADS_LIMIT: usize = 16;
+pub const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+pub const MAX_DOWNLOAD_RETRIES: u32 = 10;
+pub const DEFAULT_UPDATE_CHECK_HOURS: u64 = 6;
+pub const MAX_UPDATE_CHECK_HOURS: u64 = 48;
+pub const MAX_CACHE_TTL_HOURS: u64 = 24 * 30;
+pub const DEFAULT_MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+
+// Checks a custom API base URL. Empty means the official endpoint, trailing slashes are dropped.
+pub fn validate_api_url(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    if url.is_empty() {
+        return Ok(String::new());
+    }
+
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Not a valid URL: {e}"))?;
+    if parsed.scheme() != "https" {
+        return Err("The API URL must use https".to_string());
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err("The API URL needs a host".to_string());
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err("The API URL can't contain a query or fragment".to_string());
+    }
+
+    Ok(url.to_string())
+}
+
+pub const DEFAULT_USER_AGENT_CONTACT: &str = "github.com/allwepro/minecraft-mod-downloader";
+
+// Modrinth asks for a User-Agent that identifies the app and a way to contact its user
+pub fn user_agent(contact: &str) -> String {
+    let contact = contact.trim();
+    let contact = if contact.is_empty() {
+        DEFAULT_USER_AGENT_CONTACT
+    } else {
+        contact
+    };
+    format!(
+        "allwepro/minecraft-mod-downloader/{} ({contact})",
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+// Header values only allow printable ASCII, parentheses would break the agent's comment
+pub fn validate_user_agent_contact(contact: &str) -> Result<String, String> {
+    let contact = contact.trim();
+    if let Some(c) = contact
+        .chars()
+        .find(|c| !(c.is_ascii_graphic() || *c == ' ') || matches!(c, '(' | ')'))
+    {
+        return Err(format!("The contact can't contain '{c}'"));
+    }
+    Ok(contact.to_string())
+}
+
+// Accepts a bare collection ID or a modrinth.com/collection/<id> link
+pub fn parse_collection_id(input: &str) -> Result<String, String> {
+    let input = input.trim().trim_end_matches('/');
+    let id = match input.split_once("/collection/") {
+        Some((_, rest)) => rest.split(['/', '?', '#']).next().unwrap_or_default(),
+        None => input,
+    };
+
+    if id.is_empty() {
+        return Err("Enter a collection ID or link".to_string());
+    }
+    if !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(format!("'{id}' is not a valid collection ID"));
+    }
+    Ok(id.to_string())
+}
+
+// Accepts a bare project slug or a modrinth.com/<type>/<slug> link
+pub fn parse_project_slug(input: &str) -> Result<String, String> {
+    let input = input.trim().trim_end_matches('/');
+    let slug = match input.split_once("modrinth.com/") {
+        Some((_, rest)) => {
+            let mut segments = rest.split(['/', '?', '#']);
+            match (segments.next(), segments.next()) {
+                (Some(_), Some(slug)) => slug,
+                _ => return Err(format!("'{input}' is not a project link")),
+            }
+        }
+        None => input,
+    };
+
+    if slug.is_empty() {
+        return Err("Enter a project slug or link".to_string());
+    }
+    if slug.contains(['/', ':']) || slug.chars().any(char::is_whitespace) {
+        return Err(format!("'{slug}' is not a valid project slug"));
+    }
+    Ok(slug.to_string())
 }
 
 fn default_list_name() -> String {
     "New List".to_string()
 }
 
+fn default_max_parallel_downloads() -> usize {
+    DEFAULT_MAX_PARALLEL_DOWNLOADS
+}
+
+fn default_download_retries() -> u32 {
+    DEFAULT_DOWNLOAD_RETRIES
+}
+
+fn default_update_check_hours() -> u64 {
+    DEFAULT_UPDATE_CHECK_HOURS
+}
+
 fn default_modloader() -> ModLoader {
     ModLoader {
         id: String::new(),
@@ -177,33 +1368,122 @@ where
 }
 
 pub enum Event {
+    InitialLoadProgress {
+        step: InitialLoadStep,
+    },
     InitialDataLoaded {
         mod_lists: Vec<ModList>,
         current_list_id: Option<String>,
         minecraft_versions: Vec<MinecraftVersion>,
         mod_loaders: Vec<ModLoader>,
         default_list_name: String,
+        curseforge_api_key: String,
+        modrinth_api_url: String,
+        user_agent_contact: String,
+        proxy: Box<ProxySettings>,
+        timeouts: NetworkTimeouts,
+        max_parallel_downloads: usize,
+        download_retries: u32,
+        clean_replaced_files: bool,
+        update_check_hours: u64,
+        cache_ttl: Box<CacheTtl>,
+        compat_policy: CompatPolicy,
+        default_dirs: HashMap<ProjectType, String>,
+        bundles: Vec<Bundle>,
+        download_history: Vec<DownloadRecord>,
+    },
+    MinecraftVersionsLoaded(Vec<MinecraftVersion>),
+    CategoriesLoaded {
+        provider: ProviderKind,
+        project_type: ProjectType,
+        categories: Vec<Category>,
+    },
+    LoaderVersionsLoaded {
+        version: String,
+        loader: String,
+        versions: Vec<String>,
     },
     LoadersForTypeLoaded {
         project_type: ProjectType,
         loaders: Vec<ModLoader>,
     },
     SearchResults(Vec<Arc<ModInfo>>),
+    AlternativesLoaded {
+        mod_id: String,
+        results: Vec<Arc<ModInfo>>,
+    },
     ModDetails {
+        // The id the details were fetched with, a slug when the list refers to the project by slug
+        requested_id: String,
         info: Arc<ModInfo>,
         version: String,
         loader: String,
+        stale: bool,
+        // Expired cache shown while a fresh copy is still being fetched
+        revalidating: bool,
     },
     ModDetailsFailed {
         mod_id: String,
+        error: String,
+    },
+    CompatibilityChecked {
+        mod_id: String,
+        version: String,
+        info: Option<Arc<ModInfo>>,
+    },
+    // Mods per list with a newer version than the installed file
+    UpdateCheckFinished {
+        updates: HashMap<String, Vec<String>>,
+    },
+    SlugResolved {
+        list_id: String,
+        slug: String,
+        result: Result<Arc<ModInfo>, String>,
+    },
+    DependenciesResolved {
+        mod_info: Arc<ModInfo>,
+        dependencies: Vec<Arc<ModInfo>>,
     },
     DownloadProgress {
         mod_id: String,
-        progress: f32,
+        progress: TransferProgress,
+    },
+    DownloadRetrying {
+        mod_id: String,
+        attempt: u32,
+        max_attempts: u32,
+    },
+    JarVersionMismatch {
+        mod_id: String,
+        requirement: String,
+    },
+    PackFormatMismatch {
+        mod_id: String,
+        declared: String,
+    },
+    DownloadDeduped {
+        mod_id: String,
+        file: String,
     },
     DownloadComplete {
+        record: DownloadRecord,
+    },
+    ChangelogLoaded {
+        version_id: String,
+        changelog: Option<String>,
+    },
+    ProjectVersionsLoaded {
+        mod_id: String,
+        versions: Vec<ProjectVersion>,
+    },
+    ChosenVersionLoaded {
         mod_id: String,
-        success: bool,
+        version_id: String,
+        mod_info: Option<Arc<ModInfo>>,
+    },
+    DiskSpaceChecked {
+        mod_ids: Vec<String>,
+        space: Option<DiskSpace>,
     },
     LegacyListProgress {
         current: usize,
@@ -221,8 +1501,63 @@ pub enum Event {
         error: String,
         is_import: bool,
     },
+    LegacyImportPreviewLoaded {
+        suggested_name: String,
+        entries: Vec<String>,
+    },
+    // One per resolved entry, in the same order as the successful mods
+    LegacyMatchesResolved {
+        matches: Vec<LegacyMatch>,
+    },
+    LegacyCandidatesLoaded {
+        index: usize,
+        candidates: Vec<Arc<ModInfo>>,
+    },
     MetadataLoaded {
         download_dir: String,
         metadata: DownloadMetadata,
     },
+    DownloadDirChanged {
+        download_dir: String,
+    },
+    UnknownFilesDeleted {
+        removed: usize,
+        failed: Vec<String>,
+    },
+    OrphanedFilesDeleted {
+        removed: usize,
+        failed: Vec<String>,
+    },
+    RateLimitWaiting {
+        waiting: bool,
+    },
+    HttpClientConfigured {
+        error: Option<String>,
+    },
+    ConnectivityChanged {
+        offline: bool,
+    },
+    DroppedFileProcessed {
+        file_name: String,
+        mod_info: Option<Arc<ModInfo>>,
+    },
+    LockfileProgress {
+        current: usize,
+        total: usize,
+        message: String,
+    },
+    LockfileInstalled {
+        installed: usize,
+        unchanged: usize,
+        errors: Vec<String>,
+    },
+    VerifyProgress {
+        list_id: String,
+        checked: usize,
+        total: usize,
+    },
+    ListVerified {
+        list_id: String,
+        results: Vec<VerifyResult>,
+    },
 }
@@ -1,24 +1,32 @@
-use crate::domain::ModInfo;
-use crate::infra::{ApiService, ProjectCache};
+use crate::domain::{
+    CacheTtl, DependencyType, ModInfo, ProjectType, ProviderKind, ReleaseChannel, SearchSort,
+};
+use crate::infra::{ApiService, ProjectCache, retry_rate_limited};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+const MAX_DEPENDENCY_DEPTH: usize = 5;
+const SEARCH_CACHE_SIZE: usize = 50;
+const SEARCH_CACHE_TTL_MINUTES: i64 = 5;
+
 #[derive(Clone)]
 pub struct ModService {
     pub(crate) api_service: Arc<ApiService>,
     pool: Arc<Mutex<ModInfoPool>>,
     disk_cache: Arc<ProjectCache>,
+    search_cache: Arc<Mutex<SearchResultCache>>,
 }
 
 impl ModService {
     pub fn new(api_service: Arc<ApiService>, cache_dir: std::path::PathBuf) -> Self {
         Self {
             api_service,
-            pool: Arc::new(Mutex::new(ModInfoPool::new(500, 1))),
+            pool: Arc::new(Mutex::new(ModInfoPool::new(500, CacheTtl::default()))),
             disk_cache: Arc::new(ProjectCache::new(cache_dir)),
+            search_cache: Arc::new(Mutex::new(SearchResultCache::new(SEARCH_CACHE_SIZE))),
         }
     }
 
@@ -26,23 +34,34 @@ impl ModService {
         self.disk_cache.clone()
     }
 
+    pub async fn set_cache_ttl(&self, ttl: CacheTtl) {
+        self.pool.lock().await.set_ttl(ttl);
+        self.disk_cache.set_ttl(ttl).await;
+    }
+
     pub async fn get_mod_by_id(
         &self,
+        provider: ProviderKind,
         id: &str,
         version: &str,
         loader: &str,
+        channel: ReleaseChannel,
     ) -> Result<Arc<ModInfo>> {
-        self.get_mod_internal(id, version, loader, |pool, v, l| pool.get(id, v, l))
-            .await
+        self.get_mod_internal(provider, id, version, loader, channel, |pool, v, l| {
+            pool.get(id, v, l)
+        })
+        .await
     }
 
     pub async fn get_mod_by_slug(
         &self,
+        provider: ProviderKind,
         slug: &str,
         version: &str,
         loader: &str,
+        channel: ReleaseChannel,
     ) -> Result<Arc<ModInfo>> {
-        self.get_mod_internal(slug, version, loader, |pool, v, l| {
+        self.get_mod_internal(provider, slug, version, loader, channel, |pool, v, l| {
             pool.get_by_slug(slug, v, l)
         })
         .await
@@ -50,17 +69,21 @@ impl ModService {
 
     async fn get_mod_internal<F>(
         &self,
+        provider: ProviderKind,
         identifier: &str,
         version: &str,
         loader: &str,
+        channel: ReleaseChannel,
         cache_check: F,
     ) -> Result<Arc<ModInfo>>
     where
         F: FnOnce(&ModInfoPool, &str, &str) -> Option<Arc<ModInfo>>,
     {
+        let context = channel.cache_context(loader);
+
         if let Some(info) = {
             let pool = self.pool.lock().await;
-            cache_check(&pool, version, loader)
+            cache_check(&pool, version, &context)
         } {
             if !info.version.is_empty() {
                 log::debug!("Returning cached complete info for {identifier}");
@@ -69,28 +92,173 @@ impl ModService {
             log::debug!("Cached info for {identifier} has empty version, fetching fresh");
         }
 
-        if let Some(cached_info) = self.disk_cache.get(identifier, version, loader).await {
+        if let Some(cached_info) = self.disk_cache.get(identifier, version, &context).await {
             log::debug!("Returning disk-cached info for {identifier}");
             let mut pool = self.pool.lock().await;
-            return Ok(pool.insert(cached_info, version.to_string(), loader.to_string()));
+            return Ok(pool.insert(cached_info, version.to_string(), context));
         }
 
         let _permit = self.api_service.limiter.acquire(1).await;
 
-        log::debug!("Fetching mod details for {identifier} (version={version} loader={loader})");
+        log::debug!(
+            "Fetching mod details for {identifier} from {} (version={version} loader={loader})",
+            provider.display_name()
+        );
+
+        let mod_provider = self.api_service.provider(provider);
+        let details = self.api_service.track(
+            retry_rate_limited(|| {
+                mod_provider.fetch_mod_details(identifier, version, loader, channel)
+            })
+            .await,
+        )?;
+
+        self.disk_cache
+            .set(identifier, version, &context, details.clone())
+            .await;
+
+        let mut pool = self.pool.lock().await;
+        Ok(pool.insert(details, version.to_string(), context))
+    }
+
+    pub async fn get_cached_mod(
+        &self,
+        id: &str,
+        pinned_version: Option<&str>,
+        version: &str,
+        loader: &str,
+        channel: ReleaseChannel,
+    ) -> Option<Arc<ModInfo>> {
+        let loader = &channel.cache_context(loader);
+        if let Some(version_id) = pinned_version {
+            let cache_key = format!("{id}@{version_id}");
+            return self
+                .disk_cache
+                .get_stale(&cache_key, "", "")
+                .await
+                .map(Arc::new);
+        }
+
+        if let Some(info) = self.pool.lock().await.get(id, version, loader) {
+            return Some(info);
+        }
+
+        let cached_info = self.disk_cache.get_stale(id, version, loader).await?;
+        let mut pool = self.pool.lock().await;
+        Some(pool.insert(cached_info, version.to_string(), loader.to_string()))
+    }
 
+    pub async fn get_expired_mod(
+        &self,
+        id: &str,
+        pinned_version: Option<&str>,
+        version: &str,
+        loader: &str,
+        channel: ReleaseChannel,
+    ) -> Option<Arc<ModInfo>> {
+        let info = match pinned_version {
+            Some(version_id) => {
+                self.disk_cache
+                    .get_expired(&format!("{id}@{version_id}"), "", "")
+                    .await
+            }
+            None => {
+                self.disk_cache
+                    .get_expired(id, version, &channel.cache_context(loader))
+                    .await
+            }
+        };
+        info.map(Arc::new)
+    }
+
+    pub async fn get_pinned_mod(
+        &self,
+        provider: ProviderKind,
+        id: &str,
+        version_id: &str,
+    ) -> Result<Arc<ModInfo>> {
+        let cache_key = format!("{id}@{version_id}");
+
+        if let Some(cached_info) = self.disk_cache.get(&cache_key, "", "").await {
+            log::debug!("Returning disk-cached pinned info for {cache_key}");
+            return Ok(Arc::new(cached_info));
+        }
+
+        let _permit = self.api_service.limiter.acquire(1).await;
+
+        log::debug!("Fetching pinned version {version_id} for {id}");
+
+        let mod_provider = self.api_service.provider(provider);
         let details = self
             .api_service
-            .provider
-            .fetch_mod_details(identifier, version, loader)
-            .await?;
+            .track(retry_rate_limited(|| mod_provider.fetch_mod_version(id, version_id)).await)?;
 
         self.disk_cache
-            .set(identifier, version, loader, details.clone())
+            .set(&cache_key, "", "", details.clone())
             .await;
 
-        let mut pool = self.pool.lock().await;
-        Ok(pool.insert(details, version.to_string(), loader.to_string()))
+        Ok(Arc::new(details))
+    }
+
+    pub async fn resolve_required_dependencies(
+        &self,
+        mod_info: &ModInfo,
+        version: &str,
+        loader: &str,
+        channel: ReleaseChannel,
+        known_ids: HashSet<String>,
+    ) -> Vec<Arc<ModInfo>> {
+        let mut visited = known_ids;
+        visited.insert(mod_info.id.clone());
+
+        let mut resolved = Vec::new();
+        let mut frontier = vec![(mod_info.provider, mod_info.id.clone())];
+
+        for depth in 0..=MAX_DEPENDENCY_DEPTH {
+            let mut next = Vec::new();
+
+            for (provider, mod_id) in frontier {
+                let info = match self
+                    .get_mod_by_id(provider, &mod_id, version, loader, channel)
+                    .await
+                {
+                    Ok(info) => info,
+                    Err(e) => {
+                        log::warn!("Failed to resolve dependency {mod_id}: {e}");
+                        continue;
+                    }
+                };
+
+                if depth > 0 {
+                    resolved.push(info.clone());
+                }
+
+                if depth == MAX_DEPENDENCY_DEPTH {
+                    continue;
+                }
+
+                for dependency in &info.dependencies {
+                    if dependency.dependency_type == DependencyType::Required
+                        && visited.insert(dependency.mod_id.clone())
+                    {
+                        next.push((info.provider, dependency.mod_id.clone()));
+                    }
+                }
+            }
+
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        log::debug!(
+            "Resolved {} required dependencies for {}",
+            resolved.len(),
+            mod_info.id
+        );
+
+        resolved
     }
 
     pub async fn cache_search_results(
@@ -105,6 +273,130 @@ impl ModService {
             .map(|mod_info| pool.insert(mod_info, version.clone(), loader.clone()))
             .collect()
     }
+
+    pub async fn get_search(&self, key: &SearchKey) -> Option<Vec<Arc<ModInfo>>> {
+        self.search_cache.lock().await.get(key)
+    }
+
+    pub async fn remember_search(&self, key: SearchKey, results: Vec<Arc<ModInfo>>) {
+        self.search_cache.lock().await.insert(key, results);
+    }
+
+    pub async fn forget_search(&self, key: &SearchKey) {
+        self.search_cache.lock().await.remove(key);
+    }
+
+    // Drops cached details that are known to be outdated, the next lookup fetches them again
+    pub async fn forget_mod(
+        &self,
+        mod_id: &str,
+        version: &str,
+        loader: &str,
+        channel: ReleaseChannel,
+    ) {
+        let context = channel.cache_context(loader);
+        self.pool.lock().await.remove(mod_id);
+        self.disk_cache.remove(mod_id, version, &context).await;
+    }
+}
+
+// Everything that changes what a search returns
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SearchKey {
+    pub provider: ProviderKind,
+    pub query: String,
+    pub project_type: ProjectType,
+    pub version: String,
+    pub loader: String,
+    pub categories: Vec<String>,
+    pub sort: SearchSort,
+}
+
+impl SearchKey {
+    pub fn new(
+        provider: ProviderKind,
+        query: &str,
+        project_type: ProjectType,
+        version: &str,
+        loader: &str,
+        categories: &[String],
+        sort: SearchSort,
+    ) -> Self {
+        let mut categories = categories.to_vec();
+        categories.sort();
+        Self {
+            provider,
+            query: query.trim().to_lowercase(),
+            project_type,
+            version: version.to_string(),
+            loader: loader.to_string(),
+            categories,
+            sort,
+        }
+    }
+}
+
+struct CachedSearch {
+    results: Vec<Arc<ModInfo>>,
+    cached_at: DateTime<Utc>,
+    last_used: DateTime<Utc>,
+}
+
+// Short-lived, so browsing back and forth doesn't refetch but new projects still show up soon
+struct SearchResultCache {
+    cache: HashMap<SearchKey, CachedSearch>,
+    max_size: usize,
+}
+
+impl SearchResultCache {
+    fn new(max_size: usize) -> Self {
+        Self {
+            cache: HashMap::new(),
+            max_size,
+        }
+    }
+
+    fn get(&mut self, key: &SearchKey) -> Option<Vec<Arc<ModInfo>>> {
+        let now = Utc::now();
+        let cached = self.cache.get_mut(key)?;
+        if now.signed_duration_since(cached.cached_at).num_minutes() >= SEARCH_CACHE_TTL_MINUTES {
+            self.cache.remove(key);
+            return None;
+        }
+        cached.last_used = now;
+        Some(cached.results.clone())
+    }
+
+    fn insert(&mut self, key: SearchKey, results: Vec<Arc<ModInfo>>) {
+        if !self.cache.contains_key(&key) && self.cache.len() >= self.max_size {
+            self.evict_oldest();
+        }
+        let now = Utc::now();
+        self.cache.insert(
+            key,
+            CachedSearch {
+                results,
+                cached_at: now,
+                last_used: now,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &SearchKey) {
+        self.cache.remove(key);
+    }
+
+    // Drops the least recently used search
+    fn evict_oldest(&mut self) {
+        if let Some(oldest_key) = self
+            .cache
+            .iter()
+            .min_by_key(|(_, v)| v.last_used)
+            .map(|(k, _)| k.clone())
+        {
+            self.cache.remove(&oldest_key);
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -125,10 +417,10 @@ impl CachedModInfo {
         }
     }
 
-    pub fn is_expired(&self, max_age_hours: i64) -> bool {
+    pub fn is_expired(&self, ttl: &CacheTtl) -> bool {
         let now = Utc::now();
         let age = now.signed_duration_since(self.cached_at);
-        age.num_hours() >= max_age_hours
+        age.num_hours() >= ttl.ttl_for(self.info.project_type) as i64
     }
 
     pub fn matches_context(&self, version: &str, loader: &str) -> bool {
@@ -142,23 +434,28 @@ pub struct ModInfoPool {
     base_info_cache: HashMap<String, Arc<ModInfo>>,
     slug_to_id: HashMap<String, String>,
     max_size: usize,
-    max_age_hours: i64,
+    ttl: CacheTtl,
 }
 
 impl ModInfoPool {
-    pub fn new(max_size: usize, max_age_hours: i64) -> Self {
+    pub fn new(max_size: usize, ttl: CacheTtl) -> Self {
         Self {
             cache: HashMap::new(),
             base_info_cache: HashMap::new(),
             slug_to_id: HashMap::new(),
             max_size,
-            max_age_hours,
+            ttl,
         }
     }
 
+    pub fn set_ttl(&mut self, ttl: CacheTtl) {
+        self.ttl = ttl;
+        self.cache.retain(|_, cached| !cached.is_expired(&ttl));
+    }
+
     pub fn get(&self, mod_id: &str, version: &str, loader: &str) -> Option<Arc<ModInfo>> {
         self.cache.get(mod_id).and_then(|cached| {
-            if cached.is_expired(self.max_age_hours) || !cached.matches_context(version, loader) {
+            if cached.is_expired(&self.ttl) || !cached.matches_context(version, loader) {
                 None
             } else {
                 Some(cached.info.clone())
@@ -184,7 +481,7 @@ impl ModInfoPool {
         self.base_info_cache.insert(id.clone(), arc_info.clone());
 
         if let Some(existing) = self.cache.get(&id)
-            && !existing.is_expired(self.max_age_hours)
+            && !existing.is_expired(&self.ttl)
             && existing.matches_context(&version, &loader)
         {
             if !existing.info.version.is_empty() {
@@ -212,6 +509,12 @@ impl ModInfoPool {
         arc_info
     }
 
+    pub fn remove(&mut self, mod_id: &str) {
+        if let Some(cached) = self.cache.remove(mod_id) {
+            self.slug_to_id.remove(&cached.info.slug);
+        }
+    }
+
     fn evict_oldest(&mut self) {
         if let Some(oldest_key) = self
             .cache
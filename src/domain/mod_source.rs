@@ -1,4 +1,7 @@
-use super::{MinecraftVersion, ModInfo, ModLoader, ProjectType};
+use super::{
+    Category, FileHashes, MinecraftVersion, ModInfo, ModLoader, ProjectType, ProjectVersion,
+    ReleaseChannel, SearchSort, TransferProgress,
+};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -9,6 +12,8 @@ pub trait ModProvider: Send + Sync {
         version: &str,
         loader: &str,
         project_type: &ProjectType,
+        categories: &[String],
+        sort: SearchSort,
     ) -> anyhow::Result<Vec<ModInfo>>;
 
     async fn fetch_mod_details(
@@ -16,8 +21,17 @@ pub trait ModProvider: Send + Sync {
         mod_id: &str,
         version: &str,
         loader: &str,
+        channel: ReleaseChannel,
     ) -> anyhow::Result<ModInfo>;
 
+    async fn fetch_mod_version(&self, mod_id: &str, version_id: &str) -> anyhow::Result<ModInfo>;
+
+    async fn fetch_project_versions(
+        &self,
+        mod_id: &str,
+        loader: &str,
+    ) -> anyhow::Result<Vec<ProjectVersion>>;
+
     async fn get_minecraft_versions(&self) -> anyhow::Result<Vec<MinecraftVersion>>;
 
     async fn get_mod_loaders_for_type(
@@ -25,12 +39,66 @@ pub trait ModProvider: Send + Sync {
         project_type: ProjectType,
     ) -> anyhow::Result<Vec<ModLoader>>;
 
+    async fn fetch_categories(&self, project_type: ProjectType) -> anyhow::Result<Vec<Category>>;
+
+    async fn fetch_changelog(
+        &self,
+        mod_id: &str,
+        version_id: &str,
+    ) -> anyhow::Result<Option<String>>;
+
     async fn download_mod(
         &self,
         download_url: &str,
+        hashes: &FileHashes,
         destination: &std::path::Path,
-        progress_callback: Box<dyn Fn(f32) + Send>,
+        progress_callback: Box<dyn Fn(TransferProgress) + Send>,
     ) -> anyhow::Result<()>;
 
     fn get_project_link(&self, project_type: &ProjectType, mod_id: &str) -> String;
 }
+
+#[derive(Debug)]
+pub struct RateLimited {
+    pub retry_after: Option<std::time::Duration>,
+}
+
+impl std::fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.retry_after {
+            Some(delay) => write!(f, "Rate limited, retry after {}s", delay.as_secs()),
+            None => write!(f, "Rate limited"),
+        }
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+#[derive(Debug)]
+pub struct HashMismatch {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for HashMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Hash mismatch, expected {} but got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for HashMismatch {}
+
+#[derive(Debug)]
+pub struct DownloadCancelled;
+
+impl std::fmt::Display for DownloadCancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Download cancelled")
+    }
+}
+
+impl std::error::Error for DownloadCancelled {}
@@ -1,43 +1,247 @@
-use crate::adapters::ModrinthProvider;
-use crate::domain::ModProvider;
-use std::sync::Arc;
-use tokio::sync::Semaphore;
+use crate::adapters::{CurseForgeProvider, ModrinthProvider};
+use crate::domain::{
+    DEFAULT_MAX_PARALLEL_DOWNLOADS, HashMismatch, ModProvider, NetworkTimeouts, ProviderKind,
+    ProxySettings, RateLimited,
+};
+use reqwest::Client;
+use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, watch};
+
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+const OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+const MAX_BACKOFF_SECS: u64 = 60;
 
 #[derive(Clone)]
 pub struct ApiService {
-    pub provider: Arc<dyn ModProvider>,
+    pub modrinth: Arc<ModrinthProvider>,
+    pub curseforge: Arc<CurseForgeProvider>,
     pub limiter: Arc<ConnectionLimiter>,
+    pub download_limiter: Arc<ConnectionLimiter>,
+    pub connectivity: Arc<ConnectivityTracker>,
+    client: Arc<RwLock<Client>>,
 }
 
 impl ApiService {
     pub fn new() -> Self {
-        let provider: Arc<dyn ModProvider> = Arc::new(ModrinthProvider::new());
         let connection_limiter = Arc::new(ConnectionLimiter::new(5));
 
-        Self {
-            provider,
+        let api_service = Self {
+            modrinth: Arc::new(ModrinthProvider::new()),
+            curseforge: Arc::new(CurseForgeProvider::new()),
             limiter: connection_limiter,
+            download_limiter: Arc::new(ConnectionLimiter::new(DEFAULT_MAX_PARALLEL_DOWNLOADS)),
+            connectivity: Arc::new(ConnectivityTracker::new()),
+            client: Arc::new(RwLock::new(Client::new())),
+        };
+        // Requests made before the config is loaded still identify the app
+        if let Ok(client) =
+            super::build_client(&ProxySettings::default(), NetworkTimeouts::default(), "")
+        {
+            api_service.set_client(client);
+        }
+        api_service
+    }
+
+    // Client for requests outside the providers, such as icons and loader metadata
+    pub fn client(&self) -> Client {
+        self.client.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn set_client(&self, client: Client) {
+        self.modrinth.set_client(client.clone());
+        self.curseforge.set_client(client.clone());
+        if let Ok(mut current) = self.client.write() {
+            *current = client;
+        }
+    }
+
+    pub fn track<T>(&self, result: anyhow::Result<T>) -> anyhow::Result<T> {
+        self.connectivity.record(&result);
+        result
+    }
+
+    pub fn provider(&self, kind: ProviderKind) -> Arc<dyn ModProvider> {
+        match kind {
+            ProviderKind::Modrinth => self.modrinth.clone(),
+            ProviderKind::CurseForge => self.curseforge.clone(),
+        }
+    }
+}
+
+pub async fn retry_rate_limited<T, F, Fut>(mut operation: F) -> anyhow::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match operation().await {
+            Err(e) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                let Some(rate_limited) = e.downcast_ref::<RateLimited>() else {
+                    return Err(e);
+                };
+                let delay = retry_backoff(&e, attempt + 1);
+                log::warn!("{rate_limited}, retrying in {}s", delay.as_secs());
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return result,
         }
     }
 }
 
+// Timeouts, dropped connections, 5xx and 429 are worth retrying, anything else is permanent
+pub fn is_retriable_error(error: &anyhow::Error) -> bool {
+    if error.is::<HashMismatch>() {
+        return false;
+    }
+
+    error.chain().any(|cause| {
+        if cause.is::<RateLimited>() {
+            return true;
+        }
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| match e.status() {
+                Some(status) => {
+                    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                None => e.is_timeout() || e.is_connect() || e.is_request() || e.is_body(),
+            })
+    })
+}
+
+pub fn retry_backoff(error: &anyhow::Error, attempt: u32) -> Duration {
+    error
+        .downcast_ref::<RateLimited>()
+        .and_then(|r| r.retry_after)
+        .unwrap_or(Duration::from_secs(2u64.pow(attempt).min(MAX_BACKOFF_SECS)))
+}
+
+pub struct ConnectivityTracker {
+    consecutive_failures: AtomicU32,
+    offline_tx: watch::Sender<bool>,
+}
+
+impl ConnectivityTracker {
+    pub fn new() -> Self {
+        Self {
+            consecutive_failures: AtomicU32::new(0),
+            offline_tx: watch::Sender::new(false),
+        }
+    }
+
+    pub fn record<T>(&self, result: &anyhow::Result<T>) {
+        let failed = result.as_ref().err().is_some_and(is_network_error);
+
+        if failed {
+            let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+            if failures >= OFFLINE_FAILURE_THRESHOLD {
+                self.offline_tx
+                    .send_if_modified(|offline| !std::mem::replace(offline, true));
+            }
+        } else {
+            self.consecutive_failures.store(0, Ordering::Relaxed);
+            self.offline_tx
+                .send_if_modified(|offline| std::mem::replace(offline, false));
+        }
+    }
+
+    // Used when the user chooses to work offline; the probe loop clears it once a request succeeds
+    pub fn mark_offline(&self) {
+        self.consecutive_failures
+            .store(OFFLINE_FAILURE_THRESHOLD, Ordering::Relaxed);
+        self.offline_tx
+            .send_if_modified(|offline| !std::mem::replace(offline, true));
+    }
+
+    pub fn is_offline(&self) -> bool {
+        *self.offline_tx.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<bool> {
+        self.offline_tx.subscribe()
+    }
+}
+
+fn is_network_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect() || e.is_timeout())
+    })
+}
+
+// Permits still owed after a shrink while they were in use
+struct LimiterState {
+    max_connections: usize,
+    debt: usize,
+}
+
 #[derive(Clone)]
 pub struct ConnectionLimiter {
     semaphore: Arc<Semaphore>,
+    state: Arc<Mutex<LimiterState>>,
 }
 
 impl ConnectionLimiter {
     pub fn new(max_connections: usize) -> Self {
         Self {
             semaphore: Arc::new(Semaphore::new(max_connections)),
+            state: Arc::new(Mutex::new(LimiterState {
+                max_connections,
+                debt: 0,
+            })),
         }
     }
 
-    pub async fn acquire(&self, slots: u32) -> tokio::sync::OwnedSemaphorePermit {
-        self.semaphore
+    // Shrinking never interrupts holders: idle permits are removed right away and the
+    // remainder is taken back as in-flight work releases its permits
+    pub fn set_max_connections(&self, max_connections: usize) {
+        let mut state = self.state.lock().unwrap();
+        if max_connections > state.max_connections {
+            let added = max_connections - state.max_connections;
+            let repaid = added.min(state.debt);
+            state.debt -= repaid;
+            self.semaphore.add_permits(added - repaid);
+        } else if max_connections < state.max_connections {
+            let excess = state.max_connections - max_connections;
+            state.debt += excess - self.semaphore.forget_permits(excess);
+        }
+        state.max_connections = max_connections;
+    }
+
+    pub async fn acquire(&self, slots: u32) -> ConnectionPermit {
+        let permit = self
+            .semaphore
             .clone()
             .acquire_many_owned(slots)
             .await
-            .expect("Semaphore closed")
+            .expect("Semaphore closed");
+        ConnectionPermit {
+            permit,
+            state: self.state.clone(),
+        }
+    }
+}
+
+pub struct ConnectionPermit {
+    permit: OwnedSemaphorePermit,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        let owed = state.debt.min(self.permit.num_permits());
+        if owed > 0
+            && let Some(owed_permits) = self.permit.split(owed)
+        {
+            owed_permits.forget();
+            state.debt -= owed;
+        }
     }
 }
@@ -1,4 +1,7 @@
-use crate::domain::{AppConfig, ModList};
+use crate::domain::{
+    AppConfig, CompatPolicy, DEFAULT_DOWNLOAD_RETRIES, DEFAULT_MAX_PARALLEL_DOWNLOADS,
+    DEFAULT_UPDATE_CHECK_HOURS, ModList, NetworkTimeouts, ProxySettings, Theme,
+};
 
 #[derive(Clone)]
 pub struct ConfigManager {
@@ -75,11 +78,30 @@ impl ConfigManager {
         let config = AppConfig {
             current_list_id: None,
             default_list_name: "New List".to_string(),
+            curseforge_api_key: String::new(),
+            modrinth_api_url: String::new(),
+            user_agent_contact: String::new(),
+            proxy: ProxySettings::default(),
+            timeouts: NetworkTimeouts::default(),
+            max_parallel_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+            download_retries: DEFAULT_DOWNLOAD_RETRIES,
+            clean_replaced_files: false,
+            update_check_hours: DEFAULT_UPDATE_CHECK_HOURS,
+            cache_ttl: Default::default(),
+            compat_policy: CompatPolicy::default(),
+            default_dirs: Default::default(),
+            bundles: Vec::new(),
+            window: None,
+            theme: Theme::default(),
         };
         self.save_config(&config).await?;
         Ok(config)
     }
 
+    pub fn get_download_history_path(&self) -> std::path::PathBuf {
+        self.config_dir.join("download_history.jsonl")
+    }
+
     pub fn get_cache_dir(&self) -> std::path::PathBuf {
         self.config_dir.clone().join("cache")
     }
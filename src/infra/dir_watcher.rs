@@ -0,0 +1,77 @@
+use crate::domain::Event;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{DebounceEventResult, Debouncer, new_debouncer};
+use std::path::Path;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+pub struct DirWatcher {
+    debouncer: Option<Debouncer<RecommendedWatcher>>,
+    watched_dir: Option<String>,
+    tx: mpsc::Sender<Event>,
+}
+
+impl DirWatcher {
+    pub fn new(tx: mpsc::Sender<Event>) -> Self {
+        Self {
+            debouncer: None,
+            watched_dir: None,
+            tx,
+        }
+    }
+
+    pub fn watch(&mut self, download_dir: String) {
+        if self.watched_dir.as_ref() == Some(&download_dir) {
+            return;
+        }
+
+        self.debouncer = None;
+        self.watched_dir = None;
+
+        let path = Path::new(&download_dir);
+        if !path.is_dir() {
+            log::debug!("Not watching {download_dir}, directory does not exist yet");
+            return;
+        }
+
+        let tx = self.tx.clone();
+        let dir = download_dir.clone();
+        let debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| {
+            let events = match result {
+                Ok(events) => events,
+                Err(e) => {
+                    log::warn!("Watching {dir} failed: {e}");
+                    return;
+                }
+            };
+
+            // Our own metadata is a dotfile, running downloads and exports write .part/.tmp files
+            let relevant = events.iter().any(|event| {
+                event.path.file_name().is_some_and(|name| {
+                    let name = name.to_string_lossy();
+                    !name.starts_with('.') && !name.ends_with(".part") && !name.ends_with(".tmp")
+                })
+            });
+            if relevant {
+                let _ = tx.try_send(Event::DownloadDirChanged {
+                    download_dir: dir.clone(),
+                });
+            }
+        });
+
+        match debouncer {
+            Ok(mut debouncer) => {
+                if let Err(e) = debouncer.watcher().watch(path, RecursiveMode::NonRecursive) {
+                    log::warn!("Failed to watch {download_dir}: {e}");
+                    return;
+                }
+                log::debug!("Watching {download_dir} for changes");
+                self.debouncer = Some(debouncer);
+                self.watched_dir = Some(download_dir);
+            }
+            Err(e) => log::warn!("Failed to create watcher for {download_dir}: {e}"),
+        }
+    }
+}
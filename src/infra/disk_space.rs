@@ -0,0 +1,28 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+pub struct DiskSpace {
+    pub needed: u64,
+    pub available: u64,
+}
+
+impl DiskSpace {
+    pub fn is_sufficient(&self) -> bool {
+        self.available >= self.needed
+    }
+}
+
+pub async fn check_disk_space(path: &Path, needed: u64) -> Result<DiskSpace> {
+    // The download directory may not exist yet, so query the closest existing ancestor
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .context("No existing parent directory for download path")?
+        .to_path_buf();
+
+    let available = tokio::task::spawn_blocking(move || fs2::available_space(&existing))
+        .await?
+        .context("Failed to query available disk space")?;
+
+    Ok(DiskSpace { needed, available })
+}
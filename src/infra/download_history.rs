@@ -0,0 +1,48 @@
+use crate::domain::DownloadRecord;
+use anyhow::Result;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+// Stored as JSON lines so each download only appends a single record.
+pub struct DownloadHistory {
+    path: PathBuf,
+}
+
+impl DownloadHistory {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub async fn load(&self) -> Result<Vec<DownloadRecord>> {
+        let content = match tokio::fs::read_to_string(&self.path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .filter_map(|l| match serde_json::from_str(l) {
+                Ok(record) => Some(record),
+                Err(e) => {
+                    log::warn!("Skipping corrupt download history entry: {e}");
+                    None
+                }
+            })
+            .collect())
+    }
+
+    pub async fn append(&self, record: &DownloadRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        Ok(())
+    }
+}
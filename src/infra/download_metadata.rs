@@ -1,14 +1,24 @@
+use crate::domain::FileHashes;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::OwnedMutexGuard;
+
+// One lock per download folder, so read-modify-write cycles on .mcd.json never interleave
+static METADATA_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DownloadMetadataEntry {
     pub file: String,
     pub version: String,
     pub downloaded_at: DateTime<Utc>,
+    // Empty for files recorded before hashes were tracked
+    #[serde(default)]
+    pub hashes: FileHashes,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -27,13 +37,20 @@ impl DownloadMetadata {
         self.mods.get(mod_id)
     }
 
-    pub fn update_entry(&mut self, mod_id: String, file: String, version: String) {
+    pub fn update_entry(
+        &mut self,
+        mod_id: String,
+        file: String,
+        version: String,
+        hashes: FileHashes,
+    ) {
         self.mods.insert(
             mod_id,
             DownloadMetadataEntry {
                 file,
                 version,
                 downloaded_at: Utc::now(),
+                hashes,
             },
         );
     }
@@ -42,6 +59,29 @@ impl DownloadMetadata {
         self.mods.remove(mod_id);
     }
 
+    // True when another entry records the same file, as deduped downloads do
+    pub fn is_shared(&self, mod_id: &str) -> bool {
+        self.get_entry(mod_id).is_some_and(|entry| {
+            self.mods
+                .iter()
+                .any(|(id, other)| id != mod_id && other.file == entry.file)
+        })
+    }
+
+    // Entries whose mod is not among the given project ids, sorted by file name
+    pub fn orphaned_entries(
+        &self,
+        project_ids: &HashSet<&str>,
+    ) -> Vec<(&String, &DownloadMetadataEntry)> {
+        let mut orphans: Vec<_> = self
+            .mods
+            .iter()
+            .filter(|(mod_id, _)| !project_ids.contains(mod_id.as_str()))
+            .collect();
+        orphans.sort_by(|a, b| a.1.file.cmp(&b.1.file));
+        orphans
+    }
+
     pub fn validate_and_cleanup(&mut self, download_dir: &Path) {
         let mut to_remove = Vec::new();
 
@@ -72,10 +112,36 @@ pub async fn read_download_metadata(download_dir: &Path) -> Result<DownloadMetad
     Ok(metadata)
 }
 
-pub async fn write_download_metadata(
-    download_dir: &Path,
-    metadata: &DownloadMetadata,
-) -> Result<()> {
+// Exclusive access to a folder's metadata, the only way to write it
+pub struct DownloadMetadataGuard {
+    download_dir: PathBuf,
+    _lock: OwnedMutexGuard<()>,
+}
+
+impl DownloadMetadataGuard {
+    pub async fn read(&self) -> Result<DownloadMetadata> {
+        read_download_metadata(&self.download_dir).await
+    }
+
+    pub async fn write(&self, metadata: &DownloadMetadata) -> Result<()> {
+        write_download_metadata(&self.download_dir, metadata).await
+    }
+}
+
+pub async fn lock_download_metadata(download_dir: &Path) -> DownloadMetadataGuard {
+    let lock = METADATA_LOCKS
+        .lock()
+        .unwrap()
+        .entry(download_dir.to_path_buf())
+        .or_default()
+        .clone();
+    DownloadMetadataGuard {
+        download_dir: download_dir.to_path_buf(),
+        _lock: lock.lock_owned().await,
+    }
+}
+
+async fn write_download_metadata(download_dir: &Path, metadata: &DownloadMetadata) -> Result<()> {
     tokio::fs::create_dir_all(download_dir).await?;
 
     let metadata_path = download_dir.join(".mcd.json");
@@ -89,21 +155,25 @@ pub async fn write_download_metadata(
     Ok(())
 }
 
+// Drops entries whose file is gone and saves the result
+pub async fn prune_download_metadata(download_dir: &Path) -> Result<DownloadMetadata> {
+    let guard = lock_download_metadata(download_dir).await;
+    let mut metadata = guard.read().await?;
+    metadata.validate_and_cleanup(download_dir);
+    guard.write(&metadata).await?;
+    Ok(metadata)
+}
+
 pub async fn update_metadata_entry(
     download_dir: &Path,
     mod_id: String,
     filename: String,
     version: String,
+    hashes: FileHashes,
 ) -> Result<()> {
-    let mut metadata = read_download_metadata(download_dir).await?;
-    metadata.update_entry(mod_id, filename, version);
-    write_download_metadata(download_dir, &metadata).await?;
-    Ok(())
-}
-
-pub async fn remove_metadata_entry(download_dir: &Path, mod_id: &str) -> Result<()> {
-    let mut metadata = read_download_metadata(download_dir).await?;
-    metadata.remove_entry(mod_id);
-    write_download_metadata(download_dir, &metadata).await?;
+    let guard = lock_download_metadata(download_dir).await;
+    let mut metadata = guard.read().await?;
+    metadata.update_entry(mod_id, filename, version, hashes);
+    guard.write(&metadata).await?;
     Ok(())
 }
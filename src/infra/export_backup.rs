@@ -0,0 +1,26 @@
+use crate::domain::OverwritePolicy;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+// Copies an existing export target aside before it gets replaced, returning where the copy went
+pub async fn backup_existing_file(path: &Path, policy: OverwritePolicy) -> Result<Option<PathBuf>> {
+    if policy != OverwritePolicy::Backup || !tokio::fs::try_exists(path).await.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    let file_name = path
+        .file_name()
+        .context("Export path has no file name")?
+        .to_string_lossy();
+    let backup_path = path.with_file_name(format!(
+        "{file_name}.{}.bak",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    tokio::fs::copy(path, &backup_path)
+        .await
+        .with_context(|| format!("Failed to back up {file_name}"))?;
+    log::info!("Backed up {} to {}", path.display(), backup_path.display());
+
+    Ok(Some(backup_path))
+}
@@ -0,0 +1,238 @@
+use crate::domain::{MinecraftVersion, ModLoader, ModProvider, ProjectType, VersionType};
+use crate::infra::ApiService;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const GAME_DATA_TTL_HOURS: i64 = 24;
+
+#[derive(Serialize, Deserialize)]
+struct CachedGameData<T> {
+    cached_at: DateTime<Utc>,
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct LoaderMetaEntry {
+    loader: LoaderMetaVersion,
+}
+
+#[derive(Deserialize)]
+struct LoaderMetaVersion {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct NeoForgeVersions {
+    versions: Vec<String>,
+}
+
+// Loader builds that support a Minecraft version, newest first, empty for loaders without per-version builds
+pub async fn fetch_loader_versions(
+    client: &reqwest::Client,
+    version: &str,
+    loader: &str,
+) -> anyhow::Result<Vec<String>> {
+    let url = match loader {
+        "fabric" => format!("https://meta.fabricmc.net/v2/versions/loader/{version}"),
+        "quilt" => format!("https://meta.quiltmc.org/v3/versions/loader/{version}"),
+        "forge" => return fetch_forge_versions(client, version).await,
+        "neoforge" => return fetch_neoforge_versions(client, version).await,
+        _ => return Ok(Vec::new()),
+    };
+
+    let entries: Vec<LoaderMetaEntry> = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(entries.into_iter().map(|e| e.loader.version).collect())
+}
+
+// Forge lists builds as "<minecraft>-<forge>" per Minecraft version, oldest first
+async fn fetch_forge_versions(
+    client: &reqwest::Client,
+    version: &str,
+) -> anyhow::Result<Vec<String>> {
+    let url = "https://files.minecraftforge.net/net/minecraftforge/forge/maven-metadata.json";
+    let mut builds: HashMap<String, Vec<String>> = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let prefix = format!("{version}-");
+    Ok(builds
+        .remove(version)
+        .unwrap_or_default()
+        .into_iter()
+        .rev()
+        .map(|b| b.strip_prefix(&prefix).map(str::to_string).unwrap_or(b))
+        .collect())
+}
+
+// NeoForge drops the leading "1." of the Minecraft version, e.g. 21.1.x is for 1.21.1
+async fn fetch_neoforge_versions(
+    client: &reqwest::Client,
+    version: &str,
+) -> anyhow::Result<Vec<String>> {
+    let url = "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+    let versions: NeoForgeVersions = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let minor = version.trim_start_matches("1.");
+    let prefix = if minor.contains('.') {
+        format!("{minor}.")
+    } else {
+        format!("{minor}.0.")
+    };
+    Ok(versions
+        .versions
+        .into_iter()
+        .rev()
+        .filter(|v| v.starts_with(&prefix))
+        .collect())
+}
+
+pub struct GameDataService {
+    api_service: Arc<ApiService>,
+    cache_dir: PathBuf,
+}
+
+impl GameDataService {
+    pub fn new(api_service: Arc<ApiService>, cache_dir: PathBuf) -> Self {
+        Self {
+            api_service,
+            cache_dir: cache_dir.join("game_data"),
+        }
+    }
+
+    pub async fn minecraft_versions(&self, force_refresh: bool) -> Vec<MinecraftVersion> {
+        let name = "game_versions";
+
+        if !force_refresh && let Some(versions) = self.load_cached(name, false).await {
+            return versions;
+        }
+
+        let fetched = self
+            .api_service
+            .track(self.api_service.modrinth.get_minecraft_versions().await);
+
+        match fetched {
+            Ok(versions) => {
+                self.save_cache(name, &versions).await;
+                versions
+            }
+            Err(e) => {
+                log::warn!("Failed to fetch Minecraft versions: {e}");
+                self.cached_minecraft_versions().await
+            }
+        }
+    }
+
+    // Cached versions regardless of age, without touching the network
+    pub async fn cached_minecraft_versions(&self) -> Vec<MinecraftVersion> {
+        self.load_cached("game_versions", true)
+            .await
+            .unwrap_or_else(|| {
+                vec![MinecraftVersion {
+                    id: "1.20.1".to_string(),
+                    name: "1.20.1".to_string(),
+                    version_type: VersionType::Release,
+                }]
+            })
+    }
+
+    pub async fn loaders_for_type(
+        &self,
+        project_type: ProjectType,
+        force_refresh: bool,
+    ) -> Vec<ModLoader> {
+        let name = format!("loaders_{}", project_type.id());
+
+        if !force_refresh && let Some(loaders) = self.load_cached(&name, false).await {
+            return loaders;
+        }
+
+        let fetched = self.api_service.track(
+            self.api_service
+                .modrinth
+                .get_mod_loaders_for_type(project_type)
+                .await,
+        );
+
+        match fetched {
+            Ok(loaders) => {
+                self.save_cache(&name, &loaders).await;
+                loaders
+            }
+            Err(e) => {
+                log::warn!("Failed to fetch loaders for {}: {e}", project_type.id());
+                self.cached_loaders_for_type(project_type).await
+            }
+        }
+    }
+
+    pub async fn cached_loaders_for_type(&self, project_type: ProjectType) -> Vec<ModLoader> {
+        self.load_cached(&format!("loaders_{}", project_type.id()), true)
+            .await
+            .unwrap_or_default()
+    }
+
+    pub async fn loader_versions(&self, version: &str, loader: &str) -> Vec<String> {
+        match fetch_loader_versions(&self.api_service.client(), version, loader).await {
+            Ok(versions) => versions,
+            Err(e) => {
+                log::warn!("Failed to fetch {loader} loader versions for {version}: {e}");
+                Vec::new()
+            }
+        }
+    }
+
+    async fn load_cached<T: DeserializeOwned>(&self, name: &str, allow_stale: bool) -> Option<T> {
+        let path = self.cache_dir.join(format!("{name}.json"));
+        let content = tokio::fs::read_to_string(&path).await.ok()?;
+
+        let cached: CachedGameData<T> = match serde_json::from_str(&content) {
+            Ok(cached) => cached,
+            Err(e) => {
+                log::warn!("Ignoring corrupt game data cache {}: {e}", path.display());
+                return None;
+            }
+        };
+
+        let is_fresh = Utc::now() - cached.cached_at < chrono::Duration::hours(GAME_DATA_TTL_HOURS);
+        (allow_stale || is_fresh).then_some(cached.data)
+    }
+
+    async fn save_cache<T: Serialize>(&self, name: &str, data: &T) {
+        let cached = CachedGameData {
+            cached_at: Utc::now(),
+            data,
+        };
+
+        let _ = tokio::fs::create_dir_all(&self.cache_dir).await;
+        match serde_json::to_string(&cached) {
+            Ok(json) => {
+                if let Err(e) =
+                    tokio::fs::write(self.cache_dir.join(format!("{name}.json")), json).await
+                {
+                    log::warn!("Failed to write game data cache {name}: {e}");
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize game data cache {name}: {e}"),
+        }
+    }
+}
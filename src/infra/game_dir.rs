@@ -0,0 +1,211 @@
+use std::path::{Path, PathBuf};
+
+// The default launcher's game directory, whether or not it exists
+fn minecraft_game_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "windows") {
+        Some(dirs::data_dir()?.join(".minecraft"))
+    } else if cfg!(target_os = "macos") {
+        Some(dirs::data_dir()?.join("minecraft"))
+    } else {
+        Some(dirs::home_dir()?.join(".minecraft"))
+    }
+}
+
+// The mods folder of the default launcher's game directory, if it exists
+pub fn minecraft_mods_dir() -> Option<PathBuf> {
+    let mods_dir = minecraft_game_dir()?.join("mods");
+    mods_dir.is_dir().then_some(mods_dir)
+}
+
+// Expands a leading ~, ${VAR}, $VAR and %VAR% so shared lists point at the same place for
+// everyone. MC_DIR falls back to the default game directory when it isn't set. The error names
+// the variable that couldn't be expanded.
+pub fn resolve_path(raw: &str) -> Result<PathBuf, String> {
+    let raw = raw.trim();
+    let mut expanded = String::new();
+    let mut rest = raw;
+
+    if let Some(after) = raw.strip_prefix('~')
+        && (after.is_empty() || after.starts_with(['/', '\\']))
+    {
+        let home = dirs::home_dir().ok_or("Home directory for ~ not found")?;
+        expanded.push_str(&home.to_string_lossy());
+        rest = after;
+    }
+
+    while let Some(start) = rest.find(['$', '%']) {
+        expanded.push_str(&rest[..start]);
+        let marker = &rest[start..];
+
+        let (name, consumed) = if let Some(inner) = marker.strip_prefix("${") {
+            let end = inner.find('}').ok_or("Missing } after ${")?;
+            (&inner[..end], end + 3)
+        } else if let Some(inner) = marker.strip_prefix('%') {
+            match inner.find('%') {
+                Some(end) if end > 0 => (&inner[..end], end + 2),
+                _ => ("", 1),
+            }
+        } else {
+            let inner = &marker[1..];
+            let end = inner
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(inner.len());
+            (&inner[..end], end + 1)
+        };
+
+        if name.is_empty() {
+            // A lone $ or % is part of the path
+            expanded.push_str(&marker[..1]);
+            rest = &marker[1..];
+            continue;
+        }
+
+        let value =
+            env_var(name).ok_or_else(|| format!("Unknown variable {}", &marker[..consumed]))?;
+        expanded.push_str(&value);
+        rest = &marker[consumed..];
+    }
+    expanded.push_str(rest);
+
+    if expanded.trim().is_empty() {
+        return Err("Path is empty".to_string());
+    }
+    Ok(PathBuf::from(expanded))
+}
+
+fn env_var(name: &str) -> Option<String> {
+    match std::env::var(name) {
+        Ok(value) if !value.is_empty() => Some(value),
+        _ if name == "MC_DIR" => Some(minecraft_game_dir()?.to_string_lossy().to_string()),
+        _ => None,
+    }
+}
+
+// True when one path is the other or inside it, after resolving symlinks
+pub fn paths_overlap(a: &Path, b: &Path) -> bool {
+    let (Some(a), Some(b)) = (resolve(a), resolve(b)) else {
+        return false;
+    };
+    a.starts_with(&b) || b.starts_with(&a)
+}
+
+// Canonicalizes the closest existing ancestor, so directories that aren't created yet still compare
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let path = std::path::absolute(path).ok()?;
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let rest = path.strip_prefix(existing).ok()?;
+    Some(existing.canonicalize().ok()?.join(rest))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    // A fresh directory per test, removed again on drop
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("mmd-{name}-{}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn home() -> String {
+        dirs::home_dir().unwrap().to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn expands_known_variables() {
+        let expected = PathBuf::from(format!("{}/mods", home()));
+        assert_eq!(resolve_path("$HOME/mods"), Ok(expected.clone()));
+        assert_eq!(resolve_path("${HOME}/mods"), Ok(expected.clone()));
+        assert_eq!(resolve_path("%HOME%/mods"), Ok(expected));
+    }
+
+    #[test]
+    fn expands_leading_tilde_only() {
+        assert_eq!(resolve_path("~"), Ok(PathBuf::from(home())));
+        assert_eq!(
+            resolve_path("~/mods"),
+            Ok(PathBuf::from(format!("{}/mods", home())))
+        );
+        assert_eq!(resolve_path("~mods/a~b"), Ok(PathBuf::from("~mods/a~b")));
+    }
+
+    #[test]
+    fn unknown_variable_is_an_error() {
+        let error = resolve_path("$MMD_TEST_UNSET_VAR/mods").unwrap_err();
+        assert!(error.contains("$MMD_TEST_UNSET_VAR"));
+        let error = resolve_path("%MMD_TEST_UNSET_VAR%\\mods").unwrap_err();
+        assert!(error.contains("%MMD_TEST_UNSET_VAR%"));
+        assert!(resolve_path("${MMD_TEST_UNSET_VAR").is_err());
+    }
+
+    #[test]
+    fn lone_markers_stay_in_the_path() {
+        assert_eq!(
+            resolve_path("/srv/$/100%"),
+            Ok(PathBuf::from("/srv/$/100%"))
+        );
+        assert!(resolve_path("  ").is_err());
+    }
+
+    #[test]
+    fn symlink_to_same_dir_overlaps() {
+        let tmp = TempDir::new("overlap-same");
+        let mods = tmp.0.join("mods");
+        std::fs::create_dir(&mods).unwrap();
+        let link = tmp.0.join("link");
+        symlink(&mods, &link).unwrap();
+
+        assert!(paths_overlap(&link, &mods));
+        assert!(paths_overlap(&mods, &link));
+    }
+
+    #[test]
+    fn symlink_nested_in_other_overlaps() {
+        let tmp = TempDir::new("overlap-nested");
+        let mods = tmp.0.join("mods");
+        std::fs::create_dir_all(mods.join("sub")).unwrap();
+        let link = tmp.0.join("link");
+        symlink(mods.join("sub"), &link).unwrap();
+
+        assert!(paths_overlap(&link, &mods));
+        // Not created yet, still inside the linked folder
+        assert!(paths_overlap(&link.join("later"), &mods));
+    }
+
+    #[test]
+    fn symlink_containing_other_overlaps() {
+        let tmp = TempDir::new("overlap-parent");
+        let game = tmp.0.join("game");
+        std::fs::create_dir_all(game.join("mods")).unwrap();
+        let link = tmp.0.join("link");
+        symlink(&game, &link).unwrap();
+
+        assert!(paths_overlap(&link, &game.join("mods")));
+    }
+
+    #[test]
+    fn separate_dirs_do_not_overlap() {
+        let tmp = TempDir::new("overlap-separate");
+        let mods = tmp.0.join("mods");
+        let other = tmp.0.join("mods-backup");
+        std::fs::create_dir(&mods).unwrap();
+        std::fs::create_dir(&other).unwrap();
+        let link = tmp.0.join("link");
+        symlink(&other, &link).unwrap();
+
+        assert!(!paths_overlap(&link, &mods));
+    }
+}
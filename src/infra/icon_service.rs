@@ -1,3 +1,4 @@
+use crate::infra::IconResult;
 use egui::TextureHandle;
 use std::collections::{HashMap, HashSet};
 use tokio::sync::mpsc;
@@ -5,39 +6,32 @@ use tokio::sync::mpsc;
 pub struct IconService {
     handles: HashMap<String, TextureHandle>,
     loading: HashSet<String>,
-    rx: mpsc::Receiver<(String, Vec<u8>)>,
+    // Shared by every icon that failed to load, so those aren't requested again
+    placeholder: Option<TextureHandle>,
+    rx: mpsc::Receiver<IconResult>,
     url_tx: mpsc::Sender<String>,
 }
 
 impl IconService {
-    pub fn new(rx: mpsc::Receiver<(String, Vec<u8>)>, url_tx: mpsc::Sender<String>) -> Self {
+    pub fn new(rx: mpsc::Receiver<IconResult>, url_tx: mpsc::Sender<String>) -> Self {
         Self {
             handles: HashMap::new(),
             loading: HashSet::new(),
+            placeholder: None,
             rx,
             url_tx,
         }
     }
 
+    // Images arrive already decoded, only the texture upload happens on the UI thread
     pub fn update(&mut self, ctx: &egui::Context) {
-        while let Ok((url, bytes)) = self.rx.try_recv() {
-            match image::load_from_memory(&bytes) {
-                Ok(img) => {
-                    let size = [img.width() as _, img.height() as _];
-                    let rgba = img.to_rgba8();
-                    let texture_handle = ctx.load_texture(
-                        &url,
-                        egui::ColorImage::from_rgba_unmultiplied(size, &rgba),
-                        Default::default(),
-                    );
-                    self.handles.insert(url.clone(), texture_handle);
-                    self.loading.remove(&url);
-                }
-                Err(e) => {
-                    log::error!("Failed to decode image for {url}: {e}");
-                    self.loading.remove(&url);
-                }
-            }
+        while let Ok((url, image)) = self.rx.try_recv() {
+            self.loading.remove(&url);
+            let texture_handle = match image {
+                Some(image) => ctx.load_texture(&url, image, Default::default()),
+                None => self.placeholder(ctx),
+            };
+            self.handles.insert(url, texture_handle);
         }
     }
 
@@ -50,10 +44,23 @@ impl IconService {
             return None;
         }
 
-        self.loading.insert(url.to_string());
-
-        let _ = self.url_tx.try_send(url.to_string());
+        // A full queue is retried on a later frame
+        if self.url_tx.try_send(url.to_string()).is_ok() {
+            self.loading.insert(url.to_string());
+        }
 
         None
     }
+
+    fn placeholder(&mut self, ctx: &egui::Context) -> TextureHandle {
+        self.placeholder
+            .get_or_insert_with(|| {
+                ctx.load_texture(
+                    "icon_placeholder",
+                    egui::ColorImage::new([1, 1], vec![egui::Color32::from_gray(60)]),
+                    Default::default(),
+                )
+            })
+            .clone()
+    }
 }
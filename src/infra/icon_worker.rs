@@ -1,4 +1,4 @@
-use crate::infra::ApiService;
+use crate::infra::{ApiService, ConnectionLimiter};
 use std::collections::HashSet;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::path::Path;
@@ -7,11 +7,20 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tokio::sync::mpsc;
 
+// Icon fetches get their own slots so a large list doesn't starve metadata requests
+const ICON_FETCH_CONCURRENCY: usize = 6;
+// Icons are shown at 32 points, this leaves room for high DPI screens
+const ICON_MAX_SIZE: u32 = 64;
+
+// None when the icon could not be fetched or decoded
+pub type IconResult = (String, Option<egui::ColorImage>);
+
 pub struct IconWorker {
     api_service: Arc<ApiService>,
+    limiter: Arc<ConnectionLimiter>,
     cache_dir: PathBuf,
     url_rx: mpsc::Receiver<String>,
-    icon_tx: mpsc::Sender<(String, Vec<u8>)>,
+    icon_tx: mpsc::Sender<IconResult>,
     in_flight: HashSet<String>,
 }
 
@@ -20,10 +29,11 @@ impl IconWorker {
         api_service: Arc<ApiService>,
         cache_dir: PathBuf,
         url_rx: mpsc::Receiver<String>,
-        icon_tx: mpsc::Sender<(String, Vec<u8>)>,
+        icon_tx: mpsc::Sender<IconResult>,
     ) -> Self {
         Self {
             api_service,
+            limiter: Arc::new(ConnectionLimiter::new(ICON_FETCH_CONCURRENCY)),
             cache_dir,
             url_rx,
             icon_tx,
@@ -42,14 +52,22 @@ impl IconWorker {
             self.in_flight.insert(url.clone());
 
             let api_service = self.api_service.clone();
+            let limiter = self.limiter.clone();
             let cache_dir = self.cache_dir.clone();
             let icon_tx = self.icon_tx.clone();
 
             tokio::spawn(async move {
-                let bytes = fetch_icon_bytes(&api_service, &cache_dir, &url).await;
-                if let Some(data) = bytes {
-                    let _ = icon_tx.send((url, data)).await;
+                let image = match fetch_icon_bytes(&api_service, &limiter, &cache_dir, &url).await {
+                    Some(bytes) => tokio::task::spawn_blocking(move || decode_icon(&bytes))
+                        .await
+                        .ok()
+                        .flatten(),
+                    None => None,
+                };
+                if image.is_none() {
+                    log::debug!("No usable icon at {url}");
                 }
+                let _ = icon_tx.send((url, image)).await;
             });
         }
     }
@@ -57,6 +75,7 @@ impl IconWorker {
 
 async fn fetch_icon_bytes(
     api_service: &ApiService,
+    limiter: &ConnectionLimiter,
     cache_dir: &Path,
     url: &str,
 ) -> Option<Vec<u8>> {
@@ -76,9 +95,16 @@ async fn fetch_icon_bytes(
         }
     }
 
-    let _permit = api_service.limiter.acquire(1).await;
+    let _permit = limiter.acquire(1).await;
 
-    let resp = reqwest::get(url).await.ok()?;
+    let resp = api_service
+        .client()
+        .get(url)
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?;
     let data = resp.bytes().await.ok()?;
 
     if let Some(parent) = icon_path.parent() {
@@ -89,6 +115,27 @@ async fn fetch_icon_bytes(
     Some(data.to_vec())
 }
 
+fn decode_icon(bytes: &[u8]) -> Option<egui::ColorImage> {
+    let image = match image::load_from_memory(bytes) {
+        Ok(image) => image,
+        Err(e) => {
+            log::warn!("Failed to decode icon: {e}");
+            return None;
+        }
+    };
+    let image = if image.width() > ICON_MAX_SIZE || image.height() > ICON_MAX_SIZE {
+        image.thumbnail(ICON_MAX_SIZE, ICON_MAX_SIZE)
+    } else {
+        image
+    };
+
+    let size = [image.width() as _, image.height() as _];
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        &image.to_rgba8(),
+    ))
+}
+
 fn cache_path_for_url(cache_dir: &Path, url: &str) -> PathBuf {
     let mut hasher = DefaultHasher::new();
     url.hash(&mut hasher);
@@ -0,0 +1,123 @@
+use crate::domain::{DISABLED_SUFFIX, FileHashes, ModList, to_hex};
+use crate::infra::DownloadMetadata;
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use std::io::Read;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyStatus {
+    Ok,
+    HashMismatch,
+    Missing,
+    // Downloaded before hashes were recorded, nothing to compare against
+    Unverified,
+}
+
+#[derive(Clone, Debug)]
+pub struct VerifyResult {
+    pub mod_id: String,
+    pub mod_name: String,
+    pub file: String,
+    pub status: VerifyStatus,
+}
+
+// Checks every downloaded, non-archived entry of the list against the hash recorded when it was
+// downloaded. Without `full` only the files' existence is checked, which is fast enough to run
+// routinely. Reads whole files otherwise, so callers should run it on a blocking thread.
+pub fn verify_list(
+    list: &ModList,
+    download_dir: &Path,
+    metadata: &DownloadMetadata,
+    full: bool,
+    mut on_progress: impl FnMut(usize, usize),
+) -> Vec<VerifyResult> {
+    let recorded: Vec<_> = list
+        .mods
+        .iter()
+        .filter(|entry| !entry.archived)
+        .filter_map(|entry| Some((entry, metadata.get_entry(&entry.mod_id)?)))
+        .collect();
+    let total = recorded.len();
+
+    recorded
+        .into_iter()
+        .enumerate()
+        .map(|(index, (entry, recorded))| {
+            let path = download_dir.join(&recorded.file);
+            on_progress(index, total);
+
+            let status = if !path.exists() {
+                VerifyStatus::Missing
+            } else if !full {
+                VerifyStatus::Ok
+            } else {
+                match matches_hashes(&path, &recorded.hashes) {
+                    Ok(Some(true)) => VerifyStatus::Ok,
+                    Ok(Some(false)) => VerifyStatus::HashMismatch,
+                    Ok(None) => VerifyStatus::Unverified,
+                    Err(e) => {
+                        log::warn!("Failed to read {} for verification: {e}", recorded.file);
+                        VerifyStatus::HashMismatch
+                    }
+                }
+            };
+
+            VerifyResult {
+                mod_id: entry.mod_id.clone(),
+                mod_name: entry.mod_name.clone(),
+                file: recorded.file.clone(),
+                status,
+            }
+        })
+        .collect()
+}
+
+// Looks for a file in the directory that is byte-identical to the given hashes, under any name.
+// Only files of the expected size are hashed when the size is known.
+pub fn find_identical_file(dir: &Path, hashes: &FileHashes, size: u64) -> Option<String> {
+    if hashes.sha512.is_empty() && hashes.sha1.is_empty() {
+        return None;
+    }
+
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter(|entry| size == 0 || entry.metadata().is_ok_and(|m| m.len() == size))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| {
+            !name.starts_with(".mcd")
+                && !name.ends_with(".part")
+                && !name.ends_with(".archived")
+                && !name.ends_with(DISABLED_SUFFIX)
+        })
+        .find(|name| matches!(matches_hashes(&dir.join(name), hashes), Ok(Some(true))))
+}
+
+// None when no hash was recorded for the file
+pub fn matches_hashes(path: &Path, hashes: &FileHashes) -> std::io::Result<Option<bool>> {
+    let (expected, actual) = if !hashes.sha512.is_empty() {
+        (&hashes.sha512, hash_file::<Sha512>(path)?)
+    } else if !hashes.sha1.is_empty() {
+        (&hashes.sha1, hash_file::<Sha1>(path)?)
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(expected.eq_ignore_ascii_case(&actual)))
+}
+
+fn hash_file<D: Digest>(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(to_hex(&hasher.finalize()))
+}
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::io::Read;
+use std::path::Path;
+
+// Returns the declared requirement when the jar's mod metadata rules out the given Minecraft version.
+// Jars without Fabric/Quilt/Forge metadata or without a minecraft dependency are treated as unknown.
+pub fn find_version_mismatch(jar_path: &Path, game_version: &str) -> Result<Option<String>> {
+    let Some(requirements) = read_minecraft_requirements(jar_path)? else {
+        return Ok(None);
+    };
+
+    if requirements
+        .iter()
+        .any(|req| matches_version_requirement(req, game_version))
+    {
+        Ok(None)
+    } else {
+        Ok(Some(requirements.join(" || ")))
+    }
+}
+
+fn read_minecraft_requirements(jar_path: &Path) -> Result<Option<Vec<String>>> {
+    let file = std::fs::File::open(jar_path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    if let Some(json) = read_entry(&mut archive, "fabric.mod.json")? {
+        return Ok(json
+            .get("depends")
+            .and_then(|d| d.get("minecraft"))
+            .and_then(requirement_strings));
+    }
+
+    if let Some(json) = read_entry(&mut archive, "quilt.mod.json")? {
+        let depends = json
+            .get("quilt_loader")
+            .and_then(|l| l.get("depends"))
+            .and_then(Value::as_array);
+        let minecraft = depends.and_then(|deps| {
+            deps.iter()
+                .find(|d| d.get("id").and_then(Value::as_str) == Some("minecraft"))
+        });
+        return Ok(minecraft
+            .and_then(|d| d.get("versions"))
+            .and_then(requirement_strings));
+    }
+
+    for name in ["META-INF/neoforge.mods.toml", "META-INF/mods.toml"] {
+        if let Some(contents) = read_entry_text(&mut archive, name)? {
+            return Ok(forge_minecraft_requirements(&contents));
+        }
+    }
+
+    Ok(None)
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Result<Option<Value>> {
+    // Some mods ship metadata with stray control characters, treat those as unknown
+    Ok(read_entry_text(archive, name)?.and_then(|contents| serde_json::from_str(&contents).ok()))
+}
+
+fn read_entry_text(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    name: &str,
+) -> Result<Option<String>> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents)?;
+    Ok(Some(contents))
+}
+
+fn forge_minecraft_requirements(contents: &str) -> Option<Vec<String>> {
+    let doc: toml::Table = toml::from_str(contents).ok()?;
+
+    // [[dependencies.<modid>]] tables, any of the jar's mods may declare the minecraft range
+    let range = doc
+        .get("dependencies")?
+        .as_table()?
+        .values()
+        .filter_map(toml::Value::as_array)
+        .flatten()
+        .find(|dep| dep.get("modId").and_then(toml::Value::as_str) == Some("minecraft"))?
+        .get("versionRange")?
+        .as_str()?;
+
+    maven_range_to_requirements(range)
+}
+
+// Converts Maven version ranges like "[1.20.1,1.21)" or "[1.19,1.20),[1.20.1,)" into
+// requirement strings, one per alternative range
+fn maven_range_to_requirements(range: &str) -> Option<Vec<String>> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return None;
+    }
+
+    if !range.starts_with(['[', '(']) {
+        // A bare version is a soft minimum in Maven terms
+        return Some(vec![format!(">={range}")]);
+    }
+
+    let mut requirements = Vec::new();
+    let mut rest = range;
+    while let Some(start) = rest.find(['[', '(']) {
+        let end = start + rest[start..].find([']', ')'])?;
+        let spec = &rest[start..=end];
+        requirements.push(maven_spec_to_requirement(spec)?);
+        rest = &rest[end + 1..];
+    }
+
+    (!requirements.is_empty()).then_some(requirements)
+}
+
+fn maven_spec_to_requirement(spec: &str) -> Option<String> {
+    let inclusive_low = spec.starts_with('[');
+    let inclusive_high = spec.ends_with(']');
+    let inner = &spec[1..spec.len() - 1];
+
+    let Some((low, high)) = inner.split_once(',') else {
+        // "[1.20.1]" pins an exact version
+        return Some(inner.trim().to_string());
+    };
+
+    let mut tokens = Vec::new();
+    let (low, high) = (low.trim(), high.trim());
+    if !low.is_empty() {
+        tokens.push(format!("{}{low}", if inclusive_low { ">=" } else { ">" }));
+    }
+    if !high.is_empty() {
+        tokens.push(format!("{}{high}", if inclusive_high { "<=" } else { "<" }));
+    }
+
+    Some(if tokens.is_empty() {
+        "*".to_string()
+    } else {
+        tokens.join(" ")
+    })
+}
+
+fn requirement_strings(value: &Value) -> Option<Vec<String>> {
+    let requirements: Vec<String> = match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        Value::Object(obj) => obj.get("any").and_then(requirement_strings)?,
+        _ => return None,
+    };
+
+    (!requirements.is_empty()).then_some(requirements)
+}
+
+// All space-separated tokens of a requirement must hold
+pub fn matches_version_requirement(requirement: &str, version: &str) -> bool {
+    requirement
+        .split_whitespace()
+        .all(|token| eval_requirement_token(token, version))
+}
+
+pub fn eval_requirement_token(token: &str, version: &str) -> bool {
+    let Some(version_parts) = parse_version(version) else {
+        // Snapshots and other odd names can't be compared, don't flag them
+        return true;
+    };
+
+    let (op, target) = split_operator(token);
+    if target.is_empty() || target == "*" || target == "x" {
+        return true;
+    }
+
+    if target.ends_with(".x") || target.ends_with(".*") {
+        let Some(prefix) = parse_version(&target[..target.len() - 2]) else {
+            return true;
+        };
+        return version_parts.starts_with(&prefix);
+    }
+
+    let Some(target_parts) = parse_version(target) else {
+        return true;
+    };
+    let ordering = compare_versions(&version_parts, &target_parts);
+
+    match op {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "~" => {
+            let minor_prefix = &target_parts[..target_parts.len().min(2)];
+            ordering != Ordering::Less && version_parts.starts_with(minor_prefix)
+        }
+        "^" => ordering != Ordering::Less && version_parts.first() == target_parts.first(),
+        _ => ordering == Ordering::Equal,
+    }
+}
+
+fn split_operator(token: &str) -> (&str, &str) {
+    for op in [">=", "<=", ">", "<", "=", "~", "^"] {
+        if let Some(rest) = token.strip_prefix(op) {
+            return (op, rest);
+        }
+    }
+    ("", token)
+}
+
+fn parse_version(version: &str) -> Option<Vec<u32>> {
+    // Drop pre-release and build suffixes like "1.20-rc.1" or "1.20.1+build.3"
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+fn compare_versions(a: &[u32], b: &[u32]) -> Ordering {
+    let len = a.len().max(b.len());
+    (0..len)
+        .map(|i| a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
@@ -1,8 +1,19 @@
-use crate::domain::{Event, ModService};
-use std::path::PathBuf;
+use crate::domain::{
+    Event, LegacyMatch, ModInfo, ModService, OverwritePolicy, ProjectType, ProviderKind,
+    ReleaseChannel, SearchSort, to_hex,
+};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 
+// Project lookups per request when reading a collection
+const COLLECTION_CHUNK_SIZE: usize = 100;
+// File hashes per bulk lookup when importing a folder
+const HASH_LOOKUP_CHUNK_SIZE: usize = 100;
+// Search hits kept per entry so a wrong match can be corrected
+const LEGACY_MATCH_CANDIDATES: usize = 5;
+
 pub struct LegacyListService {
     mod_service: Arc<ModService>,
 }
@@ -12,34 +23,140 @@ impl LegacyListService {
         Self { mod_service }
     }
 
-    pub async fn import_legacy_list(
-        &self,
-        path: PathBuf,
-        version: String,
-        loader: String,
-        tx: mpsc::Sender<Event>,
-    ) {
-        let content = match tokio::fs::read_to_string(&path).await {
-            Ok(c) => c,
-            Err(e) => {
-                let _ = tx
-                    .send(Event::LegacyListFailed {
-                        error: format!("Failed to read file: {e}"),
-                        is_import: true,
-                    })
-                    .await;
-                return;
-            }
-        };
+    pub async fn preview_import(&self, path: &Path) -> Result<Vec<String>> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .context("Failed to read file")?;
 
-        let slugs: Vec<String> = content
+        Ok(content
             .lines()
             .map(|l| l.trim())
             .filter(|l| !l.is_empty() && !l.starts_with('#'))
             .map(|l| l.to_string())
-            .collect();
+            .collect())
+    }
+
+    // Lists a Modrinth collection's projects by slug, reporting progress for large collections
+    pub async fn preview_collection(
+        &self,
+        collection_id: &str,
+        tx: &mpsc::Sender<Event>,
+    ) -> Result<(String, Vec<String>)> {
+        let modrinth = &self.mod_service.api_service.modrinth;
+        let (name, project_ids) = modrinth
+            .fetch_collection(collection_id)
+            .await
+            .context("Failed to load collection")?;
+
+        let mut slugs = Vec::with_capacity(project_ids.len());
+        for chunk in project_ids.chunks(COLLECTION_CHUNK_SIZE) {
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: slugs.len(),
+                    total: project_ids.len(),
+                    message: format!("Reading collection '{name}'..."),
+                })
+                .await;
+            slugs.extend(
+                modrinth
+                    .fetch_project_slugs(chunk)
+                    .await
+                    .context("Failed to look up collection projects")?,
+            );
+        }
+
+        Ok((name, slugs))
+    }
+
+    // Matches every jar in a folder by hash, returns the matched projects and unmatched file names
+    pub async fn import_directory(
+        &self,
+        path: &Path,
+        tx: &mpsc::Sender<Event>,
+    ) -> Result<(Vec<Arc<ModInfo>>, Vec<String>)> {
+        use sha1::{Digest, Sha1};
+
+        let mut jars = Vec::new();
+        let mut entries = tokio::fs::read_dir(path)
+            .await
+            .context("Failed to read folder")?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_path = entry.path();
+            if file_path.is_file()
+                && file_path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("jar"))
+            {
+                jars.push(file_path);
+            }
+        }
+        jars.sort();
+
+        let mut hashed = Vec::with_capacity(jars.len());
+        let mut unresolved = Vec::new();
+        for (idx, jar) in jars.iter().enumerate() {
+            let file_name = jar
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: idx + 1,
+                    total: jars.len(),
+                    message: format!("Hashing '{file_name}'..."),
+                })
+                .await;
+
+            match tokio::fs::read(jar).await {
+                Ok(bytes) => hashed.push((to_hex(&Sha1::digest(&bytes)), file_name)),
+                Err(e) => {
+                    log::warn!("Failed to read {}: {e}", jar.display());
+                    unresolved.push(file_name);
+                }
+            }
+        }
 
+        let modrinth = &self.mod_service.api_service.modrinth;
+        let mut resolved: Vec<Arc<ModInfo>> = Vec::new();
+        for (chunk_idx, chunk) in hashed.chunks(HASH_LOOKUP_CHUNK_SIZE).enumerate() {
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: chunk_idx * HASH_LOOKUP_CHUNK_SIZE,
+                    total: hashed.len(),
+                    message: "Looking up files on Modrinth...".into(),
+                })
+                .await;
+
+            let sha1s: Vec<String> = chunk.iter().map(|(sha1, _)| sha1.clone()).collect();
+            let mut matches = modrinth
+                .find_by_hashes(&sha1s)
+                .await
+                .context("Failed to look up files on Modrinth")?;
+
+            for (sha1, file_name) in chunk {
+                match matches.remove(sha1) {
+                    // Several jars of the same project only add it once
+                    Some(info) if resolved.iter().any(|m| m.id == info.id) => {}
+                    Some(info) => resolved.push(Arc::new(info)),
+                    None => unresolved.push(file_name.clone()),
+                }
+            }
+        }
+
+        Ok((resolved, unresolved))
+    }
+
+    pub async fn import_legacy_list(
+        &self,
+        suggested_name: String,
+        slugs: Vec<String>,
+        version: String,
+        loader: String,
+        tx: mpsc::Sender<Event>,
+    ) {
         let mut successful_mods = Vec::new();
+        let mut matches = Vec::new();
         let mut failed = Vec::new();
         let warnings = Vec::new();
 
@@ -54,22 +171,52 @@ impl LegacyListService {
 
             match self
                 .mod_service
-                .get_mod_by_slug(slug, &version, &loader)
+                .get_mod_by_slug(
+                    ProviderKind::Modrinth,
+                    slug,
+                    &version,
+                    &loader,
+                    ReleaseChannel::Alpha,
+                )
                 .await
             {
                 Ok(info) => {
+                    matches.push(LegacyMatch {
+                        input: slug.clone(),
+                        candidates: vec![info.clone()],
+                        by_name: false,
+                        query: slug.clone(),
+                    });
                     successful_mods.push(info);
                 }
                 Err(e) => {
-                    log::warn!("Failed to resolve slug '{slug}': {e}");
-                    failed.push(slug.clone());
+                    // Older lists hold names rather than slugs, the top search hit may be wrong
+                    log::debug!("No project with slug '{slug}', searching by name: {e}");
+                    match self.search_candidates(slug, &version, &loader).await {
+                        Ok(candidates) if !candidates.is_empty() => {
+                            successful_mods.push(candidates[0].clone());
+                            matches.push(LegacyMatch {
+                                input: slug.clone(),
+                                candidates,
+                                by_name: true,
+                                query: slug.clone(),
+                            });
+                        }
+                        result => {
+                            if let Err(e) = result {
+                                log::warn!("Failed to search for '{slug}': {e}");
+                            }
+                            failed.push(slug.clone());
+                        }
+                    }
                 }
             }
         }
 
+        let _ = tx.send(Event::LegacyMatchesResolved { matches }).await;
         let _ = tx
             .send(Event::LegacyListComplete {
-                suggested_name: path.file_stem().unwrap().to_str().unwrap().to_string(),
+                suggested_name,
                 successful: successful_mods,
                 failed,
                 warnings,
@@ -78,12 +225,46 @@ impl LegacyListService {
             .await;
     }
 
+    // Modrinth projects matching a legacy entry by name, best match first
+    pub async fn search_candidates(
+        &self,
+        entry: &str,
+        version: &str,
+        loader: &str,
+    ) -> Result<Vec<Arc<ModInfo>>> {
+        let query = entry.replace(['-', '_'], " ");
+        let api_service = &self.mod_service.api_service;
+        let modrinth = api_service.provider(ProviderKind::Modrinth);
+
+        let _permit = api_service.limiter.acquire(1).await;
+        let mut results = api_service.track(
+            crate::infra::retry_rate_limited(|| {
+                modrinth.search_mods(
+                    &query,
+                    version,
+                    loader,
+                    &ProjectType::Mod,
+                    &[],
+                    SearchSort::Relevance,
+                )
+            })
+            .await,
+        )?;
+        results.truncate(LEGACY_MATCH_CANDIDATES);
+
+        Ok(self
+            .mod_service
+            .cache_search_results(results, version.to_string(), loader.to_string())
+            .await)
+    }
+
     pub async fn export_legacy_list(
         &self,
         path: PathBuf,
-        mod_ids: Vec<String>,
+        mod_ids: Vec<(String, ProviderKind)>,
         version: String,
         loader: String,
+        overwrite: OverwritePolicy,
         tx: mpsc::Sender<Event>,
     ) {
         let mut successful_mods = Vec::new();
@@ -91,7 +272,7 @@ impl LegacyListService {
         let mut warnings = Vec::new();
         let mut slugs = Vec::new();
 
-        for (idx, mod_id) in mod_ids.iter().enumerate() {
+        for (idx, (mod_id, provider)) in mod_ids.iter().enumerate() {
             let _ = tx
                 .send(Event::LegacyListProgress {
                     current: idx + 1,
@@ -100,9 +281,18 @@ impl LegacyListService {
                 })
                 .await;
 
+            if *provider != ProviderKind::Modrinth {
+                warnings.push(format!(
+                    "Mod '{mod_id}' is from {}, legacy lists only support Modrinth, skipping",
+                    provider.display_name()
+                ));
+                failed.push(mod_id.clone());
+                continue;
+            }
+
             match self
                 .mod_service
-                .get_mod_by_id(mod_id, &version, &loader)
+                .get_mod_by_id(*provider, mod_id, &version, &loader, ReleaseChannel::Alpha)
                 .await
             {
                 Ok(mod_info) => {
@@ -122,6 +312,16 @@ impl LegacyListService {
             }
         }
 
+        if let Err(e) = crate::infra::backup_existing_file(&path, overwrite).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("{e:#}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
         let temp_path = path.with_extension("mods.tmp");
         let content = format!(
             "# Minecraft Mod List\n# Generated on {}\n\n{}\n",
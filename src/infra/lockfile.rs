@@ -0,0 +1,351 @@
+use crate::domain::{
+    CompatPolicy, Event, FileHashes, ModList, ModService, OverwritePolicy, ProviderKind,
+    compatibility_for,
+};
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+const LOCKFILE_FORMAT_VERSION: u32 = 1;
+
+// Every file pinned to an exact version and hash, so installing it later either reproduces the
+// same files or fails
+#[derive(Serialize, Deserialize)]
+struct Lockfile {
+    format_version: u32,
+    name: String,
+    minecraft: String,
+    loader: String,
+    generated_at: chrono::DateTime<chrono::Utc>,
+    files: Vec<LockedFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LockedFile {
+    project_id: String,
+    name: String,
+    provider: ProviderKind,
+    version_id: String,
+    version: String,
+    file: String,
+    url: String,
+    hashes: FileHashes,
+    #[serde(default)]
+    size: u64,
+}
+
+pub struct LockfileService {
+    mod_service: Arc<ModService>,
+}
+
+impl LockfileService {
+    pub fn new(mod_service: Arc<ModService>) -> Self {
+        Self { mod_service }
+    }
+
+    // Unpinned entries are locked to the version that is current at export time
+    pub async fn export_lockfile(
+        &self,
+        path: PathBuf,
+        list: ModList,
+        version: String,
+        loader: String,
+        overwrite: OverwritePolicy,
+        tx: mpsc::Sender<Event>,
+    ) {
+        let entries: Vec<_> = list.mods.iter().filter(|m| !m.archived).collect();
+
+        let mut successful_mods = Vec::new();
+        let mut failed = Vec::new();
+        let mut warnings = Vec::new();
+        let mut files = Vec::new();
+
+        for (idx, entry) in entries.iter().enumerate() {
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: idx + 1,
+                    total: entries.len(),
+                    message: format!("Resolving '{}'...", entry.mod_name),
+                })
+                .await;
+
+            let resolved = match &entry.pinned_version {
+                Some(version_id) => {
+                    self.mod_service
+                        .get_pinned_mod(entry.provider, &entry.mod_id, version_id)
+                        .await
+                }
+                None => {
+                    self.mod_service
+                        .get_mod_by_id(
+                            entry.provider,
+                            &entry.mod_id,
+                            &version,
+                            &loader,
+                            list.allowed_channel,
+                        )
+                        .await
+                }
+            };
+
+            let mod_info = match resolved {
+                Ok(info) => info,
+                Err(e) => {
+                    log::warn!("Failed to resolve '{}': {e}", entry.mod_id);
+                    failed.push(entry.mod_id.clone());
+                    continue;
+                }
+            };
+
+            let compatible = compatibility_for(
+                list.content_type,
+                &mod_info,
+                &version,
+                &loader,
+                CompatPolicy::Exact,
+                None,
+            );
+            if !compatible && !entry.compatibility_override {
+                warnings.push(format!(
+                    "'{}' is not compatible with {version} {loader}, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            if mod_info.download_url.is_empty() || mod_info.version_id.is_empty() {
+                warnings.push(format!(
+                    "'{}' has no direct download, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            if mod_info.hashes.sha1.is_empty() && mod_info.hashes.sha512.is_empty() {
+                warnings.push(format!(
+                    "'{}' has no published hash and can't be locked, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            files.push(LockedFile {
+                project_id: mod_info.id.clone(),
+                name: mod_info.name.clone(),
+                provider: mod_info.provider,
+                version_id: mod_info.version_id.clone(),
+                version: mod_info.version.clone(),
+                file: entry.filename_for(&mod_info),
+                url: mod_info.download_url.clone(),
+                hashes: mod_info.hashes.clone(),
+                size: mod_info.file_size,
+            });
+            successful_mods.push(mod_info);
+        }
+
+        let lockfile = Lockfile {
+            format_version: LOCKFILE_FORMAT_VERSION,
+            name: list.name.clone(),
+            minecraft: version,
+            loader,
+            generated_at: chrono::Utc::now(),
+            files,
+        };
+
+        if let Err(e) = write_lockfile(&path, &lockfile, overwrite).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("{e:#}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        let _ = tx
+            .send(Event::LegacyListComplete {
+                suggested_name: path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                successful: successful_mods,
+                failed,
+                warnings,
+                is_import: false,
+            })
+            .await;
+    }
+
+    // Downloads every locked file into the directory, files that already match are kept. Each file
+    // must match its recorded hash, anything else is reported as an error.
+    pub async fn install_lockfile(&self, path: PathBuf, dir: PathBuf, tx: mpsc::Sender<Event>) {
+        let lockfile = match read_lockfile(&path).await {
+            Ok(lockfile) => lockfile,
+            Err(e) => {
+                let _ = tx
+                    .send(Event::LockfileInstalled {
+                        installed: 0,
+                        unchanged: 0,
+                        errors: vec![format!("{e:#}")],
+                    })
+                    .await;
+                return;
+            }
+        };
+
+        let api_service = &self.mod_service.api_service;
+        let total = lockfile.files.len();
+        let mut installed = 0;
+        let mut unchanged = 0;
+        let mut errors = Vec::new();
+
+        for (idx, locked) in lockfile.files.iter().enumerate() {
+            let _ = tx
+                .send(Event::LockfileProgress {
+                    current: idx + 1,
+                    total,
+                    message: format!("Installing '{}' {}...", locked.name, locked.version),
+                })
+                .await;
+
+            // Without a hash there is nothing to verify the download against
+            if locked.hashes.sha1.is_empty() && locked.hashes.sha512.is_empty() {
+                let error = format!("{} {} has no locked hash", locked.name, locked.version);
+                log::error!("Lockfile install failed: {error}");
+                errors.push(error);
+                continue;
+            }
+
+            // Lockfiles may come from elsewhere, so names can't point outside the directory
+            let file_name = if is_plain_file_name(&locked.file) {
+                locked.file.clone()
+            } else {
+                log::warn!("Ignoring unsafe file name in lockfile: {}", locked.file);
+                format!("{}-{}.jar", locked.project_id, locked.version_id)
+            };
+            if !is_plain_file_name(&file_name) {
+                let error = format!("{} {} has an unsafe file name", locked.name, locked.version);
+                log::error!("Lockfile install failed: {error}");
+                errors.push(error);
+                continue;
+            }
+            let destination = dir.join(&file_name);
+
+            let hashes = locked.hashes.clone();
+            let existing = destination.clone();
+            let already_present = tokio::task::spawn_blocking(move || {
+                existing.exists()
+                    && matches!(
+                        crate::infra::matches_hashes(&existing, &hashes),
+                        Ok(Some(true))
+                    )
+            })
+            .await
+            .unwrap_or(false);
+            if already_present {
+                unchanged += 1;
+                continue;
+            }
+
+            let _permit = api_service.download_limiter.acquire(1).await;
+            let result = api_service.track(
+                api_service
+                    .provider(locked.provider)
+                    .download_mod(&locked.url, &locked.hashes, &destination, Box::new(|_| {}))
+                    .await,
+            );
+
+            match result {
+                Ok(()) => installed += 1,
+                Err(e) => {
+                    let gone = e
+                        .downcast_ref::<reqwest::Error>()
+                        .and_then(|e| e.status())
+                        .is_some_and(|s| {
+                            s == reqwest::StatusCode::NOT_FOUND || s == reqwest::StatusCode::GONE
+                        });
+                    let error = if gone {
+                        format!(
+                            "{} {} ({}) is no longer available upstream",
+                            locked.name, locked.version, locked.version_id
+                        )
+                    } else if e.is::<crate::domain::HashMismatch>() {
+                        format!(
+                            "{} {} doesn't match the locked hash, the upstream file changed",
+                            locked.name, locked.version
+                        )
+                    } else {
+                        format!("{} {}: {e:#}", locked.name, locked.version)
+                    };
+                    log::error!("Lockfile install failed: {error}");
+                    errors.push(error);
+                }
+            }
+        }
+
+        let _ = tx
+            .send(Event::LockfileInstalled {
+                installed,
+                unchanged,
+                errors,
+            })
+            .await;
+    }
+}
+
+fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && Path::new(name).file_name().and_then(|n| n.to_str()) == Some(name)
+        && name != ".."
+}
+
+async fn read_lockfile(path: &Path) -> anyhow::Result<Lockfile> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let lockfile: Lockfile = serde_json::from_str(&content).context("Not a valid lockfile")?;
+    anyhow::ensure!(
+        lockfile.format_version <= LOCKFILE_FORMAT_VERSION,
+        "Lockfile format {} is newer than this version supports",
+        lockfile.format_version
+    );
+    Ok(lockfile)
+}
+
+async fn write_lockfile(
+    path: &Path,
+    lockfile: &Lockfile,
+    overwrite: OverwritePolicy,
+) -> anyhow::Result<()> {
+    crate::infra::backup_existing_file(path, overwrite).await?;
+
+    let json = serde_json::to_string_pretty(lockfile)?;
+    let temp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&temp_path, json)
+        .await
+        .context("Failed to write file")?;
+    tokio::fs::rename(&temp_path, path)
+        .await
+        .context("Failed to finalize file")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_file_names_stay_in_the_directory() {
+        assert!(is_plain_file_name("sodium-0.5.jar"));
+        assert!(!is_plain_file_name(""));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name("../../x-1.jar"));
+        assert!(!is_plain_file_name("/etc/x-1.jar"));
+        assert!(!is_plain_file_name("mods/x-1.jar"));
+    }
+}
@@ -1,18 +1,48 @@
 mod api_service;
 mod config_manager;
+mod dir_watcher;
+mod disk_space;
+mod download_history;
 mod download_metadata;
+mod export_backup;
+mod game_data;
+mod game_dir;
+mod http_client;
 mod icon_service;
 mod icon_worker;
+mod integrity;
+mod jar_metadata;
 mod legacy_list;
+mod lockfile;
+mod mrpack;
 mod project_cache;
+mod resource_pack;
+mod share_link;
+mod url_list;
 
-pub use api_service::ApiService;
+pub use api_service::{
+    ApiService, ConnectionLimiter, is_retriable_error, retry_backoff, retry_rate_limited,
+};
 pub use config_manager::ConfigManager;
+pub use dir_watcher::DirWatcher;
+pub use disk_space::{DiskSpace, check_disk_space};
+pub use download_history::DownloadHistory;
 pub use download_metadata::{
-    DownloadMetadata, read_download_metadata, remove_metadata_entry, update_metadata_entry,
-    write_download_metadata,
+    DownloadMetadata, lock_download_metadata, prune_download_metadata, read_download_metadata,
+    update_metadata_entry,
 };
+pub use export_backup::backup_existing_file;
+pub use game_data::{GameDataService, fetch_loader_versions};
+pub use game_dir::{minecraft_mods_dir, paths_overlap, resolve_path};
+pub use http_client::build_client;
 pub use icon_service::IconService;
-pub use icon_worker::IconWorker;
+pub use icon_worker::{IconResult, IconWorker};
+pub use integrity::{VerifyResult, VerifyStatus, find_identical_file, matches_hashes, verify_list};
+pub use jar_metadata::find_version_mismatch;
 pub use legacy_list::LegacyListService;
+pub use lockfile::LockfileService;
+pub use mrpack::{MrpackPart, MrpackService};
 pub use project_cache::ProjectCache;
+pub use resource_pack::validate_resource_pack;
+pub use share_link::{decode_share_link, encode_share_link};
+pub use url_list::UrlListService;
@@ -0,0 +1,353 @@
+use crate::domain::{
+    Event, ModInfo, ModList, ModService, OverwritePolicy, ProjectType, ProviderKind,
+    generate_mod_filename, to_hex,
+};
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha512};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MrpackIndex {
+    format_version: u32,
+    game: String,
+    version_id: String,
+    name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    summary: String,
+    files: Vec<MrpackFile>,
+    dependencies: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MrpackFile {
+    path: String,
+    hashes: MrpackHashes,
+    downloads: Vec<String>,
+    file_size: u64,
+}
+
+#[derive(Serialize)]
+struct MrpackHashes {
+    sha1: String,
+    sha512: String,
+}
+
+#[derive(Deserialize)]
+struct ForgePromotions {
+    promos: BTreeMap<String, String>,
+}
+
+// One list going into a pack, resolved against its own loader
+#[derive(Clone)]
+pub struct MrpackPart {
+    pub list: ModList,
+    pub loader: String,
+}
+
+pub struct MrpackService {
+    mod_service: Arc<ModService>,
+}
+
+impl MrpackService {
+    pub fn new(mod_service: Arc<ModService>) -> Self {
+        Self { mod_service }
+    }
+
+    fn client(&self) -> Client {
+        self.mod_service.api_service.client()
+    }
+
+    pub async fn export_mrpack(
+        &self,
+        path: PathBuf,
+        name: String,
+        parts: Vec<MrpackPart>,
+        version: String,
+        overwrite: OverwritePolicy,
+        tx: mpsc::Sender<Event>,
+    ) {
+        let entries: Vec<_> = parts
+            .iter()
+            .flat_map(|part| {
+                part.list
+                    .mods
+                    .iter()
+                    .filter(|m| !m.archived)
+                    .map(move |m| (m, part.loader.as_str(), part.list.allowed_channel))
+            })
+            .collect();
+
+        let mut successful_mods = Vec::new();
+        let mut failed = Vec::new();
+        let mut warnings = Vec::new();
+        let mut files = Vec::new();
+
+        for (idx, &(entry, loader, channel)) in entries.iter().enumerate() {
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: idx + 1,
+                    total: entries.len(),
+                    message: format!("Resolving '{}'...", entry.mod_name),
+                })
+                .await;
+
+            if entry.provider != ProviderKind::Modrinth {
+                warnings.push(format!(
+                    "'{}' is from {}, Modrinth packs only allow Modrinth downloads, skipping",
+                    entry.mod_name,
+                    entry.provider.display_name()
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            let resolved = match &entry.pinned_version {
+                Some(version_id) => {
+                    self.mod_service
+                        .get_pinned_mod(entry.provider, &entry.mod_id, version_id)
+                        .await
+                }
+                None => {
+                    self.mod_service
+                        .get_mod_by_id(entry.provider, &entry.mod_id, &version, loader, channel)
+                        .await
+                }
+            };
+
+            let mod_info = match resolved {
+                Ok(info) => info,
+                Err(e) => {
+                    log::warn!("Failed to resolve '{}': {e}", entry.mod_id);
+                    failed.push(entry.mod_id.clone());
+                    continue;
+                }
+            };
+
+            if mod_info.download_url.is_empty() {
+                warnings.push(format!(
+                    "'{}' has no download for {version} {loader}, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            match self
+                .build_file(&mod_info, entry.filename_override.as_deref())
+                .await
+            {
+                Ok(file) => {
+                    files.push(file);
+                    successful_mods.push(mod_info);
+                }
+                Err(e) => {
+                    log::warn!("Failed to hash '{}': {e}", entry.mod_id);
+                    failed.push(entry.mod_id.clone());
+                }
+            }
+        }
+
+        let _ = tx
+            .send(Event::LegacyListProgress {
+                current: entries.len(),
+                total: entries.len(),
+                message: "Writing modpack...".into(),
+            })
+            .await;
+
+        // Only the mod list decides the pack's loader, other types have no loader to install
+        let pack_part = parts
+            .iter()
+            .find(|part| part.list.content_type == ProjectType::Mod)
+            .or(parts.first());
+        let (loader, pinned) = pack_part.map_or(("", None), |part| {
+            (part.loader.as_str(), part.list.loader_version.as_deref())
+        });
+
+        let mut dependencies = BTreeMap::new();
+        dependencies.insert("minecraft".to_string(), version.clone());
+        match self.fetch_loader_dependency(&version, loader, pinned).await {
+            Ok(Some((key, loader_version))) => {
+                if let Some(pinned) = pinned
+                    && pinned != loader_version
+                {
+                    warnings.push(format!(
+                        "{loader} loader {pinned} doesn't support Minecraft {version}, used {loader_version} instead"
+                    ));
+                }
+                dependencies.insert(key.to_string(), loader_version);
+            }
+            Ok(None) => {}
+            Err(e) => warnings.push(format!("Could not determine {loader} loader version: {e}")),
+        }
+
+        let index = MrpackIndex {
+            format_version: 1,
+            game: "minecraft".into(),
+            version_id: chrono::Utc::now().format("%Y.%m.%d").to_string(),
+            name,
+            summary: match parts.as_slice() {
+                [part] => part.list.notes.clone(),
+                _ => String::new(),
+            },
+            files,
+            dependencies,
+        };
+
+        if let Err(e) = crate::infra::backup_existing_file(&path, overwrite).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("{e:#}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        let write_path = path.clone();
+        let result = tokio::task::spawn_blocking(move || write_mrpack(&write_path, &index)).await;
+
+        if let Err(e) = result.map_err(anyhow::Error::from).and_then(|inner| inner) {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("Failed to write modpack: {e}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        let _ = tx
+            .send(Event::LegacyListComplete {
+                suggested_name: path.file_stem().unwrap().to_str().unwrap().to_string(),
+                successful: successful_mods,
+                failed,
+                warnings,
+                is_import: false,
+            })
+            .await;
+    }
+
+    async fn build_file(
+        &self,
+        mod_info: &ModInfo,
+        filename_override: Option<&str>,
+    ) -> Result<MrpackFile> {
+        let mut sha1 = mod_info.hashes.sha1.clone();
+        let mut sha512 = mod_info.hashes.sha512.clone();
+        let mut file_size = mod_info.file_size;
+
+        if sha1.is_empty() || sha512.is_empty() || file_size == 0 {
+            let bytes = self
+                .client()
+                .get(&mod_info.download_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .bytes()
+                .await?;
+
+            sha1 = to_hex(&Sha1::digest(&bytes));
+            sha512 = to_hex(&Sha512::digest(&bytes));
+            file_size = bytes.len() as u64;
+        }
+
+        Ok(MrpackFile {
+            path: format!(
+                "{}/{}",
+                mod_info.project_type.folder_name(),
+                filename_override
+                    .map(str::to_string)
+                    .unwrap_or_else(|| generate_mod_filename(mod_info))
+            ),
+            hashes: MrpackHashes { sha1, sha512 },
+            downloads: vec![mod_info.download_url.clone()],
+            file_size,
+        })
+    }
+
+    async fn fetch_loader_dependency(
+        &self,
+        version: &str,
+        loader: &str,
+        pinned: Option<&str>,
+    ) -> Result<Option<(&'static str, String)>> {
+        let dependency = match loader {
+            "fabric" | "quilt" => {
+                let versions =
+                    crate::infra::fetch_loader_versions(&self.client(), version, loader).await?;
+                let key = if loader == "fabric" {
+                    "fabric-loader"
+                } else {
+                    "quilt-loader"
+                };
+                // A pinned build only counts if it still supports this Minecraft version
+                pinned
+                    .filter(|p| versions.iter().any(|v| v == p))
+                    .map(str::to_string)
+                    .or_else(|| versions.into_iter().next())
+                    .map(|v| (key, v))
+            }
+            "forge" => {
+                let pinned = match pinned {
+                    Some(p) => crate::infra::fetch_loader_versions(&self.client(), version, loader)
+                        .await?
+                        .into_iter()
+                        .find(|v| v == p),
+                    None => None,
+                };
+                match pinned {
+                    Some(p) => Some(("forge", p)),
+                    None => {
+                        let url = "https://files.minecraftforge.net/net/minecraftforge/forge/promotions_slim.json";
+                        let promotions: ForgePromotions =
+                            self.client().get(url).send().await?.json().await?;
+                        promotions
+                            .promos
+                            .get(&format!("{version}-recommended"))
+                            .or_else(|| promotions.promos.get(&format!("{version}-latest")))
+                            .map(|v| ("forge", v.clone()))
+                    }
+                }
+            }
+            "neoforge" => {
+                let versions =
+                    crate::infra::fetch_loader_versions(&self.client(), version, loader).await?;
+                pinned
+                    .filter(|p| versions.iter().any(|v| v == p))
+                    .map(str::to_string)
+                    .or_else(|| versions.into_iter().next())
+                    .map(|v| ("neoforge", v))
+            }
+            _ => return Ok(None),
+        };
+
+        dependency
+            .map(Some)
+            .with_context(|| format!("no {loader} release found for {version}"))
+    }
+}
+
+fn write_mrpack(path: &Path, index: &MrpackIndex) -> Result<()> {
+    let temp_path = path.with_extension("mrpack.tmp");
+    let file = std::fs::File::create(&temp_path)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("modrinth.index.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(index)?.as_bytes())?;
+    zip.finish()?;
+
+    std::fs::rename(temp_path, path)?;
+    Ok(())
+}
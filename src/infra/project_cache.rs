@@ -1,11 +1,11 @@
-use crate::domain::ModInfo;
+use crate::domain::{CacheTtl, ModInfo};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 
-const CACHE_DURATION_HOURS: u64 = 1;
+const STALE_RETENTION_HOURS: u64 = 24 * 30;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 struct CachedProject {
@@ -26,20 +26,28 @@ impl CachedProject {
         }
     }
 
-    fn is_expired(&self) -> bool {
+    fn age_seconds(&self) -> u64 {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or(Duration::from_secs(0))
             .as_secs();
 
-        let age_seconds = now.saturating_sub(self.cached_at);
-        age_seconds >= CACHE_DURATION_HOURS * 60 * 60
+        now.saturating_sub(self.cached_at)
+    }
+
+    fn is_expired(&self, ttl: &CacheTtl) -> bool {
+        self.age_seconds() >= ttl.ttl_for(self.mod_info.project_type) * 60 * 60
+    }
+
+    fn is_past_retention(&self) -> bool {
+        self.age_seconds() >= STALE_RETENTION_HOURS * 60 * 60
     }
 }
 
 pub struct ProjectCache {
     cache_dir: PathBuf,
     memory_cache: RwLock<HashMap<String, CachedProject>>,
+    ttl: std::sync::RwLock<CacheTtl>,
 }
 
 impl ProjectCache {
@@ -47,38 +55,78 @@ impl ProjectCache {
         Self {
             cache_dir: cache_dir.join("data"),
             memory_cache: RwLock::new(HashMap::new()),
+            ttl: std::sync::RwLock::new(CacheTtl::default()),
         }
     }
 
+    // Entries older than the new TTL are dropped from memory right away, disk entries
+    // are checked against it on their next lookup
+    pub async fn set_ttl(&self, ttl: CacheTtl) {
+        if let Ok(mut current) = self.ttl.write() {
+            *current = ttl;
+        }
+        self.memory_cache
+            .write()
+            .await
+            .retain(|_, cached| !cached.is_expired(&ttl));
+    }
+
+    fn ttl(&self) -> CacheTtl {
+        self.ttl.read().map(|t| *t).unwrap_or_default()
+    }
+
     pub async fn get(&self, mod_id: &str, version: &str, loader: &str) -> Option<ModInfo> {
+        self.lookup(mod_id, version, loader, false).await
+    }
+
+    pub async fn get_stale(&self, mod_id: &str, version: &str, loader: &str) -> Option<ModInfo> {
+        self.lookup(mod_id, version, loader, true).await
+    }
+
+    async fn lookup(
+        &self,
+        mod_id: &str,
+        version: &str,
+        loader: &str,
+        allow_stale: bool,
+    ) -> Option<ModInfo> {
         let key = Self::make_key(mod_id, version, loader);
+        let ttl = self.ttl();
 
         {
             let memory_cache = self.memory_cache.read().await;
             if let Some(cached) = memory_cache.get(&key)
-                && !cached.is_expired()
+                && (allow_stale || !cached.is_expired(&ttl))
             {
                 return Some(cached.mod_info.clone());
             }
         }
 
-        let cache_path = self.cache_path(&key);
-        if cache_path.exists()
-            && let Ok(content) = tokio::fs::read_to_string(&cache_path).await
-            && let Ok(cached) = serde_json::from_str::<CachedProject>(&content)
+        if let Some(cached) = self.read_disk(&key).await
+            && (allow_stale || !cached.is_expired(&ttl))
         {
-            if !cached.is_expired() {
+            if !cached.is_expired(&ttl) {
                 let mut memory_cache = self.memory_cache.write().await;
                 memory_cache.insert(key.clone(), cached.clone());
-                return Some(cached.mod_info);
-            } else {
-                let _ = tokio::fs::remove_file(&cache_path).await;
             }
+            return Some(cached.mod_info);
         }
 
         None
     }
 
+    // Only entries past their TTL, to show right away while a fresh copy is fetched
+    pub async fn get_expired(&self, mod_id: &str, version: &str, loader: &str) -> Option<ModInfo> {
+        let key = Self::make_key(mod_id, version, loader);
+        let cached = self.read_disk(&key).await?;
+        cached.is_expired(&self.ttl()).then_some(cached.mod_info)
+    }
+
+    async fn read_disk(&self, key: &str) -> Option<CachedProject> {
+        let content = tokio::fs::read_to_string(self.cache_path(key)).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
     pub async fn set(&self, mod_id: &str, version: &str, loader: &str, mod_info: ModInfo) {
         let key = Self::make_key(mod_id, version, loader);
         let cached = CachedProject::new(mod_info);
@@ -98,10 +146,17 @@ impl ProjectCache {
         }
     }
 
+    pub async fn remove(&self, mod_id: &str, version: &str, loader: &str) {
+        let key = Self::make_key(mod_id, version, loader);
+        self.memory_cache.write().await.remove(&key);
+        let _ = tokio::fs::remove_file(self.cache_path(&key)).await;
+    }
+
     pub async fn clear_expired(&self) {
+        let ttl = self.ttl();
         {
             let mut memory_cache = self.memory_cache.write().await;
-            memory_cache.retain(|_, cached| !cached.is_expired());
+            memory_cache.retain(|_, cached| !cached.is_expired(&ttl));
         }
 
         if let Ok(mut entries) = tokio::fs::read_dir(&self.cache_dir).await {
@@ -110,7 +165,7 @@ impl ProjectCache {
                 if path.is_file()
                     && let Ok(content) = tokio::fs::read_to_string(&path).await
                     && let Ok(cached) = serde_json::from_str::<CachedProject>(&content)
-                    && cached.is_expired()
+                    && cached.is_past_retention()
                 {
                     let _ = tokio::fs::remove_file(&path).await;
                 }
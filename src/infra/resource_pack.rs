@@ -0,0 +1,136 @@
+use super::jar_metadata::matches_version_requirement;
+use anyhow::Result;
+use serde_json::Value;
+use std::io::Read;
+use std::path::Path;
+
+// Resource pack formats and the Minecraft releases that expect them, oldest first
+const PACK_FORMATS: &[(u32, &str, &str)] = &[
+    (1, "1.6.1", "1.8.9"),
+    (2, "1.9", "1.10.2"),
+    (3, "1.11", "1.12.2"),
+    (4, "1.13", "1.14.4"),
+    (5, "1.15", "1.16.1"),
+    (6, "1.16.2", "1.16.5"),
+    (7, "1.17", "1.17.1"),
+    (8, "1.18", "1.18.2"),
+    (9, "1.19", "1.19.2"),
+    (12, "1.19.3", "1.19.3"),
+    (13, "1.19.4", "1.19.4"),
+    (15, "1.20", "1.20.1"),
+    (18, "1.20.2", "1.20.2"),
+    (22, "1.20.3", "1.20.4"),
+    (32, "1.20.5", "1.20.6"),
+    (34, "1.21", "1.21.1"),
+    (42, "1.21.2", "1.21.3"),
+    (46, "1.21.4", "1.21.4"),
+    (55, "1.21.5", "1.21.5"),
+    (63, "1.21.6", "1.21.6"),
+    (64, "1.21.7", "1.21.8"),
+    (69, "1.21.9", "1.21.10"),
+];
+
+// Returns a description of the declared format when the pack's pack.mcmeta doesn't support the
+// format the given Minecraft version expects. Versions missing from the table are treated as unknown.
+pub fn validate_resource_pack(path: &Path, mc_version: &str) -> Result<Option<String>> {
+    let Some(expected) = expected_pack_format(mc_version) else {
+        return Ok(None);
+    };
+    let Some((min, max)) = read_declared_formats(path)? else {
+        return Ok(None);
+    };
+
+    if (min..=max).contains(&expected) {
+        Ok(None)
+    } else if min == max {
+        Ok(Some(describe_format(min)))
+    } else {
+        Ok(Some(format!(
+            "{} to {}",
+            describe_format(min),
+            describe_format(max)
+        )))
+    }
+}
+
+fn expected_pack_format(mc_version: &str) -> Option<u32> {
+    // Snapshots can't be placed in the table
+    if !mc_version
+        .split('.')
+        .all(|part| part.parse::<u32>().is_ok())
+    {
+        return None;
+    }
+
+    PACK_FORMATS
+        .iter()
+        .find(|(_, min, max)| matches_version_requirement(&format!(">={min} <={max}"), mc_version))
+        .map(|(format, _, _)| *format)
+}
+
+fn describe_format(format: u32) -> String {
+    match PACK_FORMATS.iter().find(|(f, _, _)| *f == format) {
+        Some((_, min, max)) if min == max => format!("format {format} (Minecraft {min})"),
+        Some((_, min, max)) => format!("format {format} (Minecraft {min}–{max})"),
+        None => format!("format {format}"),
+    }
+}
+
+// The inclusive range of formats a pack declares, from min_format/max_format (1.21.9+),
+// supported_formats (1.20.2+) or the plain pack_format
+fn read_declared_formats(path: &Path) -> Result<Option<(u32, u32)>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let mut entry = match archive.by_name("pack.mcmeta") {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents)?;
+
+    let Ok(json) = serde_json::from_str::<Value>(contents.trim_start_matches('\u{feff}')) else {
+        return Ok(None);
+    };
+    let Some(pack) = json.get("pack") else {
+        return Ok(None);
+    };
+
+    if let (Some(min), Some(max)) = (
+        pack.get("min_format").and_then(major_format),
+        pack.get("max_format").and_then(major_format),
+    ) {
+        return Ok(Some((min, max)));
+    }
+
+    let pack_format = pack.get("pack_format").and_then(major_format);
+    let supported = pack.get("supported_formats").and_then(format_range);
+
+    Ok(match (pack_format, supported) {
+        (Some(format), Some((min, max))) => Some((min.min(format), max.max(format))),
+        (Some(format), None) => Some((format, format)),
+        (None, range) => range,
+    })
+}
+
+// Formats are plain numbers, or [major, minor] pairs since 1.21.9
+fn major_format(value: &Value) -> Option<u32> {
+    match value {
+        Value::Array(parts) => parts.first().and_then(major_format),
+        _ => value.as_u64().and_then(|v| u32::try_from(v).ok()),
+    }
+}
+
+fn format_range(value: &Value) -> Option<(u32, u32)> {
+    match value {
+        Value::Array(bounds) if bounds.len() == 2 => {
+            Some((major_format(&bounds[0])?, major_format(&bounds[1])?))
+        }
+        Value::Object(bounds) => Some((
+            major_format(bounds.get("min_inclusive")?)?,
+            major_format(bounds.get("max_inclusive")?)?,
+        )),
+        _ => major_format(value).map(|format| (format, format)),
+    }
+}
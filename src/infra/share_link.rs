@@ -0,0 +1,148 @@
+use crate::domain::{ModEntry, ModList, ModLoader, ProjectType, ProviderKind, ReleaseChannel};
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+// Leading byte of every share link, followed by the format version
+const SHARE_LINK_MAGIC: u8 = 0x4d;
+const SHARE_LINK_VERSION: u8 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SharedList {
+    #[serde(rename = "n")]
+    name: String,
+    #[serde(rename = "v")]
+    version: String,
+    #[serde(rename = "l")]
+    loader: String,
+    #[serde(rename = "t")]
+    content_type: ProjectType,
+    #[serde(rename = "m")]
+    mods: Vec<SharedMod>,
+    #[serde(rename = "d", default, skip_serializing_if = "String::is_empty")]
+    notes: String,
+    #[serde(rename = "g", default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SharedMod {
+    #[serde(rename = "i")]
+    id: String,
+    #[serde(rename = "n")]
+    name: String,
+    #[serde(rename = "p", default, skip_serializing_if = "is_modrinth")]
+    provider: ProviderKind,
+    #[serde(rename = "v", default, skip_serializing_if = "Option::is_none")]
+    pinned_version: Option<String>,
+    #[serde(rename = "f", default, skip_serializing_if = "Option::is_none")]
+    filename_override: Option<String>,
+}
+
+fn is_modrinth(provider: &ProviderKind) -> bool {
+    *provider == ProviderKind::Modrinth
+}
+
+pub fn encode_share_link(list: &ModList) -> Result<String> {
+    let shared = SharedList {
+        name: list.name.clone(),
+        version: list.version.clone(),
+        loader: list.loader.id.clone(),
+        content_type: list.content_type,
+        notes: list.notes.clone(),
+        tags: list.tags.clone(),
+        mods: list
+            .mods
+            .iter()
+            .filter(|m| !m.archived)
+            .map(|m| SharedMod {
+                id: m.mod_id.clone(),
+                name: m.mod_name.clone(),
+                provider: m.provider,
+                pinned_version: m.pinned_version.clone(),
+                filename_override: m.filename_override.clone(),
+            })
+            .collect(),
+    };
+
+    let mut encoder = DeflateEncoder::new(
+        vec![SHARE_LINK_MAGIC, SHARE_LINK_VERSION],
+        Compression::best(),
+    );
+    encoder.write_all(&serde_json::to_vec(&shared)?)?;
+    let bytes = encoder.finish()?;
+
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+pub fn decode_share_link(link: &str) -> Result<ModList> {
+    // Accept links pasted with a URL or surrounding whitespace
+    let encoded = link
+        .trim()
+        .rsplit(['/', '#', '='])
+        .next()
+        .unwrap_or_default();
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .context("Share link is not valid base64")?;
+
+    match bytes.as_slice() {
+        [SHARE_LINK_MAGIC, SHARE_LINK_VERSION, ..] => {}
+        [SHARE_LINK_MAGIC, version, ..] => {
+            bail!("Share link format version {version} is not supported by this version")
+        }
+        _ => bail!("Not a mod list share link"),
+    }
+
+    let mut json = Vec::new();
+    DeflateDecoder::new(&bytes[2..])
+        .read_to_end(&mut json)
+        .context("Share link is corrupted")?;
+    let shared: SharedList = serde_json::from_slice(&json).context("Share link is corrupted")?;
+
+    let now = chrono::Utc::now();
+    let mut list = ModList {
+        id: format!("list_{}", now.timestamp_millis()),
+        name: shared.name,
+        created_at: now,
+        mods: shared
+            .mods
+            .into_iter()
+            .map(|m| ModEntry {
+                mod_id: m.id,
+                mod_name: m.name,
+                added_at: now,
+                archived: false,
+                compatibility_override: false,
+                provider: m.provider,
+                added_as_dependency: false,
+                pinned_version: m.pinned_version,
+                filename_override: m.filename_override,
+                disabled: false,
+                locked: false,
+            })
+            .collect(),
+        version: shared.version,
+        loader: ModLoader {
+            name: shared.loader.clone(),
+            id: shared.loader,
+        },
+        download_dir: String::new(),
+        content_type: shared.content_type,
+        include_snapshots: false,
+        server_layout: false,
+        server_mode: false,
+        loader_version: None,
+        allowed_channel: ReleaseChannel::Alpha,
+        notes: shared.notes,
+        tags: shared.tags,
+        is_favorite: false,
+    };
+    list.drop_invalid_filename_overrides();
+    Ok(list)
+}
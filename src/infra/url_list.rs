@@ -0,0 +1,146 @@
+use crate::domain::{CompatPolicy, Event, ModList, ModService, OverwritePolicy, compatibility_for};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+pub struct UrlListService {
+    mod_service: Arc<ModService>,
+}
+
+impl UrlListService {
+    pub fn new(mod_service: Arc<ModService>) -> Self {
+        Self { mod_service }
+    }
+
+    // Writes one direct download URL per line, suitable for `wget -i`
+    pub async fn export_url_list(
+        &self,
+        path: PathBuf,
+        list: ModList,
+        version: String,
+        loader: String,
+        overwrite: OverwritePolicy,
+        tx: mpsc::Sender<Event>,
+    ) {
+        let entries: Vec<_> = list.mods.iter().filter(|m| !m.archived).collect();
+
+        let mut successful_mods = Vec::new();
+        let mut failed = Vec::new();
+        let mut warnings = Vec::new();
+        let mut urls = Vec::new();
+
+        for (idx, entry) in entries.iter().enumerate() {
+            let _ = tx
+                .send(Event::LegacyListProgress {
+                    current: idx + 1,
+                    total: entries.len(),
+                    message: format!("Resolving '{}'...", entry.mod_name),
+                })
+                .await;
+
+            let resolved = match &entry.pinned_version {
+                Some(version_id) => {
+                    self.mod_service
+                        .get_pinned_mod(entry.provider, &entry.mod_id, version_id)
+                        .await
+                }
+                None => {
+                    self.mod_service
+                        .get_mod_by_id(
+                            entry.provider,
+                            &entry.mod_id,
+                            &version,
+                            &loader,
+                            list.allowed_channel,
+                        )
+                        .await
+                }
+            };
+
+            let mod_info = match resolved {
+                Ok(info) => info,
+                Err(e) => {
+                    log::warn!("Failed to resolve '{}': {e}", entry.mod_id);
+                    failed.push(entry.mod_id.clone());
+                    continue;
+                }
+            };
+
+            let compatible = compatibility_for(
+                list.content_type,
+                &mod_info,
+                &version,
+                &loader,
+                CompatPolicy::Exact,
+                None,
+            );
+            if !compatible && !entry.compatibility_override {
+                warnings.push(format!(
+                    "'{}' is not compatible with {version} {loader}, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            if mod_info.download_url.is_empty() {
+                warnings.push(format!(
+                    "'{}' has no direct download, skipping",
+                    mod_info.name
+                ));
+                failed.push(entry.mod_id.clone());
+                continue;
+            }
+
+            urls.push(mod_info.download_url.clone());
+            successful_mods.push(mod_info);
+        }
+
+        if let Err(e) = crate::infra::backup_existing_file(&path, overwrite).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("{e:#}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        let temp_path = path.with_extension("txt.tmp");
+        let content = format!(
+            "# Minecraft {version} | {loader}\n# Generated on {}\n{}\n",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
+            urls.join("\n")
+        );
+
+        if let Err(e) = tokio::fs::write(&temp_path, content).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("Failed to write file: {e}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        if let Err(e) = tokio::fs::rename(temp_path, &path).await {
+            let _ = tx
+                .send(Event::LegacyListFailed {
+                    error: format!("Failed to finalize file: {e}"),
+                    is_import: false,
+                })
+                .await;
+            return;
+        }
+
+        let _ = tx
+            .send(Event::LegacyListComplete {
+                suggested_name: path.file_stem().unwrap().to_str().unwrap().to_string(),
+                successful: successful_mods,
+                failed,
+                warnings,
+                is_import: false,
+            })
+            .await;
+    }
+}
@@ -28,12 +28,28 @@ fn main() -> eframe::Result<()> {
         height,
     };
 
+    // Read before the window opens so its placement and theme are right from the first frame
+    let saved_config = runtime.block_on(async {
+        let config_manager = infra::ConfigManager::new().ok()?;
+        config_manager.load_config().await.ok()
+    });
+    let saved_window = saved_config.as_ref().and_then(|config| config.window);
+    let theme = saved_config.map(|config| config.theme).unwrap_or_default();
+
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_inner_size([1200.0, 700.0])
+        .with_min_inner_size([1000.0, 400.0])
+        .with_title("Minecraft Mod Downloader")
+        .with_icon(icon_data);
+    if let Some(window) = saved_window {
+        viewport = viewport
+            .with_inner_size([window.width, window.height])
+            .with_position([window.x, window.y])
+            .with_maximized(window.maximized);
+    }
+
     let options = NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_inner_size([1200.0, 700.0])
-            .with_min_inner_size([1000.0, 400.0])
-            .with_title("Minecraft Mod Downloader")
-            .with_icon(icon_data),
+        viewport,
         ..Default::default()
     };
 
@@ -42,7 +58,7 @@ fn main() -> eframe::Result<()> {
         options,
         Box::new(|cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::new(App::new(cc, runtime)) as Box<dyn eframe::App>)
+            Ok(Box::new(App::new(cc, runtime, saved_window, theme)) as Box<dyn eframe::App>)
         }),
     )
 }
@@ -12,15 +12,28 @@ impl Dialogs {
         FileDialog::new()
             .add_filter("MMD List", &["mmd"])
             .add_filter("Legacy Mod List", &["mods", "all-mods", "queue-mods"])
+            .add_filter("Modrinth Modpack", &["mrpack"])
+            .add_filter("URL List (wget -i)", &["txt"])
+            .add_filter("Lockfile (exact versions + hashes)", &["json"])
             .set_title("Export List")
             .set_file_name(format!("{default_name}.mmd"))
             .save_file()
     }
 
+    pub fn save_export_bundle_file(default_name: &str) -> Option<PathBuf> {
+        FileDialog::new()
+            .add_filter("MMD Bundle", &["mmd"])
+            .add_filter("Modrinth Modpack", &["mrpack"])
+            .set_title("Export Bundle")
+            .set_file_name(format!("{default_name}.mmd"))
+            .save_file()
+    }
+
     pub fn pick_import_list_file() -> Option<PathBuf> {
         FileDialog::new()
             .add_filter("MMD List", &["mmd"])
             .add_filter("Legacy Mod List", &["mods", "all-mods", "queue-mods"])
+            .add_filter("Lockfile", &["json"])
             .pick_file()
     }
 }
@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+
+pub fn open_folder(path: &Path) {
+    #[cfg(target_os = "windows")]
+    let result = Command::new("explorer").arg(path).spawn();
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg(path).spawn();
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = Command::new("xdg-open").arg(path).spawn();
+
+    if let Err(e) = result {
+        log::warn!("Failed to open {}: {e}", path.display());
+    }
+}
+
+pub fn reveal_file(path: &Path) {
+    #[cfg(target_os = "windows")]
+    let result = Command::new("explorer")
+        .arg(format!("/select,{}", path.display()))
+        .spawn()
+        .map(|_| ());
+    #[cfg(target_os = "macos")]
+    let result = Command::new("open").arg("-R").arg(path).spawn().map(|_| ());
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let result = reveal_file_linux(path);
+
+    if let Err(e) = result {
+        log::warn!("Failed to reveal {}: {e}", path.display());
+    }
+}
+
+// Linux has no standard reveal, so ask the file manager over D-Bus and fall back to the folder
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn reveal_file_linux(path: &Path) -> std::io::Result<()> {
+    let Ok(uri) = reqwest::Url::from_file_path(path) else {
+        return Command::new("xdg-open")
+            .arg(path.parent().unwrap_or(path))
+            .spawn()
+            .map(|_| ());
+    };
+    let path = path.to_path_buf();
+
+    // Waiting for the reply would stall the UI, dbus-send blocks until the file manager answers
+    std::thread::spawn(move || {
+        let shown = Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ])
+            .arg(format!("array:string:{uri}"))
+            .arg("string:")
+            .output()
+            .is_ok_and(|output| output.status.success());
+
+        if !shown
+            && let Err(e) = Command::new("xdg-open")
+                .arg(path.parent().unwrap_or(&path))
+                .spawn()
+        {
+            log::warn!("Failed to reveal {}: {e}", path.display());
+        }
+    });
+    Ok(())
+}
@@ -1,37 +1,63 @@
 mod dialogs;
+mod explorer;
 mod panels;
 mod view_state;
 mod windows;
 
 use panels::{MainPanel, SidebarPanel, TopPanel};
-pub use view_state::ViewState;
+pub use view_state::{LockedAction, ViewState};
 use windows::{
-    CreateListWindow, ImportWindow, LegacyImportSettingsWindow, LegacyWindow, ListSettingsWindow,
-    SearchWindow, SettingsWindow,
+    AlternativesWindow, BulkAddWindow, BundleWindow, CleanUpOrphansWindow, CollectionImportWindow,
+    CompatibilityWindow, CreateListWindow, DeleteUnknownWindow, DependencyWindow, DiskSpaceWindow,
+    DownloadHistoryWindow, ExportOverwriteWindow, ImportWindow, LegacyImportSettingsWindow,
+    LegacyWindow, ListSettingsWindow, LockedActionWindow, LockfileWindow, OverruleAllWindow,
+    QuitWindow, SearchWindow, SettingsWindow, VerifyWindow,
 };
 
 use crate::app::{AppRuntime, AppState, Effect};
+use crate::domain::{Theme, WindowGeometry};
 use eframe::egui;
 
+pub fn apply_theme(ctx: &egui::Context, theme: Theme) {
+    ctx.set_theme(match theme {
+        Theme::System => egui::ThemePreference::System,
+        Theme::Light => egui::ThemePreference::Light,
+        Theme::Dark => egui::ThemePreference::Dark,
+    });
+}
+
 pub struct App {
     state: AppState,
     view_state: ViewState,
     runtime: AppRuntime,
-    _tokio_runtime: tokio::runtime::Runtime,
+    tokio_runtime: tokio::runtime::Runtime,
+    saved_window: Option<WindowGeometry>,
+    window_checked: bool,
 }
 
 impl App {
-    pub fn new(_cc: &eframe::CreationContext<'_>, runtime: tokio::runtime::Runtime) -> Self {
+    pub fn new(
+        cc: &eframe::CreationContext<'_>,
+        runtime: tokio::runtime::Runtime,
+        saved_window: Option<WindowGeometry>,
+        theme: Theme,
+    ) -> Self {
+        apply_theme(&cc.egui_ctx, theme);
+
         let rt_handle = runtime.handle().clone();
         let (app_runtime, event_rx) = AppRuntime::new(rt_handle);
-        let (state, init_effects) = AppState::new(event_rx);
+        let (mut state, init_effects) = AppState::new(event_rx);
         app_runtime.enqueue_all(init_effects);
+        state.window_geometry = saved_window;
+        state.theme = theme;
 
         Self {
             state,
             view_state: ViewState::default(),
             runtime: app_runtime,
-            _tokio_runtime: runtime,
+            tokio_runtime: runtime,
+            saved_window,
+            window_checked: false,
         }
     }
 
@@ -42,17 +68,111 @@ impl App {
     fn icon_service(&mut self, ctx: &egui::Context) {
         self.runtime.icon_service.update(ctx);
     }
+
+    // A window saved on a monitor that's no longer connected can open off-screen. When the
+    // monitor setup changed and the window doesn't fit the current monitor, center it there.
+    fn clamp_restored_window(&mut self, ctx: &egui::Context) {
+        if self.window_checked {
+            return;
+        }
+
+        let (outer, inner, monitor) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.outer_rect,
+                viewport.inner_rect,
+                viewport.monitor_size,
+            )
+        });
+        let (Some(outer), Some(inner), Some(monitor)) = (outer, inner, monitor) else {
+            return;
+        };
+        self.window_checked = true;
+
+        let Some(saved) = self.saved_window else {
+            return;
+        };
+        let same_setup = saved.monitor_width == monitor.x && saved.monitor_height == monitor.y;
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, monitor);
+        if same_setup || screen.contains_rect(outer) {
+            return;
+        }
+
+        log::info!("Saved window position is outside the current monitor, moving it back");
+        let size = inner.size().min(monitor);
+        if size != inner.size() {
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(size));
+        }
+        let position = ((monitor - outer.size().min(monitor)) / 2.0).to_pos2();
+        ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+    }
+
+    fn track_window_geometry(&mut self, ctx: &egui::Context) {
+        let (outer, inner, maximized, minimized, monitor) = ctx.input(|i| {
+            let viewport = i.viewport();
+            (
+                viewport.outer_rect,
+                viewport.inner_rect,
+                viewport.maximized.unwrap_or(false),
+                viewport.minimized.unwrap_or(false),
+                viewport.monitor_size.unwrap_or_default(),
+            )
+        });
+
+        if minimized {
+            return;
+        }
+
+        // Keep the last normal placement so un-maximizing after a restart restores it
+        if maximized {
+            if let Some(geometry) = &mut self.state.window_geometry {
+                geometry.maximized = true;
+            }
+            return;
+        }
+
+        if let (Some(outer), Some(inner)) = (outer, inner) {
+            self.state.window_geometry = Some(WindowGeometry {
+                x: outer.min.x,
+                y: outer.min.y,
+                width: inner.width(),
+                height: inner.height(),
+                maximized: false,
+                monitor_width: monitor.x,
+                monitor_height: monitor.y,
+            });
+        }
+    }
 }
 
 impl eframe::App for App {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        // Settings aren't known until the initial data loaded, saving now would reset them
+        if self.state.initial_loading {
+            return;
+        }
+
+        let config = self.state.app_config();
+        if let Err(e) = self
+            .tokio_runtime
+            .block_on(self.runtime.config_manager.save_config(&config))
+        {
+            log::warn!("Failed to save config on exit: {e}");
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.clamp_restored_window(ctx);
+        self.track_window_geometry(ctx);
+
         let effects = self.state.process_events();
         self.run_effects(effects);
         self.icon_service(ctx);
 
         if self.state.initial_loading {
             ctx.request_repaint();
-            self.show_loading_screen(ctx);
+            let effects = self.show_loading_screen(ctx);
+            self.run_effects(effects);
             return;
         }
 
@@ -60,6 +180,18 @@ impl eframe::App for App {
             self.view_state.close_all_windows();
         }
 
+        let shortcut_effects = self.handle_shortcuts(ctx);
+        self.run_effects(shortcut_effects);
+
+        let update_check_effects = self.state.poll_update_check();
+        self.run_effects(update_check_effects);
+        // egui only repaints on input, this wakes it up for the next check
+        if let Some(next_check) = self.state.next_update_check_in() {
+            ctx.request_repaint_after(next_check);
+        }
+
+        self.handle_close_request(ctx);
+
         self.render_main_ui(ctx);
 
         self.render_windows(ctx);
@@ -67,7 +199,85 @@ impl eframe::App for App {
 }
 
 impl App {
-    fn show_loading_screen(&self, ctx: &egui::Context) {
+    // Closing with downloads running asks first, the window stays open while waiting for them
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        let active = self.state.active_download_count();
+        if ctx.input(|i| i.viewport().close_requested())
+            && active > 0
+            && !self.view_state.quit_confirmed
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.view_state.quit_prompt_open = true;
+        }
+
+        if self.view_state.quit_when_done {
+            if active == 0 {
+                self.view_state.quit_confirmed = true;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        if ctx.wants_keyboard_input() {
+            return effects;
+        }
+
+        let (download_all, focus_search, new_list, undo) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::D),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::F),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::N),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+            )
+        });
+
+        if undo {
+            if let Some(action) = self.state.undo_description() {
+                log::info!("Undoing {action}");
+            }
+            self.view_state.selected_mod = None;
+            self.view_state.selected_mods.clear();
+            effects.extend(self.state.undo());
+        }
+
+        if new_list {
+            self.view_state.close_all_windows();
+            self.view_state.create_list_window_open = true;
+        }
+
+        if self.state.current_list_id.is_none() {
+            return effects;
+        }
+
+        if focus_search {
+            self.view_state.close_all_windows();
+            self.view_state.search_window_open = true;
+            self.view_state.search_focus_requested = true;
+        }
+
+        if download_all && !self.state.is_offline {
+            let filtered_mods = self.state.get_filtered_mods(
+                &self.view_state.search_query,
+                self.view_state.current_sort_mode,
+                self.view_state.current_order_mode,
+                self.view_state.current_filter_mode,
+            );
+            let mod_ids = self.state.get_downloadable_mod_ids(&filtered_mods);
+            effects.extend(self.state.start_bulk_download(mod_ids));
+        }
+
+        effects
+    }
+
+    fn show_loading_screen(&mut self, ctx: &egui::Context) -> Vec<Effect> {
+        let mut effects = Vec::new();
+        let step = self.state.initial_load_step;
+
         egui::CentralPanel::default().show(ctx, |ui| {
             let rect = ui.available_rect_before_wrap();
             ui.painter()
@@ -79,17 +289,33 @@ impl App {
                 ui.add_space(20.0);
                 ui.add(egui::Spinner::new().size(50.0));
                 ui.add_space(20.0);
-                ui.label(
-                    egui::RichText::new("Loading lists and metadata...")
-                        .size(16.0)
-                        .weak(),
-                );
+                let message = step.map_or("Loading lists and metadata...", |s| s.message());
+                ui.label(egui::RichText::new(message).size(16.0).weak());
+
+                if step.is_some_and(|s| s.is_network()) {
+                    ui.add_space(20.0);
+                    let skipped = self.state.initial_load_skipped;
+                    if ui
+                        .add_enabled(!skipped, egui::Button::new("Skip / work offline"))
+                        .on_hover_text("Continue with cached data and retry the network later")
+                        .clicked()
+                    {
+                        effects.extend(self.state.skip_initial_load());
+                    }
+                }
             });
         });
+
+        effects
     }
 
     fn render_main_ui(&mut self, ctx: &egui::Context) {
-        let top_effects = TopPanel::show(ctx, &mut self.view_state, &mut self.runtime);
+        let top_effects = TopPanel::show(
+            ctx,
+            &mut self.state,
+            &mut self.view_state,
+            &mut self.runtime,
+        );
         self.run_effects(top_effects);
 
         let sidebar_effects = SidebarPanel::show(
@@ -112,6 +338,14 @@ impl App {
     fn render_windows(&mut self, ctx: &egui::Context) {
         let mut effects = Vec::new();
 
+        if self.view_state.quit_prompt_open {
+            QuitWindow::show(ctx, &self.state, &mut self.view_state, &self.runtime);
+        }
+
+        if self.view_state.download_history_open {
+            DownloadHistoryWindow::show(ctx, &self.state, &mut self.view_state);
+        }
+
         if self.view_state.settings_window_open {
             let window_effects = SettingsWindow::show(ctx, &mut self.state, &mut self.view_state);
             effects.extend(window_effects);
@@ -142,6 +376,22 @@ impl App {
             effects.extend(window_effects);
         }
 
+        if self.view_state.bulk_add_open {
+            let window_effects = BulkAddWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.bundle_window_open {
+            let window_effects = BundleWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.collection_import_open {
+            let window_effects =
+                CollectionImportWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
         if self.view_state.search_window_open {
             let window_effects = SearchWindow::show(
                 ctx,
@@ -157,6 +407,73 @@ impl App {
             effects.extend(window_effects);
         }
 
+        if self.view_state.confirm_overrule_all {
+            let window_effects =
+                OverruleAllWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.confirm_locked_action.is_some() {
+            let window_effects =
+                LockedActionWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.confirm_delete_unknown.is_some() {
+            let window_effects =
+                DeleteUnknownWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.confirm_clean_up_orphans.is_some() {
+            let window_effects =
+                CleanUpOrphansWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.view_state.pending_export.is_some() {
+            let window_effects =
+                ExportOverwriteWindow::show(ctx, &mut self.state, &mut self.view_state);
+            effects.extend(window_effects);
+        }
+
+        if self.state.low_disk_space.is_some() {
+            let window_effects = DiskSpaceWindow::show(ctx, &mut self.state);
+            effects.extend(window_effects);
+        }
+
+        if self.state.alternatives.is_some() {
+            let window_effects = AlternativesWindow::show(ctx, &mut self.state, &mut self.runtime);
+            effects.extend(window_effects);
+        }
+
+        if self.state.compatibility_matrix.is_some() {
+            let window_effects = CompatibilityWindow::show(ctx, &mut self.state);
+            effects.extend(window_effects);
+        }
+
+        if self.state.lockfile_install.is_some() {
+            let window_effects = LockfileWindow::show(ctx, &mut self.state);
+            effects.extend(window_effects);
+        }
+
+        if self.state.verification.is_some() {
+            let window_effects = VerifyWindow::show(ctx, &mut self.state);
+            effects.extend(window_effects);
+        }
+
+        if self.state.pending_dependencies.is_some() {
+            let window_effects = DependencyWindow::show(ctx, &mut self.state);
+            effects.extend(window_effects);
+        }
+
+        if let Some((suggested_name, entries)) = self.state.pending_legacy_preview.take() {
+            self.view_state.reset_legacy_import();
+            self.view_state.legacy_import_name = suggested_name;
+            self.view_state.legacy_import_preview = Some(entries);
+            self.view_state.legacy_import_settings_open = true;
+        }
+
         if self.view_state.legacy_import_settings_open {
             let window_effects =
                 LegacyImportSettingsWindow::show(ctx, &mut self.state, &mut self.view_state);
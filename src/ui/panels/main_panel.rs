@@ -1,10 +1,18 @@
-use crate::app::{AppRuntime, AppState, DownloadStatus, Effect, ListAction};
-use crate::domain::{ModEntry, ProjectType};
-use crate::ui::{ViewState, dialogs::Dialogs};
+use crate::app::{AppRuntime, AppState, DownloadStatus, Effect, ListAction, ListStats};
+use crate::domain::{
+    ModEntry, ModEnvironment, ModInfo, OverwritePolicy, ProjectType, ReleaseChannel, format_count,
+    format_duration, generate_mod_filename, validate_filename_override,
+};
+use crate::ui::{LockedAction, ViewState, dialogs::Dialogs, explorer};
 use eframe::egui;
+use std::collections::HashMap;
+
+const OFFLINE_HINT: &str = "Downloads are unavailable while offline";
 
 pub struct MainPanel;
 
+struct DraggedMod(String);
+
 impl MainPanel {
     pub fn show(
         ctx: &egui::Context,
@@ -37,6 +45,8 @@ impl MainPanel {
             let can_interact = state.current_list_id.is_some();
             let mut content_type = ProjectType::Mod;
 
+            effects.extend(Self::handle_dropped_files(ctx, state, view_state));
+
             ui.horizontal(|ui| {
                 if let Some(list) = state.get_current_list() {
                     content_type = list.content_type;
@@ -55,7 +65,7 @@ impl MainPanel {
                     let loader_name = state
                         .mod_loaders
                         .iter()
-                        .find(|l| l.id == loader)
+                        .find(|l| l.matches(&loader))
                         .map(|l| l.name.as_str())
                         .unwrap_or(&loader);
 
@@ -75,10 +85,9 @@ impl MainPanel {
                     if view_state.show_rename_input {
                         ui.text_edit_singleline(&mut view_state.rename_list_input);
                         if ui.button("✔").clicked() {
-                            if let Some(list) = state.get_current_list_mut() {
-                                list.name = view_state.rename_list_input.clone();
-                                effects.push(Effect::SaveList { list: list.clone() });
-                            }
+                            effects.extend(
+                                state.rename_current_list(view_state.rename_list_input.clone()),
+                            );
                             view_state.show_rename_input = false;
                         }
                         if ui.button("❌").clicked() {
@@ -108,6 +117,8 @@ impl MainPanel {
                             && let Some(list) = state.get_current_list().cloned()
                         {
                             view_state.import_name_input = format!("{} (Copy)", list.name);
+                            view_state.duplicate_version = state.get_effective_version();
+                            view_state.duplicate_loader = state.get_effective_loader();
                             view_state.pending_import_list = Some(list);
                             view_state.active_action = ListAction::Duplicate;
                             view_state.import_window_open = true;
@@ -117,41 +128,49 @@ impl MainPanel {
                             .add_enabled(can_interact, egui::Button::new("📂 Open Folder"))
                             .on_hover_text("Open download directory")
                             .clicked()
-                            && let Some(list) = state.get_current_list()
                         {
-                            let download_dir = if list.download_dir.is_empty() {
-                                state.get_effective_download_dir()
-                            } else {
-                                list.download_dir.clone()
-                            };
+                            let download_dir = state.get_effective_download_dir();
+                            explorer::open_folder(std::path::Path::new(&download_dir));
+                        }
 
-                            #[cfg(target_os = "windows")]
-                            {
-                                let _ = std::process::Command::new("explorer")
-                                    .arg(&download_dir)
-                                    .spawn();
-                            }
-                            #[cfg(target_os = "macos")]
-                            {
-                                let _ = std::process::Command::new("open")
-                                    .arg(&download_dir)
-                                    .spawn();
-                            }
-                            #[cfg(target_os = "linux")]
-                            {
-                                let _ = std::process::Command::new("xdg-open")
-                                    .arg(&download_dir)
-                                    .spawn();
+                        if ui
+                            .add_enabled(can_interact, egui::Button::new("🔗 Share"))
+                            .on_hover_text("Copy a share link for this list to the clipboard")
+                            .clicked()
+                            && let Some(list) = state.get_current_list()
+                        {
+                            match crate::infra::encode_share_link(list) {
+                                Ok(link) => ctx.copy_text(link),
+                                Err(e) => log::error!("Failed to create share link: {e}"),
                             }
                         }
 
+                        if ui
+                            .add_enabled(can_interact, egui::Button::new("🧮 Matrix"))
+                            .on_hover_text("Compare availability across Minecraft versions")
+                            .clicked()
+                        {
+                            effects.extend(state.open_compatibility_matrix());
+                        }
+
+                        if ui
+                            .add_enabled(can_interact, egui::Button::new("🛡 Verify"))
+                            .on_hover_text(
+                                "Check that downloaded files are present, hashes are checked \
+                                 if any are missing or on request",
+                            )
+                            .clicked()
+                        {
+                            effects.extend(state.verify_current_list(false));
+                        }
+
                         if ui
                             .add_enabled(can_interact, egui::Button::new("📤 Export"))
                             .clicked()
                             && let Some(list) = state.get_current_list()
                             && let Some(save_path) = Dialogs::save_export_list_file(&list.name)
                         {
-                            effects.extend(state.export_current_list(save_path));
+                            effects.extend(Self::start_export(state, view_state, save_path, None));
                         }
 
                         let sort_label = match view_state.current_order_mode {
@@ -174,6 +193,16 @@ impl MainPanel {
                 });
             });
 
+            if let Some(list) = state.get_current_list()
+                && !list.notes.is_empty()
+            {
+                ui.label(egui::RichText::new(&list.notes).italics().weak());
+            }
+
+            if can_interact {
+                Self::render_list_stats(ui, &state.list_stats());
+            }
+
             ui.add_space(4.0);
 
             ui.horizontal(|ui| {
@@ -187,6 +216,74 @@ impl MainPanel {
                     view_state.search_window_open = true;
                 }
 
+                if ui
+                    .add_enabled(can_interact, egui::Button::new("📋 Add Multiple"))
+                    .on_hover_text("Paste Modrinth slugs or links, one per line")
+                    .clicked()
+                {
+                    view_state.bulk_add_open = true;
+                }
+
+                if state.is_offline {
+                    ui.colored_label(egui::Color32::YELLOW, "📴 Offline")
+                        .on_hover_text("No connection, showing cached data");
+                } else if let Some(error) = &state.http_client_error {
+                    ui.colored_label(egui::Color32::YELLOW, "⚠ Proxy not applied")
+                        .on_hover_text(error);
+                } else if state.is_rate_limited {
+                    ui.label(egui::RichText::new("⏳ Rate limited, waiting…").weak())
+                        .on_hover_text("Modrinth asked to slow down, requests continue shortly");
+                }
+
+                if state.dropped_files_pending > 0 {
+                    ui.spinner();
+                    ui.label(format!(
+                        "Matching {} dropped file(s)...",
+                        state.dropped_files_pending
+                    ));
+                } else if !view_state.rejected_dropped_files.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        format!(
+                            "⚠ Skipped {} file(s)",
+                            view_state.rejected_dropped_files.len()
+                        ),
+                    )
+                    .on_hover_text(format!(
+                        "Only .{} files can be dropped into this list:\n{}",
+                        content_type.fileext(),
+                        view_state.rejected_dropped_files.join("\n")
+                    ));
+                }
+
+                if let Some((old_version, old_loader)) = state.retarget_origin().cloned()
+                    && let Some(list) = state.get_current_list()
+                {
+                    let newly_incompatible = list
+                        .mods
+                        .iter()
+                        .filter(|e| !e.archived && state.is_newly_incompatible(&e.mod_id))
+                        .count();
+
+                    if newly_incompatible > 0 {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(255, 165, 0),
+                            format!("⚠ {newly_incompatible} no longer compatible"),
+                        )
+                        .on_hover_text(format!(
+                            "Compatible with {old_version} ({old_loader}) \
+                             but not with this list's target"
+                        ));
+                    }
+                    if ui
+                        .small_button("✖")
+                        .on_hover_text("Dismiss retarget report")
+                        .clicked()
+                    {
+                        state.dismiss_retarget_report();
+                    }
+                }
+
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     let filtered_mods = state.get_filtered_mods(
                         &view_state.search_query,
@@ -197,39 +294,68 @@ impl MainPanel {
 
                     let missing_ids = state.get_missing_mod_ids(&filtered_mods);
 
-                    let mods_to_download: Vec<String> = filtered_mods
-                        .iter()
-                        .filter(|entry| {
-                            !entry.archived
-                                && !state.mods_being_loaded.contains(&entry.mod_id)
-                                && state
-                                    .download_status
-                                    .get(&entry.mod_id)
-                                    .map(|s| {
-                                        matches!(
-                                            s,
-                                            DownloadStatus::Idle
-                                                | DownloadStatus::Complete
-                                                | DownloadStatus::Failed
-                                        )
-                                    })
-                                    .unwrap_or(true)
-                                && state.is_mod_compatible(&entry.mod_id).unwrap_or(false)
-                        })
-                        .map(|e| e.mod_id.clone())
-                        .collect();
+                    let mods_to_download = state.get_downloadable_mod_ids(&filtered_mods);
 
                     let mods_to_download_count = mods_to_download.len();
 
                     if ui
                         .add_enabled(
-                            can_interact && !mods_to_download.is_empty(),
+                            can_interact && !state.is_offline && !mods_to_download.is_empty(),
                             egui::Button::new("⬇ Download All"),
                         )
+                        .on_hover_text(format!(
+                            "Download every compatible item ({})",
+                            if cfg!(target_os = "macos") {
+                                "⌘D"
+                            } else {
+                                "Ctrl+D"
+                            }
+                        ))
+                        .with_offline_hint(state.is_offline)
                         .clicked()
                     {
-                        for mod_id in mods_to_download {
-                            effects.extend(state.start_download(&mod_id));
+                        effects.extend(state.start_bulk_download(mods_to_download));
+                    }
+
+                    if let Some((finished, total, fraction)) = state.batch_summary() {
+                        ui.add_space(5.0);
+                        ui.add(
+                            egui::ProgressBar::new(fraction)
+                                .text(format!("{finished}/{total} · {:.0}%", fraction * 100.0))
+                                .desired_width(140.0),
+                        )
+                        .on_hover_text("Overall progress of the running downloads");
+                        if let Some(eta) = state.batch_eta() {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "≈ {} remaining",
+                                    format_duration(eta)
+                                ))
+                                .weak(),
+                            )
+                            .on_hover_text(
+                                "Estimated from the transfer speed of the last few seconds",
+                            );
+                        }
+                    }
+
+                    let updatable_ids = state.scan_updates();
+
+                    if !updatable_ids.is_empty() {
+                        ui.add_space(5.0);
+                        if ui
+                            .add_enabled(
+                                can_interact && !state.is_offline,
+                                egui::Button::new(format!(
+                                    "🔄 Update All ({})",
+                                    updatable_ids.len()
+                                )),
+                            )
+                            .on_hover_text("Download the latest version of every outdated item")
+                            .with_offline_hint(state.is_offline)
+                            .clicked()
+                        {
+                            effects.extend(state.start_bulk_download(updatable_ids));
                         }
                     }
 
@@ -237,17 +363,50 @@ impl MainPanel {
                         ui.add_space(5.0);
                         if ui
                             .add_enabled(
-                                can_interact,
+                                can_interact && !state.is_offline,
                                 egui::Button::new(format!(
                                     "⬇ Download Missing ({})",
                                     missing_ids.len()
                                 )),
                             )
+                            .with_offline_hint(state.is_offline)
                             .clicked()
                         {
-                            for mod_id in missing_ids {
-                                effects.extend(state.start_download(&mod_id));
-                            }
+                            effects.extend(state.start_bulk_download(missing_ids));
+                        }
+                    }
+
+                    let overrulable_count = state.get_overrulable_mod_ids().len();
+                    if overrulable_count > 0 {
+                        ui.add_space(5.0);
+                        if ui
+                            .add_enabled(
+                                can_interact,
+                                egui::Button::new(format!(
+                                    "⚠ Overrule Incompatible ({overrulable_count})"
+                                )),
+                            )
+                            .on_hover_text("Treat every incompatible item as compatible")
+                            .clicked()
+                        {
+                            view_state.confirm_overrule_all = true;
+                        }
+                    }
+
+                    let override_count = state.count_compatibility_overrides();
+                    if override_count > 0 {
+                        ui.add_space(5.0);
+                        if ui
+                            .add_enabled(
+                                can_interact,
+                                egui::Button::new(format!(
+                                    "🔓 Revoke Overrides ({override_count})"
+                                )),
+                            )
+                            .on_hover_text("Remove every compatibility override in this list")
+                            .clicked()
+                        {
+                            effects.extend(state.set_all_incompatible_overruled(false));
                         }
                     }
                 });
@@ -270,114 +429,581 @@ impl MainPanel {
                         view_state.current_filter_mode,
                     );
 
-                    let active_mods: Vec<_> =
-                        filtered_entries.iter().filter(|e| !e.archived).collect();
-                    let archived_mods: Vec<_> =
-                        filtered_entries.iter().filter(|e| e.archived).collect();
+                    let list_id = list.id.clone();
 
-                    let unknown_files = state.get_unknown_mod_files();
+                    view_state
+                        .selected_mods
+                        .retain(|id| list.mods.iter().any(|e| &e.mod_id == id));
 
                     ui.add_space(10.0);
 
-                    egui::ScrollArea::vertical().show(ui, |ui| {
-                        for entry in &active_mods {
-                            Self::render_mod_entry(
+                    if !view_state.selected_mods.is_empty() {
+                        Self::render_selection_toolbar(
+                            ui,
+                            &filtered_entries,
+                            state,
+                            view_state,
+                            &mut effects,
+                        );
+                        ui.add_space(6.0);
+                    }
+
+                    // Each list keeps its own scroll offset and top visible row
+                    let anchor_id = egui::Id::new(("mod_list_anchor", &list_id));
+                    let anchor: Option<(String, f32)> = ui.data(|d| d.get_temp(anchor_id));
+
+                    // Re-sorting or filtering moves every row, the old anchor no longer applies
+                    let view_id = egui::Id::new(("mod_list_view", &list_id));
+                    let view = (
+                        view_state.current_sort_mode,
+                        view_state.current_order_mode,
+                        view_state.current_filter_mode,
+                        view_state.search_query.clone(),
+                    );
+                    let old_view = ui.data(|d| d.get_temp(view_id));
+                    let view_changed = old_view.is_some() && old_view.as_ref() != Some(&view);
+                    ui.data_mut(|d| d.insert_temp(view_id, view));
+                    let anchor = anchor.filter(|_| !view_changed);
+
+                    let scroll = egui::ScrollArea::vertical()
+                        .id_salt(("mod_list_scroll", &list_id))
+                        .show(ui, |ui| {
+                            Self::render_list_body(
                                 ui,
-                                content_type,
-                                entry,
+                                &filtered_entries,
                                 state,
+                                view_state,
                                 runtime,
                                 &mut effects,
-                            );
-                        }
+                            )
+                        });
 
-                        if !archived_mods.is_empty() {
-                            ui.add_space(8.0);
-                            ui.separator();
-                            ui.horizontal(|ui| {
-                                let icon = if view_state.show_archived {
-                                    "🔽"
-                                } else {
-                                    "▶"
-                                };
-                                if ui
-                                    .button(format!("{} Archived ({})", icon, archived_mods.len()))
-                                    .clicked()
-                                {
-                                    view_state.show_archived = !view_state.show_archived;
-                                }
-                            });
+                    Self::keep_scroll_anchor(
+                        ui.ctx(),
+                        anchor_id,
+                        anchor,
+                        &scroll.inner,
+                        scroll.id,
+                        scroll.state,
+                    );
+                }
+            }
+        });
 
-                            if view_state.show_archived {
-                                ui.add_space(4.0);
-                                for entry in &archived_mods {
-                                    Self::render_mod_entry(
-                                        ui,
-                                        content_type,
-                                        entry,
-                                        state,
-                                        runtime,
-                                        &mut effects,
-                                    );
-                                }
-                            }
-                        }
+        if view_state.sort_menu_open {
+            Self::show_sort_menu(ctx, view_state);
+        }
 
-                        if !unknown_files.is_empty() {
-                            ui.add_space(8.0);
-                            ui.separator();
-                            ui.horizontal(|ui| {
-                                let icon = if view_state.show_unknown_mods {
-                                    "🔽"
-                                } else {
-                                    "▶"
-                                };
-                                if ui
-                                    .button(format!(
-                                        "{} Unknown Files ({})",
-                                        icon,
-                                        unknown_files.len()
-                                    ))
-                                    .on_hover_text("Files in download folder without metadata")
-                                    .clicked()
-                                {
-                                    view_state.show_unknown_mods = !view_state.show_unknown_mods;
-                                }
-                            });
+        effects
+    }
 
-                            if view_state.show_unknown_mods {
-                                ui.add_space(4.0);
-                                for filename in &unknown_files {
-                                    Self::render_unknown_mod_entry(
-                                        ui,
-                                        filename,
-                                        state,
-                                        &mut effects,
-                                    );
-                                }
-                            }
-                        }
+    // Rows of the current list followed by the archived, unknown and removed sections, returns
+    // each row's span for keeping the scroll anchor
+    fn render_list_body(
+        ui: &mut egui::Ui,
+        filtered_entries: &[ModEntry],
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        runtime: &mut AppRuntime,
+        effects: &mut Vec<Effect>,
+    ) -> Vec<(String, f32, f32)> {
+        let active_mods: Vec<_> = filtered_entries.iter().filter(|e| !e.archived).collect();
+        let archived_mods: Vec<_> = filtered_entries.iter().filter(|e| e.archived).collect();
+        let unknown_files = state.get_unknown_mod_files();
+        let orphaned_files = state.get_orphaned_files();
+        let conflicts = state.get_conflicts();
+        let manual_order = view_state.current_sort_mode == crate::app::SortMode::Manual;
+        let mut row_spans = Vec::new();
+
+        let content_top = ui.min_rect().top();
+        for entry in &active_mods {
+            let row = ui.horizontal(|ui| {
+                if manual_order {
+                    Self::render_drag_handle(ui, entry);
+                }
+                Self::render_selection_checkbox(ui, entry, view_state);
+                Self::render_mod_entry(ui, entry, &conflicts, state, view_state, runtime, effects);
+            });
+
+            row_spans.push((
+                entry.mod_id.clone(),
+                row.response.rect.top() - content_top,
+                row.response.rect.bottom() - content_top,
+            ));
+
+            if manual_order {
+                if let Some(dragged) = row.response.dnd_release_payload::<DraggedMod>() {
+                    effects.extend(state.move_mod(&dragged.0, &entry.mod_id));
+                } else if row.response.dnd_hover_payload::<DraggedMod>().is_some() {
+                    ui.painter().hline(
+                        row.response.rect.x_range(),
+                        row.response.rect.top(),
+                        ui.visuals().selection.stroke,
+                    );
+                }
+            }
+        }
+
+        if !archived_mods.is_empty() {
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let icon = if view_state.show_archived {
+                    "🔽"
+                } else {
+                    "▶"
+                };
+                if ui
+                    .button(format!("{} Archived ({})", icon, archived_mods.len()))
+                    .clicked()
+                {
+                    view_state.show_archived = !view_state.show_archived;
+                }
+            });
+
+            if view_state.show_archived {
+                ui.add_space(4.0);
+                for entry in &archived_mods {
+                    let row = ui.horizontal(|ui| {
+                        Self::render_selection_checkbox(ui, entry, view_state);
+                        Self::render_mod_entry(
+                            ui, entry, &conflicts, state, view_state, runtime, effects,
+                        );
                     });
+                    row_spans.push((
+                        entry.mod_id.clone(),
+                        row.response.rect.top() - content_top,
+                        row.response.rect.bottom() - content_top,
+                    ));
+                }
+            }
+        }
+
+        if !unknown_files.is_empty() {
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let icon = if view_state.show_unknown_mods {
+                    "🔽"
+                } else {
+                    "▶"
+                };
+                if ui
+                    .button(format!("{} Unknown Files ({})", icon, unknown_files.len()))
+                    .on_hover_text("Files in download folder without metadata")
+                    .clicked()
+                {
+                    view_state.show_unknown_mods = !view_state.show_unknown_mods;
+                }
+
+                if ui
+                    .button("🗑 Delete All")
+                    .on_hover_text("Delete every unknown file from the folder")
+                    .clicked()
+                {
+                    view_state.confirm_delete_unknown = Some(unknown_files.clone());
+                }
+            });
+
+            if view_state.show_unknown_mods {
+                ui.add_space(4.0);
+                for filename in &unknown_files {
+                    Self::render_unknown_mod_entry(ui, filename, state, effects);
                 }
             }
+        }
+
+        if !orphaned_files.is_empty() {
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let icon = if view_state.show_orphaned_files {
+                    "🔽"
+                } else {
+                    "▶"
+                };
+                if ui
+                    .button(format!(
+                        "{} Removed From List ({})",
+                        icon,
+                        orphaned_files.len()
+                    ))
+                    .on_hover_text("Downloaded files of mods that are no longer in the list")
+                    .clicked()
+                {
+                    view_state.show_orphaned_files = !view_state.show_orphaned_files;
+                }
+
+                if ui
+                    .button("🧹 Clean Up")
+                    .on_hover_text("Delete these files and their metadata")
+                    .clicked()
+                {
+                    view_state.confirm_clean_up_orphans = Some(orphaned_files.clone());
+                }
+            });
+
+            if view_state.show_orphaned_files {
+                ui.add_space(4.0);
+                for (mod_id, filename) in &orphaned_files {
+                    Self::render_orphaned_file_entry(ui, mod_id, filename, state, effects);
+                }
+            }
+        }
+
+        if let Some((removed, failed)) = &state.orphan_cleanup_result {
+            let mut dismiss = false;
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(format!("🧹 Cleaned up {removed} removed mods"));
+                if !failed.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_RED,
+                        format!("❌ {} failed", failed.len()),
+                    )
+                    .on_hover_text(failed.join("\n"));
+                }
+                if ui.small_button("✖").clicked() {
+                    dismiss = true;
+                }
+            });
+            if dismiss {
+                state.orphan_cleanup_result = None;
+            }
+        }
+
+        if let Some((removed, failed)) = &state.unknown_cleanup_result {
+            let mut dismiss = false;
+            ui.add_space(4.0);
+            ui.horizontal(|ui| {
+                ui.label(format!("🗑 Deleted {removed} unknown files"));
+                if !failed.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::LIGHT_RED,
+                        format!("❌ {} failed", failed.len()),
+                    )
+                    .on_hover_text(failed.join("\n"));
+                }
+                if ui.small_button("✖").clicked() {
+                    dismiss = true;
+                }
+            });
+            if dismiss {
+                state.unknown_cleanup_result = None;
+            }
+        }
+
+        row_spans
+    }
+
+    // Rows above the viewport change height as their details load, so the offset follows the
+    // top visible row instead of jumping
+    fn keep_scroll_anchor(
+        ctx: &egui::Context,
+        anchor_id: egui::Id,
+        anchor: Option<(String, f32)>,
+        row_spans: &[(String, f32, f32)],
+        scroll_id: egui::Id,
+        mut scroll_state: egui::scroll_area::State,
+    ) {
+        if scroll_state.offset.y > 0.0
+            && let Some((anchor_mod, old_top)) = anchor
+            && let Some((_, top, _)) = row_spans.iter().find(|(id, _, _)| *id == anchor_mod)
+        {
+            let shift = top - old_top;
+            if shift.abs() > 0.5 {
+                scroll_state.offset.y = (scroll_state.offset.y + shift).max(0.0);
+                scroll_state.store(ctx, scroll_id);
+                ctx.request_repaint();
+            }
+        }
+
+        let offset = scroll_state.offset.y;
+        let new_anchor = row_spans
+            .iter()
+            .find(|(_, _, bottom)| *bottom > offset)
+            .map(|(id, top, _)| (id.clone(), *top));
+        ctx.data_mut(|d| match new_anchor {
+            Some(new_anchor) => d.insert_temp(anchor_id, new_anchor),
+            None => d.remove::<(String, f32)>(anchor_id),
         });
+    }
 
-        if view_state.sort_menu_open {
-            Self::show_sort_menu(ctx, view_state);
+    fn render_channel_badge(ui: &mut egui::Ui, channel: ReleaseChannel) {
+        let color = match channel {
+            ReleaseChannel::Release => return,
+            ReleaseChannel::Beta => egui::Color32::from_rgb(255, 165, 0),
+            ReleaseChannel::Alpha => egui::Color32::from_rgb(255, 100, 100),
+        };
+
+        egui::Frame::new()
+            .stroke(egui::Stroke::new(1.0, color))
+            .corner_radius(4.0)
+            .inner_margin(egui::Margin::symmetric(4, 0))
+            .show(ui, |ui| {
+                ui.label(
+                    egui::RichText::new(channel.display_name())
+                        .small()
+                        .color(color),
+                );
+            });
+    }
+
+    // Picks the version the Download button fetches this time, without pinning it
+    fn render_version_picker(
+        ui: &mut egui::Ui,
+        info: &ModInfo,
+        state: &mut AppState,
+        effects: &mut Vec<Effect>,
+    ) {
+        let selected_text = match state.chosen_versions.get(&info.id) {
+            Some((_, Some(chosen))) => format!("⬇ v{}", chosen.version),
+            Some((_, None)) => "⬇ Loading...".to_string(),
+            None => "⬇ Latest".to_string(),
+        };
+        let current = state
+            .chosen_versions
+            .get(&info.id)
+            .map(|(id, _)| id.clone());
+        let mut choice = None;
+
+        egui::ComboBox::from_id_salt(("version_picker", &info.id))
+            .selected_text(egui::RichText::new(selected_text).small())
+            .height(300.0)
+            .show_ui(ui, |ui| {
+                effects.extend(state.load_project_versions_if_needed(info));
+                if ui
+                    .selectable_label(current.is_none(), "Latest compatible")
+                    .clicked()
+                {
+                    choice = Some(None);
+                }
+
+                let Some(versions) = state.project_versions.get(&info.id) else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Loading versions...");
+                    });
+                    return;
+                };
+                if versions.is_empty() {
+                    ui.weak("No versions found for this loader");
+                    return;
+                }
+
+                for channel in ReleaseChannel::all() {
+                    let in_channel: Vec<_> =
+                        versions.iter().filter(|v| v.channel == channel).collect();
+                    if in_channel.is_empty() {
+                        continue;
+                    }
+                    ui.separator();
+                    ui.label(egui::RichText::new(channel.display_name()).strong());
+                    for version in in_channel {
+                        let game_versions = match version.game_versions.as_slice() {
+                            [] => String::new(),
+                            [first, .., last] if version.game_versions.len() > 3 => {
+                                format!("{first} – {last}")
+                            }
+                            all => all.join(", "),
+                        };
+                        let selected = current.as_deref() == Some(version.id.as_str());
+                        if ui
+                            .selectable_label(
+                                selected,
+                                format!("{}  ({game_versions})", version.name),
+                            )
+                            .on_hover_text(version.game_versions.join(", "))
+                            .clicked()
+                        {
+                            choice = Some(Some(version.id.clone()));
+                        }
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Download a specific version once, without pinning it");
+
+        if let Some(version_id) = choice {
+            effects.extend(state.choose_download_version(info, version_id));
         }
+    }
 
-        effects
+    fn render_list_stats(ui: &mut egui::Ui, stats: &ListStats) {
+        if stats.active == 0 {
+            return;
+        }
+
+        // Counts that still depend on loading entries get a trailing ellipsis
+        let pending = if stats.unknown > 0 { "…" } else { "" };
+
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "✅ {}{pending} compatible  ❌ {}{pending} incompatible  \
+                     💾 {}/{} downloaded  ⬇ {}{pending} total downloads",
+                    stats.compatible,
+                    stats.incompatible,
+                    stats.downloaded,
+                    stats.active,
+                    format_count(stats.total_downloads),
+                ))
+                .small()
+                .weak(),
+            )
+            .on_hover_text(format!(
+                "{} missing, {} still loading",
+                stats.missing, stats.unknown
+            ));
+        });
+    }
+
+    fn render_drag_handle(ui: &mut egui::Ui, entry: &ModEntry) {
+        let id = egui::Id::new(("mod_drag", &entry.mod_id));
+        ui.dnd_drag_source(id, DraggedMod(entry.mod_id.clone()), |ui| {
+            ui.label(egui::RichText::new("☰").weak())
+                .on_hover_text("Drag to reorder");
+        });
+    }
+
+    fn render_selection_checkbox(ui: &mut egui::Ui, entry: &ModEntry, view_state: &mut ViewState) {
+        let mut selected = view_state.selected_mods.contains(&entry.mod_id);
+        if ui.checkbox(&mut selected, "").changed() {
+            if selected {
+                view_state.selected_mods.insert(entry.mod_id.clone());
+            } else {
+                view_state.selected_mods.remove(&entry.mod_id);
+            }
+        }
+    }
+
+    fn render_selection_toolbar(
+        ui: &mut egui::Ui,
+        filtered_entries: &[ModEntry],
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        effects: &mut Vec<Effect>,
+    ) {
+        let selected: Vec<String> = view_state.selected_mods.iter().cloned().collect();
+        let selected_entries: Vec<ModEntry> = filtered_entries
+            .iter()
+            .filter(|e| view_state.selected_mods.contains(&e.mod_id))
+            .cloned()
+            .collect();
+
+        let deletable = state.deletable_mods(&selected);
+        let locked_count = selected.iter().filter(|id| state.is_mod_locked(id)).count();
+        let protected_count = selected.len() - deletable.len() - locked_count;
+        let kept_count = selected.len() - deletable.len();
+        let downloadable = state.get_downloadable_mod_ids(&selected_entries);
+
+        ui.horizontal(|ui| {
+            ui.label(format!("{} selected", selected.len()));
+
+            if ui.button("Select all").clicked() {
+                view_state
+                    .selected_mods
+                    .extend(filtered_entries.iter().map(|e| e.mod_id.clone()));
+            }
+            if ui.button("Clear").clicked() {
+                view_state.selected_mods.clear();
+            }
+
+            ui.separator();
+
+            if ui
+                .add_enabled(
+                    !state.is_offline && !downloadable.is_empty(),
+                    egui::Button::new(format!("⬇ Download selected ({})", downloadable.len())),
+                )
+                .with_offline_hint(state.is_offline)
+                .clicked()
+            {
+                effects.extend(state.start_bulk_download(downloadable));
+            }
+
+            if ui
+                .button("📤 Export selected")
+                .on_hover_text("Export only the selected items as a new list")
+                .clicked()
+                && let Some(list) = state.get_current_list()
+                && let Some(save_path) =
+                    Dialogs::save_export_list_file(&format!("{} (subset)", list.name))
+            {
+                effects.extend(Self::start_export(
+                    state,
+                    view_state,
+                    save_path,
+                    Some(selected.clone()),
+                ));
+            }
+
+            if ui.button("📁 Archive selected").clicked() {
+                effects.extend(state.set_mods_archived(&selected, true));
+            }
+
+            if ui.button("📂 Unarchive selected").clicked() {
+                effects.extend(state.set_mods_archived(&selected, false));
+            }
+
+            let delete_button = ui.add_enabled(
+                kept_count < selected.len(),
+                egui::Button::new("🗑 Delete selected"),
+            );
+            let mut kept = Vec::new();
+            if protected_count > 0 {
+                kept.push(format!(
+                    "{protected_count} selected item(s) are required by other items"
+                ));
+            }
+            if locked_count > 0 {
+                kept.push(format!("{locked_count} selected item(s) are locked"));
+            }
+            let delete_button = if kept.is_empty() {
+                delete_button
+            } else {
+                delete_button.on_hover_text(format!("{} and will be kept", kept.join(", ")))
+            };
+            if delete_button.clicked() {
+                effects.extend(state.delete_mods(&selected));
+                view_state
+                    .selected_mods
+                    .retain(|id| !deletable.contains(id));
+            }
+        });
+    }
+
+    // Asks before replacing an existing file unless a choice was remembered this session
+    fn start_export(
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        path: std::path::PathBuf,
+        subset: Option<Vec<String>>,
+    ) -> Vec<Effect> {
+        if !path.exists() {
+            state.export_current_list(path, OverwritePolicy::Overwrite, subset.as_deref())
+        } else if let Some(policy) = view_state.export_overwrite_choice {
+            state.export_current_list(path, policy, subset.as_deref())
+        } else {
+            view_state.pending_export = Some((path, subset));
+            Vec::new()
+        }
     }
 
     fn render_mod_entry(
         ui: &mut egui::Ui,
-        project_type: ProjectType,
         entry: &ModEntry,
+        conflicts: &HashMap<String, Vec<String>>,
         state: &mut AppState,
+        view_state: &mut ViewState,
         runtime: &mut AppRuntime,
         effects: &mut Vec<Effect>,
     ) {
         let mod_id = &entry.mod_id;
+        let project_type = state.get_current_list_type();
+        let conflict_hint = conflicts
+            .get(mod_id)
+            .map(|names| format!("Conflicts with {}", names.join(", ")));
 
         effects.extend(state.load_mod_details_if_needed(mod_id));
 
@@ -389,7 +1015,20 @@ impl MainPanel {
         let is_missing = !entry.archived && !state.is_mod_downloaded(mod_id);
         let is_updateable = !entry.archived && state.is_mod_updateable(mod_id);
 
-        ui.horizontal(|ui| {
+        // Loading rows reserve the height of a loaded one so the rows below don't shift
+        let height_id = egui::Id::new(("mod_row_height", mod_id));
+        let reserved_height = ui.data(|d| {
+            d.get_temp::<f32>(height_id)
+                .or_else(|| d.get_temp::<f32>(egui::Id::new("mod_row_loaded_height")))
+        });
+
+        let row = ui.horizontal(|ui| {
+            if mod_info.is_none()
+                && let Some(height) = reserved_height
+            {
+                ui.set_min_height(height);
+            }
+
             if let Some(ref info) = mod_info {
                 if !info.icon_url.is_empty() {
                     if let Some(handle) = runtime.icon_service.get(&info.icon_url) {
@@ -415,7 +1054,8 @@ impl MainPanel {
                     name_text = name_text.weak();
                 }
 
-                let project_link = runtime.get_project_link(&project_type, &entry.mod_id);
+                let project_link =
+                    runtime.get_project_link(entry.provider, &project_type, &entry.mod_id);
                 ui.hyperlink_to(name_text, project_link);
 
                 if let Some(ref info) = mod_info {
@@ -424,23 +1064,113 @@ impl MainPanel {
                     } else {
                         format!("v{}", info.version)
                     };
-                    ui.label(format!("{} by {}", version_text, info.author));
+                    ui.horizontal(|ui| {
+                        if entry.locked {
+                            ui.label("🔒")
+                                .on_hover_text("Locked, skipped by bulk updates and deletes");
+                        }
+                        if entry.pinned_version.is_some() {
+                            ui.label("📌").on_hover_text("Pinned to this version");
+                        }
+                        ui.label(format!("{} by {}", version_text, info.author));
+                        Self::render_channel_badge(ui, info.channel);
+                        if state.stale_mods.contains(mod_id) {
+                            ui.label(egui::RichText::new("🕓 Cached").weak())
+                                .on_hover_text("Last known data, may be out of date");
+                        }
+                        if !entry.archived && entry.pinned_version.is_none() {
+                            Self::render_version_picker(ui, info, state, effects);
+                        }
+                    });
+
+                    if !info.version_id.is_empty() {
+                        egui::CollapsingHeader::new("📋 Changelog")
+                            .id_salt(("changelog", mod_id))
+                            .show(ui, |ui| {
+                                effects.extend(state.load_changelog_if_needed(info));
+                                match state.changelogs.get(&info.version_id) {
+                                    Some(Some(changelog)) => {
+                                        egui::ScrollArea::vertical()
+                                            .id_salt(("changelog_scroll", mod_id))
+                                            .max_height(200.0)
+                                            .show(ui, |ui| {
+                                                ui.label(changelog);
+                                            });
+                                    }
+                                    Some(None) => {
+                                        ui.weak("No changelog provided for this version");
+                                    }
+                                    None => {
+                                        ui.horizontal(|ui| {
+                                            ui.spinner();
+                                            ui.label("Loading changelog...");
+                                        });
+                                    }
+                                }
+                            });
+                    }
+
+                    egui::CollapsingHeader::new("📝 Filename")
+                        .id_salt(("filename", mod_id))
+                        .show(ui, |ui| {
+                            Self::render_filename_override(
+                                ui,
+                                project_type,
+                                entry,
+                                info,
+                                state,
+                                effects,
+                            );
+                        });
                 } else if is_loading {
                     ui.label("⏳ Loading details...");
-                } else if has_failed
-                    && ui
-                        .button(
+                } else if has_failed {
+                    ui.horizontal(|ui| {
+                        let mut button = ui.button(
                             egui::RichText::new("⚠ Failed to load").color(egui::Color32::YELLOW),
-                        )
-                        .clicked()
-                {
-                    effects.extend(state.force_reload_mod(mod_id));
+                        );
+                        if let Some(error) = state.last_errors.get(mod_id) {
+                            button = button.on_hover_text(format!("{error}\nClick to retry"));
+                        }
+                        if button.clicked() {
+                            effects.extend(state.force_reload_mod(mod_id));
+                        }
+                        Self::render_copy_diagnostics(ui, state, mod_id);
+                    });
                 }
 
                 let has_override = state.has_compatibility_override(mod_id);
                 let raw_compatibility = state.is_mod_compatible_raw(mod_id);
 
                 ui.horizontal(|ui| {
+                    if entry.added_as_dependency {
+                        ui.label(egui::RichText::new("🔗 Dependency").weak())
+                            .on_hover_text("Pulled in as a required dependency");
+                        ui.add_space(3.0);
+                    }
+                    if project_type == ProjectType::Mod
+                        && let Some(info) = &mod_info
+                        && info.environment != ModEnvironment::Unknown
+                    {
+                        let environment = info.environment;
+                        if state.is_skipped_in_server_mode(mod_id) {
+                            ui.colored_label(
+                                egui::Color32::GRAY,
+                                format!("{} Client only, skipped", environment.emoji()),
+                            )
+                            .on_hover_text("This list is in server mode");
+                        } else {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} {}",
+                                    environment.emoji(),
+                                    environment.display_name()
+                                ))
+                                .weak(),
+                            );
+                        }
+                        ui.add_space(3.0);
+                    }
                     if is_updateable {
                         ui.colored_label(
                             egui::Color32::from_rgb(100, 200, 255),
@@ -464,18 +1194,78 @@ impl MainPanel {
                         });
                     } else if matches!(raw_compatibility, Some(false)) {
                         ui.horizontal(|ui| {
-                            ui.colored_label(egui::Color32::RED, "❌ Incompatible");
+                            if state.is_newly_incompatible(mod_id) {
+                                ui.colored_label(egui::Color32::RED, "❌ No longer compatible")
+                                    .on_hover_text("Worked with the list this was duplicated from");
+                            } else {
+                                ui.colored_label(egui::Color32::RED, "❌ Incompatible");
+                            }
                             if ui.small_button("🔒 Overrule").clicked() {
                                 effects.extend(state.toggle_compatibility_override(mod_id));
                             }
+                            if ui
+                                .add_enabled(
+                                    !state.is_offline,
+                                    egui::Button::new("🔍 Alternatives").small(),
+                                )
+                                .on_hover_text("Find compatible projects with a similar name")
+                                .clicked()
+                            {
+                                effects.extend(state.find_alternatives(entry));
+                            }
                         });
                     }
                 });
+
+                if entry.disabled {
+                    ui.label(egui::RichText::new("⏸ Disabled").weak());
+                }
+
+                if let Some(hint) = &conflict_hint {
+                    egui::Frame::new()
+                        .fill(egui::Color32::from_rgb(90, 20, 20))
+                        .corner_radius(4.0)
+                        .inner_margin(egui::Margin::symmetric(6, 2))
+                        .show(ui, |ui| {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(255, 120, 120),
+                                format!("⚠ {hint}"),
+                            );
+                        });
+                }
             });
 
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                if ui.button("🗑").clicked() {
-                    effects.extend(state.delete_mod(mod_id));
+                let dependents = state.get_dependents(mod_id);
+                let delete_button = ui.add_enabled(dependents.is_empty(), egui::Button::new("🗑"));
+                if delete_button
+                    .on_disabled_hover_text(format!(
+                        "Required by {}",
+                        dependents
+                            .iter()
+                            .map(|(_, name)| name.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ))
+                    .clicked()
+                {
+                    if entry.locked {
+                        view_state.confirm_locked_action =
+                            Some((mod_id.clone(), LockedAction::Delete));
+                    } else {
+                        effects.extend(state.delete_mod(mod_id));
+                    }
+                }
+
+                let downloaded_file = state.get_downloaded_file(mod_id);
+                if ui
+                    .add_enabled(downloaded_file.is_some(), egui::Button::new("📂"))
+                    .on_hover_text("Show file in folder")
+                    .on_disabled_hover_text("Not downloaded yet")
+                    .clicked()
+                    && let Some(path) = downloaded_file
+                {
+                    explorer::reveal_file(&path);
                 }
 
                 let archive_text = if entry.archived {
@@ -487,6 +1277,46 @@ impl MainPanel {
                     effects.extend(state.toggle_archive_mod(mod_id));
                 }
 
+                let lock_hint = if entry.locked {
+                    "Unlock, bulk updates and deletes include it again"
+                } else {
+                    "Lock, bulk updates and deletes skip it"
+                };
+                if ui
+                    .add(egui::Button::new("🔒").selected(entry.locked))
+                    .on_hover_text(lock_hint)
+                    .clicked()
+                {
+                    effects.extend(state.set_mod_locked(mod_id, !entry.locked));
+                }
+
+                if !entry.archived {
+                    let is_pinned = entry.pinned_version.is_some();
+                    let can_pin = is_pinned
+                        || mod_info
+                            .as_ref()
+                            .is_some_and(|info| !info.version_id.is_empty());
+                    let pin_button = ui
+                        .add_enabled(can_pin, egui::Button::new("📌").selected(is_pinned))
+                        .on_hover_text(if is_pinned {
+                            "Unpin version"
+                        } else {
+                            "Pin current version"
+                        });
+                    if pin_button.clicked() {
+                        effects.extend(state.toggle_pin_version(mod_id));
+                    }
+
+                    let (toggle_text, toggle_hint) = if entry.disabled {
+                        ("▶", "Enable mod, renames the file back to .jar")
+                    } else {
+                        ("⏸", "Disable mod, renames the file to .disabled")
+                    };
+                    if ui.button(toggle_text).on_hover_text(toggle_hint).clicked() {
+                        effects.extend(state.set_mod_enabled(mod_id, entry.disabled));
+                    }
+                }
+
                 if !entry.archived {
                     let status = state
                         .download_status
@@ -507,26 +1337,78 @@ impl MainPanel {
                                     .desired_width(80.0),
                             );
                         }
+                        DownloadStatus::Retrying {
+                            attempt,
+                            max_attempts,
+                        } => {
+                            ui.label(format!("Retrying {attempt}/{max_attempts}..."));
+                            ui.spinner();
+                        }
                         any => {
-                            let enabled =
-                                mod_info.is_some() && !matches!(compatibility, Some(false));
+                            let enabled = mod_info.is_some()
+                                && !state.is_offline
+                                && !matches!(compatibility, Some(false))
+                                && !matches!(state.chosen_versions.get(mod_id), Some((_, None)))
+                                && !state.is_skipped_in_server_mode(mod_id);
                             let button_text = if is_updateable {
                                 "🔄 Update"
                             } else {
                                 "Download"
                             };
-                            if ui
+                            let mut response = ui
                                 .add_enabled(enabled, egui::Button::new(button_text))
-                                .clicked()
-                            {
-                                effects.extend(state.start_download(mod_id));
+                                .with_offline_hint(state.is_offline);
+                            if let Some(hint) = &conflict_hint {
+                                response = response.on_hover_text(format!(
+                                    "⚠ {hint}, installing both may crash the game"
+                                ));
+                            }
+                            if response.clicked() {
+                                // Replacing the file of a locked entry needs a confirmation
+                                if entry.locked && is_downloaded {
+                                    view_state.confirm_locked_action =
+                                        Some((mod_id.clone(), LockedAction::Update));
+                                } else {
+                                    effects.extend(state.start_download(mod_id));
+                                }
                             }
                             if (any == DownloadStatus::Complete || is_downloaded) && !is_updateable
                             {
                                 ui.label("✅");
+                                if let Some(file) = state.deduped_downloads.get(mod_id) {
+                                    ui.label(egui::RichText::new("deduped").small().weak())
+                                        .on_hover_text(format!(
+                                            "An identical file was already present as {file}, \
+                                             nothing was downloaded"
+                                        ));
+                                }
+                            }
+                            if let Some(requirement) = state.jar_version_mismatches.get(mod_id) {
+                                ui.colored_label(egui::Color32::YELLOW, "⚠").on_hover_text(
+                                    format!(
+                                        "The downloaded jar declares Minecraft {requirement}, \
+                                         which doesn't include {}",
+                                        state.get_effective_version()
+                                    ),
+                                );
+                            }
+                            if let Some(declared) = state.pack_format_mismatches.get(mod_id) {
+                                ui.colored_label(egui::Color32::YELLOW, "⚠").on_hover_text(
+                                    format!(
+                                        "This resource pack declares {declared}, \
+                                         which doesn't match Minecraft {}",
+                                        state.get_effective_version()
+                                    ),
+                                );
                             }
-                            if any == DownloadStatus::Failed {
-                                ui.colored_label(egui::Color32::RED, "❌");
+                            if let DownloadStatus::Failed(failure) = any {
+                                let reason = match state.last_errors.get(mod_id) {
+                                    Some(error) => format!("{}\n{error}", failure.reason()),
+                                    None => failure.reason().to_string(),
+                                };
+                                ui.colored_label(egui::Color32::RED, "❌")
+                                    .on_hover_text(format!("Download failed: {reason}"));
+                                Self::render_copy_diagnostics(ui, state, mod_id);
                             }
                         }
                     }
@@ -534,9 +1416,130 @@ impl MainPanel {
             });
         });
 
+        if mod_info.is_some() {
+            // Expanded sections make rows taller, so the shortest loaded row is the general guess
+            let height = row.response.rect.height();
+            ui.data_mut(|d| {
+                d.insert_temp(height_id, height);
+                let loaded_id = egui::Id::new("mod_row_loaded_height");
+                let shortest = d
+                    .get_temp::<f32>(loaded_id)
+                    .map_or(height, |h| h.min(height));
+                d.insert_temp(loaded_id, shortest);
+            });
+        }
+
         ui.separator();
     }
 
+    fn render_copy_diagnostics(ui: &mut egui::Ui, state: &AppState, mod_id: &str) {
+        if ui
+            .small_button("📋")
+            .on_hover_text("Copy diagnostics for a bug report")
+            .clicked()
+            && let Some(report) = state.diagnostics_report(mod_id)
+        {
+            ui.ctx().copy_text(report);
+        }
+    }
+
+    fn handle_dropped_files(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let dropped: Vec<std::path::PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|f| f.path.clone())
+                .collect()
+        });
+
+        if dropped.is_empty() {
+            return Vec::new();
+        }
+
+        let extension = state.get_current_list_type().fileext().to_string();
+        let (accepted, rejected): (Vec<_>, Vec<_>) = dropped.into_iter().partition(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case(&extension))
+        });
+
+        view_state.rejected_dropped_files = rejected
+            .iter()
+            .filter_map(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .collect();
+
+        state.match_dropped_files(accepted)
+    }
+
+    fn render_filename_override(
+        ui: &mut egui::Ui,
+        project_type: ProjectType,
+        entry: &ModEntry,
+        info: &ModInfo,
+        state: &mut AppState,
+        effects: &mut Vec<Effect>,
+    ) {
+        // The edit buffer and validation error live in egui memory until applied
+        let input_id = ui.make_persistent_id(("filename_input", &entry.mod_id));
+        let error_id = ui.make_persistent_id(("filename_error", &entry.mod_id));
+        let mut input = ui
+            .data_mut(|d| d.get_temp::<String>(input_id))
+            .unwrap_or_else(|| entry.filename_override.clone().unwrap_or_default());
+        let mut error = ui.data_mut(|d| d.get_temp::<String>(error_id));
+
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut input)
+                    .hint_text(generate_mod_filename(info))
+                    .desired_width(220.0),
+            );
+            if response.changed() {
+                error = None;
+            }
+
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("✔ Apply").clicked() || submitted {
+                if input.trim().is_empty() {
+                    effects.extend(state.set_filename_override(&entry.mod_id, None));
+                } else {
+                    let taken = state.taken_filenames(&entry.mod_id);
+                    match validate_filename_override(&input, project_type, &taken) {
+                        Ok(name) => {
+                            input = name.clone();
+                            effects.extend(state.set_filename_override(&entry.mod_id, Some(name)));
+                        }
+                        Err(e) => error = Some(e),
+                    }
+                }
+            }
+
+            if entry.filename_override.is_some() && ui.button("↺ Reset").clicked() {
+                input.clear();
+                error = None;
+                effects.extend(state.set_filename_override(&entry.mod_id, None));
+            }
+        });
+
+        if let Some(error) = &error {
+            ui.colored_label(egui::Color32::LIGHT_RED, error);
+        } else if entry.filename_override.is_some() {
+            ui.weak("Used for the next download instead of the generated name");
+        }
+
+        ui.data_mut(|d| {
+            d.insert_temp(input_id, input);
+            match error {
+                Some(error) => d.insert_temp(error_id, error),
+                None => d.remove::<String>(error_id),
+            }
+        });
+    }
+
     fn render_unknown_mod_entry(
         ui: &mut egui::Ui,
         filename: &str,
@@ -577,6 +1580,48 @@ impl MainPanel {
         ui.separator();
     }
 
+    fn render_orphaned_file_entry(
+        ui: &mut egui::Ui,
+        mod_id: &str,
+        filename: &str,
+        state: &mut AppState,
+        effects: &mut Vec<Effect>,
+    ) {
+        ui.horizontal(|ui| {
+            let (rect, _response) =
+                ui.allocate_exact_size(egui::vec2(32.0, 32.0), egui::Sense::hover());
+
+            let text_pos = rect.center() + egui::vec2(4.0, -4.0);
+
+            ui.painter().text(
+                text_pos,
+                egui::Align2::CENTER_CENTER,
+                "🧹",
+                egui::FontId::proportional(24.0),
+                ui.style().visuals.text_color(),
+            );
+
+            ui.add_space(4.0);
+
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new(filename).weak());
+                ui.label(
+                    egui::RichText::new(format!("{mod_id} is no longer in this list"))
+                        .weak()
+                        .small(),
+                );
+            });
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("🗑").clicked() {
+                    effects.extend(state.clean_up_orphaned_files(&[mod_id.to_string()]));
+                }
+            });
+        });
+
+        ui.separator();
+    }
+
     fn show_sort_menu(ctx: &egui::Context, view_state: &mut ViewState) {
         let popup_pos = view_state.sort_btn_rect.left_bottom() + egui::vec2(0.0, 5.0);
 
@@ -648,6 +1693,17 @@ impl MainPanel {
                     {
                         view_state.sort_menu_open = false;
                     }
+                    if ui
+                        .selectable_value(
+                            &mut view_state.current_sort_mode,
+                            crate::app::SortMode::Manual,
+                            "Manual",
+                        )
+                        .on_hover_text("Drag entries to reorder them")
+                        .clicked()
+                    {
+                        view_state.sort_menu_open = false;
+                    }
 
                     ui.separator();
                     ui.label("Order:");
@@ -714,9 +1770,33 @@ impl MainPanel {
                     {
                         view_state.sort_menu_open = false;
                     }
+                    if ui
+                        .selectable_value(
+                            &mut view_state.current_filter_mode,
+                            crate::app::FilterMode::UpdatableOnly,
+                            "🔄 Updates",
+                        )
+                        .clicked()
+                    {
+                        view_state.sort_menu_open = false;
+                    }
                 });
 
                 view_state.sort_popup_rect = frame_response.response.rect;
             });
     }
 }
+
+trait OfflineHint {
+    fn with_offline_hint(self, is_offline: bool) -> Self;
+}
+
+impl OfflineHint for egui::Response {
+    fn with_offline_hint(self, is_offline: bool) -> Self {
+        if is_offline {
+            self.on_disabled_hover_text(OFFLINE_HINT)
+        } else {
+            self
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::app::{AppRuntime, AppState, Effect, ListAction};
-use crate::domain::ModList;
+use crate::domain::{Bundle, BundleFile, ModList, OverwritePolicy, fuzzy_score};
 use crate::ui::{ViewState, dialogs::Dialogs};
 use eframe::egui;
 
@@ -24,14 +24,14 @@ impl SidebarPanel {
 
             ui.add_space(4.0);
             ui.horizontal(|ui| {
-                let button_width = ui.available_width() - 35.0;
+                let button_width = ui.available_width() - 155.0;
                 if ui
                     .add_sized([button_width, 25.0], egui::Button::new("➕ New List"))
                     .clicked()
                 {
                     view_state.reset_create_list();
 
-                    if let Some(latest_version) = state.minecraft_versions.first() {
+                    if let Some(latest_version) = state.latest_release() {
                         view_state.new_list_version = latest_version.id.clone();
                     }
 
@@ -46,6 +46,44 @@ impl SidebarPanel {
                     view_state.create_list_window_open = true;
                 }
 
+                if ui
+                    .add_sized([25.0, 25.0], egui::Button::new("📦"))
+                    .on_hover_text("Create a bundle of lists")
+                    .clicked()
+                {
+                    view_state.open_bundle_editor(None);
+                }
+
+                if ui
+                    .add_sized([25.0, 25.0], egui::Button::new("🔗"))
+                    .on_hover_text("Import from share link")
+                    .clicked()
+                {
+                    view_state.share_link_input.clear();
+                    view_state.share_link_error = None;
+                    view_state.active_action = ListAction::ShareLink;
+                    view_state.import_window_open = true;
+                }
+
+                if ui
+                    .add_sized([25.0, 25.0], egui::Button::new("📚"))
+                    .on_hover_text("Import Modrinth collection")
+                    .clicked()
+                {
+                    view_state.collection_input.clear();
+                    view_state.collection_error = None;
+                    view_state.collection_import_open = true;
+                }
+
+                if ui
+                    .add_sized([25.0, 25.0], egui::Button::new("📂"))
+                    .on_hover_text("Import from a folder of jars")
+                    .clicked()
+                    && let Some(path) = Dialogs::pick_folder()
+                {
+                    effects.extend(state.start_directory_import(path));
+                }
+
                 if ui
                     .add_sized([25.0, 25.0], egui::Button::new("📥"))
                     .on_hover_text("Import")
@@ -54,18 +92,44 @@ impl SidebarPanel {
                 {
                     match path.extension().and_then(|s| s.to_str()) {
                         Some("toml") | Some("mmd") => {
-                            if let Ok(content) = std::fs::read_to_string(&path)
-                                && let Ok(list) = toml::from_str::<ModList>(&content)
-                            {
-                                view_state.import_name_input = format!("{} (Imported)", list.name);
-                                view_state.pending_import_list = Some(list);
-                                view_state.active_action = ListAction::Import;
-                                view_state.import_window_open = true;
+                            let content = match std::fs::read_to_string(&path) {
+                                Ok(content) => content,
+                                Err(e) => {
+                                    state.report_legacy_failure(
+                                        format!("Failed to read {}: {e}", path.display()),
+                                        true,
+                                    );
+                                    return;
+                                }
+                            };
+                            match toml::from_str::<ModList>(&content) {
+                                Ok(mut list) => {
+                                    list.drop_invalid_filename_overrides();
+                                    view_state.import_name_input =
+                                        format!("{} (Imported)", list.name);
+                                    view_state.pending_import_list = Some(list);
+                                    view_state.active_action = ListAction::Import;
+                                    view_state.import_window_open = true;
+                                }
+                                Err(list_error) => match toml::from_str::<BundleFile>(&content) {
+                                    Ok(bundle) => effects.extend(state.import_bundle(bundle)),
+                                    // Report the list error, lists are the far more common file
+                                    Err(_) => state.report_legacy_failure(
+                                        format!("Not a valid list or bundle: {list_error}"),
+                                        true,
+                                    ),
+                                },
                             }
                         }
                         Some("mods") | Some("all-mods") | Some("queue-mods") => {
                             effects.extend(state.start_legacy_import(path));
                         }
+                        // Lockfiles are installed as-is instead of becoming a list
+                        Some("json") => {
+                            if let Some(dir) = Dialogs::pick_folder() {
+                                effects.extend(state.install_lockfile(path, dir));
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -74,60 +138,289 @@ impl SidebarPanel {
             ui.add_space(4.0);
             ui.separator();
 
-            let list_info: Vec<(String, String, bool)> = state
+            let mut all_tags: Vec<&String> =
+                state.mod_lists.iter().flat_map(|list| &list.tags).collect();
+            all_tags.sort_by_key(|tag| tag.to_lowercase());
+            all_tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+
+            if view_state
+                .list_tag_filter
+                .as_ref()
+                .is_some_and(|filter| !all_tags.iter().any(|t| t.eq_ignore_ascii_case(filter)))
+            {
+                view_state.list_tag_filter = None;
+            }
+
+            if !all_tags.is_empty() {
+                egui::ComboBox::from_id_salt("list_tag_filter")
+                    .width(ui.available_width())
+                    .selected_text(match &view_state.list_tag_filter {
+                        Some(tag) => format!("🏷 {tag}"),
+                        None => "🏷 All tags".to_string(),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut view_state.list_tag_filter, None, "🏷 All tags");
+                        for tag in &all_tags {
+                            ui.selectable_value(
+                                &mut view_state.list_tag_filter,
+                                Some(tag.to_string()),
+                                tag.as_str(),
+                            );
+                        }
+                    });
+                ui.add_space(4.0);
+            }
+
+            let query = view_state.list_search_query.trim();
+            let mut matching_lists: Vec<(i64, &ModList)> = state
                 .mod_lists
                 .iter()
-                .filter(|list| {
-                    view_state.list_search_query.is_empty()
-                        || list
-                            .name
-                            .to_lowercase()
-                            .contains(&view_state.list_search_query.to_lowercase())
+                .filter(|list| match &view_state.list_tag_filter {
+                    Some(filter) => list.tags.iter().any(|t| t.eq_ignore_ascii_case(filter)),
+                    None => true,
                 })
-                .map(|list| {
-                    let type_icon = list.content_type.emoji();
-                    let display_text = if list.version.is_empty() && list.loader.id.is_empty() {
-                        format!("{} {} ({})", type_icon, list.name, list.mods.len())
-                    } else {
-                        format!(
-                            "{} {} [{} | {}] ({})",
-                            type_icon,
-                            list.name,
-                            list.version,
-                            if list.loader.name.is_empty() {
-                                &list.loader.id
-                            } else {
-                                &list.loader.name
-                            },
-                            list.mods.len()
-                        )
-                    };
-                    (
-                        list.id.clone(),
-                        display_text,
-                        state.current_list_id.as_ref() == Some(&list.id),
-                    )
+                .filter_map(|list| {
+                    std::iter::once(&list.name)
+                        .chain(&list.tags)
+                        .filter_map(|text| fuzzy_score(query, text))
+                        .max()
+                        .map(|s| (s, list))
                 })
                 .collect();
+            // Favorites come first, also among search results
+            if query.is_empty() {
+                matching_lists.sort_by_key(|(_, list)| !list.is_favorite);
+            } else {
+                matching_lists
+                    .sort_by_key(|(score, list)| (!list.is_favorite, std::cmp::Reverse(*score)));
+            }
+
+            // Grouping only makes sense while browsing, search results stay ranked
+            let grouped =
+                query.is_empty() && matching_lists.iter().any(|(_, l)| !l.tags.is_empty());
+
+            let list_info: Vec<ListRow> = matching_lists
+                .into_iter()
+                .map(|(_, list)| ListRow::new(list, state))
+                .collect();
+            let show_bundles = query.is_empty() && !state.bundles.is_empty();
 
             egui::ScrollArea::vertical().show(ui, |ui| {
-                for (list_id, display_text, selected) in list_info {
-                    if ui.selectable_label(selected, display_text).clicked() {
-                        if selected {
-                            state.current_list_id = None;
-                        } else {
-                            state.current_list_id = Some(list_id);
-                            effects.extend(state.invalidate_and_reload());
-
-                            let download_dir = state.get_effective_download_dir();
-                            effects.push(Effect::ValidateMetadata { download_dir });
-                        }
-                        view_state.selected_mod = None;
+                if show_bundles {
+                    Self::render_bundles(ui, state, view_state, &mut effects);
+                    ui.separator();
+                }
+
+                if !grouped {
+                    for row in list_info {
+                        Self::render_list_row(ui, state, view_state, &mut effects, row);
                     }
+                    return;
+                }
+
+                // Favorites keep their own section above the tag groups
+                let (favorites, list_info): (Vec<ListRow>, Vec<ListRow>) =
+                    list_info.into_iter().partition(|row| row.favorite);
+                if !favorites.is_empty() {
+                    egui::CollapsingHeader::new(format!("⭐ Favorites ({})", favorites.len()))
+                        .id_salt("list_group_favorites")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for row in favorites {
+                                Self::render_list_row(ui, state, view_state, &mut effects, row);
+                            }
+                        });
+                }
+
+                let mut groups: Vec<(Option<String>, Vec<ListRow>)> = Vec::new();
+                for row in list_info {
+                    let key = row.group.clone();
+                    match groups.iter_mut().find(|(group, _)| match (group, &key) {
+                        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                        (None, None) => true,
+                        _ => false,
+                    }) {
+                        Some((_, rows)) => rows.push(row),
+                        None => groups.push((key, vec![row])),
+                    }
+                }
+                groups.sort_by_key(|(group, _)| {
+                    (group.is_none(), group.as_deref().map(str::to_lowercase))
+                });
+
+                for (group, rows) in groups {
+                    let title = format!(
+                        "{} ({})",
+                        group.as_deref().unwrap_or("Untagged"),
+                        rows.len()
+                    );
+                    egui::CollapsingHeader::new(title)
+                        .id_salt(("list_group", group.as_deref().map(str::to_lowercase)))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for row in rows {
+                                Self::render_list_row(ui, state, view_state, &mut effects, row);
+                            }
+                        });
                 }
             });
         });
 
         effects
     }
+
+    fn render_bundles(
+        ui: &mut egui::Ui,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        effects: &mut Vec<Effect>,
+    ) {
+        for bundle in state.bundles.clone() {
+            let rows: Vec<ListRow> = state
+                .bundle_lists(&bundle)
+                .into_iter()
+                .map(|list| ListRow::new(list, state))
+                .collect();
+
+            egui::CollapsingHeader::new(format!(
+                "📦 {} [{}] ({})",
+                bundle.name,
+                bundle.version,
+                rows.len()
+            ))
+            .id_salt(("bundle", &bundle.id))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    Self::render_bundle_actions(ui, state, view_state, effects, &bundle);
+                });
+                for row in rows {
+                    Self::render_list_row(ui, state, view_state, effects, row);
+                }
+            });
+        }
+    }
+
+    fn render_bundle_actions(
+        ui: &mut egui::Ui,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        effects: &mut Vec<Effect>,
+        bundle: &Bundle,
+    ) {
+        if ui
+            .add_enabled(!state.is_offline, egui::Button::new("⬇").small())
+            .on_hover_text("Download every list in the bundle into its own folder")
+            .clicked()
+        {
+            effects.extend(state.download_bundle(&bundle.id));
+        }
+        if ui
+            .small_button("📤")
+            .on_hover_text("Export as one .mmd or .mrpack")
+            .clicked()
+            && let Some(path) = Dialogs::save_export_bundle_file(&bundle.name)
+        {
+            // The save dialog already confirmed replacing, keep a backup unless told otherwise
+            let overwrite = if path.exists() {
+                view_state
+                    .export_overwrite_choice
+                    .unwrap_or(OverwritePolicy::Backup)
+            } else {
+                OverwritePolicy::Overwrite
+            };
+            effects.extend(state.export_bundle(&bundle.id, path, overwrite));
+        }
+        if ui.small_button("✏").on_hover_text("Edit bundle").clicked() {
+            view_state.open_bundle_editor(Some(bundle));
+        }
+        if ui
+            .small_button("🗑")
+            .on_hover_text("Delete the bundle, its lists are kept")
+            .clicked()
+        {
+            effects.extend(state.delete_bundle(&bundle.id));
+        }
+    }
+
+    fn render_list_row(
+        ui: &mut egui::Ui,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+        effects: &mut Vec<Effect>,
+        row: ListRow,
+    ) {
+        let mut clicked = false;
+        ui.horizontal(|ui| {
+            // A dimmed star marks lists that aren't favorites yet
+            let (star, hint) = if row.favorite {
+                (egui::RichText::new("⭐"), "Remove from favorites")
+            } else {
+                (
+                    egui::RichText::new("⭐").weak(),
+                    "Pin to the top as a favorite",
+                )
+            };
+            if ui.small_button(star).on_hover_text(hint).clicked() {
+                effects.extend(state.toggle_list_favorite(&row.id));
+            }
+
+            let mut response = ui.selectable_label(row.selected, &row.display_text);
+            if !row.notes.is_empty() {
+                response = response.on_hover_text(&row.notes);
+            }
+            clicked = response.clicked();
+        });
+        if clicked {
+            if row.selected {
+                state.current_list_id = None;
+            } else {
+                state.current_list_id = Some(row.id);
+                effects.extend(state.invalidate_and_reload());
+
+                let download_dir = state.get_effective_download_dir();
+                effects.push(Effect::ValidateMetadata { download_dir });
+            }
+            view_state.selected_mod = None;
+            view_state.selected_mods.clear();
+        }
+    }
+}
+
+struct ListRow {
+    id: String,
+    display_text: String,
+    notes: String,
+    group: Option<String>,
+    selected: bool,
+    favorite: bool,
+}
+
+impl ListRow {
+    fn new(list: &ModList, state: &AppState) -> Self {
+        let type_icon = list.content_type.emoji();
+        let display_text = if list.version.is_empty() && list.loader.id.is_empty() {
+            format!("{} {} ({})", type_icon, list.name, list.mods.len())
+        } else {
+            format!(
+                "{} {} [{} | {}] ({})",
+                type_icon,
+                list.name,
+                list.version,
+                if list.loader.name.is_empty() {
+                    &list.loader.id
+                } else {
+                    &list.loader.name
+                },
+                list.mods.len()
+            )
+        };
+        ListRow {
+            id: list.id.clone(),
+            display_text,
+            notes: list.notes.clone(),
+            group: list.tags.first().cloned(),
+            selected: state.current_list_id.as_ref() == Some(&list.id),
+            favorite: list.is_favorite,
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use crate::app::{AppRuntime, Effect};
+use crate::app::{AppRuntime, AppState, Effect, FilterMode};
 use crate::ui::ViewState;
 use eframe::egui;
 
@@ -7,10 +7,11 @@ pub struct TopPanel;
 impl TopPanel {
     pub fn show(
         ctx: &egui::Context,
+        state: &mut AppState,
         view_state: &mut ViewState,
         _runtime: &mut AppRuntime,
     ) -> Vec<Effect> {
-        let effects = Vec::new();
+        let mut effects = Vec::new();
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
@@ -21,6 +22,28 @@ impl TopPanel {
                         view_state.settings_window_open = true;
                     }
 
+                    if ui.button("📜 History").clicked() {
+                        view_state.download_history_open = true;
+                    }
+
+                    let update_count = state.available_update_count();
+                    if update_count > 0
+                        && ui
+                            .button(
+                                egui::RichText::new(format!("🔔 {update_count}"))
+                                    .color(egui::Color32::GOLD),
+                            )
+                            .on_hover_text(format!(
+                                "{update_count} installed item(s) have a newer version"
+                            ))
+                            .clicked()
+                    {
+                        effects.extend(state.show_available_updates());
+                        view_state.current_filter_mode = FilterMode::UpdatableOnly;
+                        view_state.selected_mod = None;
+                        view_state.selected_mods.clear();
+                    }
+
                     /*if let Some(list_id) = &state.current_list_id {
                         if let Some(current_list) = state.get_list_by_id(list_id) {
                             ui.separator();
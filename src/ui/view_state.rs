@@ -1,31 +1,77 @@
-use crate::app::{FilterMode, OrderMode, SortMode};
-use crate::domain::{ModInfo, ModList, ProjectType};
+use crate::app::{FilterMode, HistoryStatusFilter, OrderMode, SortMode};
+use crate::domain::{
+    Bundle, CacheTtl, CompatPolicy, DEFAULT_DOWNLOAD_RETRIES, DEFAULT_MAX_PARALLEL_DOWNLOADS,
+    DEFAULT_UPDATE_CHECK_HOURS, ModInfo, ModList, NetworkTimeouts, OverwritePolicy, ProjectType,
+    ProxySettings, ReleaseChannel, Theme,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LockedAction {
+    Update,
+    Delete,
+}
+
 pub struct ViewState {
     // List management UI state
     pub list_search_query: String,
+    // Only lists with this tag are shown in the sidebar
+    pub list_tag_filter: Option<String>,
     pub show_rename_input: bool,
     pub rename_list_input: String,
 
     // Mod search UI state
     pub search_query: String,
     pub selected_mod: Option<usize>,
+    pub selected_mods: HashSet<String>,
 
     // Window states
     pub search_window_open: bool,
+    pub search_focus_requested: bool,
+    pub rejected_dropped_files: Vec<String>,
     pub search_window_query: String,
+    pub search_selected_index: Option<usize>,
     pub is_searching: bool,
     pub settings_window_open: bool,
     pub import_window_open: bool,
     pub create_list_window_open: bool,
     pub list_settings_open: bool,
     pub legacy_import_settings_open: bool,
+    pub collection_import_open: bool,
+    pub bulk_add_open: bool,
+    pub bundle_window_open: bool,
+    pub download_history_open: bool,
+    // Closing was held back because downloads are running
+    pub quit_prompt_open: bool,
+    pub quit_when_done: bool,
+    pub quit_confirmed: bool,
 
     // Import/Export state
     pub import_name_input: String,
     pub active_action: crate::app::ListAction,
     pub pending_import_list: Option<ModList>,
+    pub import_duplicate_of: Option<String>,
+    pub share_link_input: String,
+    pub share_link_error: Option<String>,
+    pub collection_input: String,
+    pub bulk_add_input: String,
+    pub collection_error: Option<String>,
+
+    // Bundle editor inputs, no ID creates a new bundle
+    pub bundle_edit_id: Option<String>,
+    pub bundle_name_input: String,
+    pub bundle_version_input: String,
+    pub bundle_list_ids: Vec<String>,
+    // Export target that already exists, waiting for the overwrite prompt
+    // Target path and, for "Export selected", the entries to export
+    pub pending_export: Option<(PathBuf, Option<Vec<String>>)>,
+    pub export_dont_ask_again: bool,
+    // Set once the user opts out of the prompt, only for this session
+    pub export_overwrite_choice: Option<OverwritePolicy>,
+    pub duplicate_version: String,
+    pub duplicate_loader: String,
 
     // Sort and filter state
     pub sort_menu_open: bool,
@@ -36,14 +82,48 @@ pub struct ViewState {
     pub sort_popup_rect: egui::Rect,
     pub show_archived: bool,
     pub show_unknown_mods: bool,
+    pub show_orphaned_files: bool,
+    pub confirm_overrule_all: bool,
+    // A locked entry waiting for confirmation before it is changed
+    pub confirm_locked_action: Option<(String, LockedAction)>,
+    // Unknown files waiting for the bulk delete confirmation
+    pub confirm_delete_unknown: Option<Vec<String>>,
+    // Orphaned (mod id, file) pairs waiting for the clean up confirmation
+    pub confirm_clean_up_orphans: Option<Vec<(String, String)>>,
 
     // List settings inputs
     pub list_settings_version: String,
     pub list_settings_loader: String,
     pub list_settings_dir: String,
+    // The Minecraft mods folder the typed directory overlaps, with the input it was checked for
+    pub list_settings_mods_overlap: Option<(String, Option<PathBuf>)>,
+    pub list_settings_snapshots: Option<bool>,
+    pub list_settings_server_layout: Option<bool>,
+    pub list_settings_server_mode: Option<bool>,
+    pub list_settings_channel: Option<ReleaseChannel>,
+    pub list_settings_notes: Option<String>,
+    // Comma separated while editing
+    pub list_settings_tags: Option<String>,
+    pub list_settings_loader_version: Option<Option<String>>,
 
     // App settings inputs
     pub app_settings_default_name: String,
+    pub app_settings_curseforge_key: String,
+    pub app_settings_modrinth_url: String,
+    pub app_settings_modrinth_url_error: Option<String>,
+    pub app_settings_user_agent_contact: String,
+    pub app_settings_user_agent_error: Option<String>,
+    pub app_settings_proxy: ProxySettings,
+    pub app_settings_proxy_error: Option<String>,
+    pub app_settings_timeouts: NetworkTimeouts,
+    pub app_settings_max_downloads: usize,
+    pub app_settings_download_retries: u32,
+    pub app_settings_update_check_hours: u64,
+    pub app_settings_clean_replaced: bool,
+    pub app_settings_compat_policy: CompatPolicy,
+    pub app_settings_cache_ttl: CacheTtl,
+    pub app_settings_default_dirs: HashMap<ProjectType, String>,
+    pub app_settings_theme: Theme,
 
     // Create list inputs
     pub new_list_name: String,
@@ -51,6 +131,8 @@ pub struct ViewState {
     pub new_list_version: String,
     pub new_list_loader: String,
     pub new_list_dir: String,
+    // Set once the folder got its default, clearing the field afterwards is intentional
+    pub new_list_dir_prefilled: bool,
 
     // Legacy import/export
     pub legacy_import_version: String,
@@ -58,27 +140,60 @@ pub struct ViewState {
     pub legacy_import_dir: String,
     pub legacy_import_mods: Option<Vec<Arc<ModInfo>>>,
     pub legacy_import_name: String,
+    pub legacy_import_preview: Option<Vec<String>>,
+    pub legacy_import_new_entry: String,
+
+    // Download history filters
+    pub history_list_filter: Option<String>,
+    pub history_status_filter: HistoryStatusFilter,
 }
 
 impl Default for ViewState {
     fn default() -> Self {
         Self {
             list_search_query: String::new(),
+            list_tag_filter: None,
             show_rename_input: false,
             rename_list_input: String::new(),
             search_query: String::new(),
             selected_mod: None,
+            selected_mods: HashSet::new(),
             search_window_open: false,
+            search_focus_requested: false,
+            rejected_dropped_files: Vec::new(),
             search_window_query: String::new(),
+            search_selected_index: None,
             is_searching: false,
             settings_window_open: false,
             import_window_open: false,
             create_list_window_open: false,
             list_settings_open: false,
             legacy_import_settings_open: false,
+            collection_import_open: false,
+            bulk_add_open: false,
+            bundle_window_open: false,
+            download_history_open: false,
+            quit_prompt_open: false,
+            quit_when_done: false,
+            quit_confirmed: false,
             import_name_input: String::new(),
             active_action: Default::default(),
             pending_import_list: None,
+            import_duplicate_of: None,
+            share_link_input: String::new(),
+            share_link_error: None,
+            collection_input: String::new(),
+            bulk_add_input: String::new(),
+            collection_error: None,
+            bundle_edit_id: None,
+            bundle_name_input: String::new(),
+            bundle_version_input: String::new(),
+            bundle_list_ids: Vec::new(),
+            pending_export: None,
+            export_dont_ask_again: false,
+            export_overwrite_choice: None,
+            duplicate_version: String::new(),
+            duplicate_loader: String::new(),
             sort_menu_open: false,
             current_sort_mode: SortMode::default(),
             current_filter_mode: FilterMode::default(),
@@ -86,21 +201,55 @@ impl Default for ViewState {
             sort_btn_rect: egui::Rect::NOTHING,
             sort_popup_rect: egui::Rect::NOTHING,
             show_archived: false,
+            confirm_delete_unknown: None,
+            confirm_clean_up_orphans: None,
+            confirm_overrule_all: false,
+            confirm_locked_action: None,
             show_unknown_mods: false,
+            show_orphaned_files: false,
             list_settings_version: String::new(),
             list_settings_loader: String::new(),
             list_settings_dir: String::new(),
+            list_settings_mods_overlap: None,
+            list_settings_snapshots: None,
+            list_settings_server_layout: None,
+            list_settings_server_mode: None,
+            list_settings_channel: None,
+            list_settings_notes: None,
+            list_settings_tags: None,
+            list_settings_loader_version: None,
             app_settings_default_name: String::new(),
+            app_settings_curseforge_key: String::new(),
+            app_settings_modrinth_url: String::new(),
+            app_settings_modrinth_url_error: None,
+            app_settings_user_agent_contact: String::new(),
+            app_settings_user_agent_error: None,
+            app_settings_proxy: ProxySettings::default(),
+            app_settings_proxy_error: None,
+            app_settings_timeouts: NetworkTimeouts::default(),
+            app_settings_max_downloads: DEFAULT_MAX_PARALLEL_DOWNLOADS,
+            app_settings_download_retries: DEFAULT_DOWNLOAD_RETRIES,
+            app_settings_update_check_hours: DEFAULT_UPDATE_CHECK_HOURS,
+            app_settings_clean_replaced: false,
+            app_settings_compat_policy: CompatPolicy::default(),
+            app_settings_cache_ttl: CacheTtl::default(),
+            app_settings_default_dirs: HashMap::new(),
+            app_settings_theme: Theme::default(),
             new_list_name: String::new(),
             new_list_type: ProjectType::default(),
             new_list_version: String::new(),
             new_list_loader: String::new(),
             new_list_dir: String::new(),
+            new_list_dir_prefilled: false,
             legacy_import_version: String::new(),
             legacy_import_loader: String::new(),
             legacy_import_dir: String::new(),
             legacy_import_mods: None,
             legacy_import_name: String::new(),
+            legacy_import_preview: None,
+            legacy_import_new_entry: String::new(),
+            history_list_filter: None,
+            history_status_filter: HistoryStatusFilter::All,
         }
     }
 }
@@ -114,12 +263,31 @@ impl ViewState {
         self.list_settings_open = false;
         self.create_list_window_open = false;
         self.legacy_import_settings_open = false;
+        self.collection_import_open = false;
+        self.bulk_add_open = false;
+        self.bundle_window_open = false;
+    }
+
+    pub fn open_bundle_editor(&mut self, bundle: Option<&Bundle>) {
+        self.bundle_edit_id = bundle.map(|b| b.id.clone());
+        self.bundle_name_input = bundle.map(|b| b.name.clone()).unwrap_or_default();
+        self.bundle_version_input = bundle.map(|b| b.version.clone()).unwrap_or_default();
+        self.bundle_list_ids = bundle.map(|b| b.list_ids.clone()).unwrap_or_default();
+        self.bundle_window_open = true;
     }
 
     pub fn reset_list_settings(&mut self) {
         self.list_settings_version.clear();
         self.list_settings_loader.clear();
         self.list_settings_dir.clear();
+        self.list_settings_mods_overlap = None;
+        self.list_settings_snapshots = None;
+        self.list_settings_server_layout = None;
+        self.list_settings_server_mode = None;
+        self.list_settings_channel = None;
+        self.list_settings_notes = None;
+        self.list_settings_tags = None;
+        self.list_settings_loader_version = None;
     }
 
     pub fn reset_create_list(&mut self) {
@@ -128,6 +296,7 @@ impl ViewState {
         self.new_list_version.clear();
         self.new_list_loader.clear();
         self.new_list_dir.clear();
+        self.new_list_dir_prefilled = false;
     }
 
     pub fn reset_legacy_import(&mut self) {
@@ -136,5 +305,7 @@ impl ViewState {
         self.legacy_import_dir.clear();
         self.legacy_import_mods = None;
         self.legacy_import_name.clear();
+        self.legacy_import_preview = None;
+        self.legacy_import_new_entry.clear();
     }
 }
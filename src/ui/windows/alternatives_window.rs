@@ -0,0 +1,114 @@
+use crate::app::{AppRuntime, AppState, Effect};
+use crate::domain::format_count;
+use eframe::egui;
+
+pub struct AlternativesWindow;
+
+impl AlternativesWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        runtime: &mut AppRuntime,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some(alternatives) = &state.alternatives else {
+            return effects;
+        };
+        let mod_id = alternatives.mod_id.clone();
+        let results = alternatives.results.clone();
+
+        let mut is_open = true;
+        let mut replacement = None;
+
+        egui::Window::new(format!("🔍 Alternatives to {}", alternatives.mod_name))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                let Some(results) = results else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Searching for compatible projects...");
+                    });
+                    return;
+                };
+
+                if results.is_empty() {
+                    ui.label("No compatible alternatives found.");
+                    return;
+                }
+
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Compatible with {} | {}",
+                        state.get_effective_version(),
+                        state.get_effective_loader()
+                    ))
+                    .small()
+                    .weak(),
+                );
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for mod_info in &results {
+                            ui.horizontal(|ui| {
+                                if !mod_info.icon_url.is_empty()
+                                    && let Some(handle) =
+                                        runtime.icon_service.get(&mod_info.icon_url)
+                                {
+                                    ui.add(
+                                        egui::Image::from_texture(handle)
+                                            .fit_to_exact_size(egui::vec2(24.0, 24.0)),
+                                    );
+                                } else {
+                                    ui.add_space(24.0);
+                                }
+
+                                ui.vertical(|ui| {
+                                    ui.strong(&mod_info.name);
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "👤 {} | ⬇ {}",
+                                            mod_info.author,
+                                            format_count(u64::from(mod_info.download_count))
+                                        ))
+                                        .small()
+                                        .weak(),
+                                    );
+                                });
+
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui
+                                            .button("🔀 Swap in")
+                                            .on_hover_text(
+                                                "Add this project and remove the incompatible one",
+                                            )
+                                            .clicked()
+                                        {
+                                            replacement = Some(mod_info.clone());
+                                        }
+                                    },
+                                );
+                            });
+                            ui.separator();
+                        }
+                    });
+            });
+
+        if let Some(replacement) = replacement {
+            effects.extend(state.replace_mod(&mod_id, replacement));
+            state.alternatives = None;
+        } else if !is_open {
+            state.alternatives = None;
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app::{AppState, Effect};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct BulkAddWindow;
+
+impl BulkAddWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let overlay_id = egui::Id::new("bulk_add_overlay");
+        let overlay = egui::Area::new(overlay_id)
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = view_state.bulk_add_open;
+        let mut should_add = false;
+        let mut should_reset = false;
+        let mut should_close = false;
+
+        egui::Window::new("📋 Add Multiple")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                let Some(bulk) = &state.bulk_add else {
+                    ui.label("Modrinth slugs or links, one per line:");
+                    egui::ScrollArea::vertical()
+                        .max_height(250.0)
+                        .show(ui, |ui| {
+                            ui.add(
+                                egui::TextEdit::multiline(&mut view_state.bulk_add_input)
+                                    .hint_text("sodium\nhttps://modrinth.com/mod/lithium")
+                                    .desired_rows(8)
+                                    .desired_width(f32::INFINITY),
+                            );
+                        });
+
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                !view_state.bulk_add_input.trim().is_empty(),
+                                egui::Button::new("➕ Add"),
+                            )
+                            .clicked()
+                        {
+                            should_add = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            should_close = true;
+                        }
+                    });
+                    return;
+                };
+
+                if !bulk.is_done() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Resolving projects...");
+                    });
+                    ui.add(
+                        egui::ProgressBar::new(bulk.finished() as f32 / bulk.total.max(1) as f32)
+                            .text(format!("{}/{}", bulk.finished(), bulk.total)),
+                    );
+                }
+
+                ui.label(format!("✅ {} added", bulk.added.len()));
+                if !bulk.already_present.is_empty() {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "{} already in the list",
+                            bulk.already_present.len()
+                        ))
+                        .weak(),
+                    );
+                }
+
+                if !bulk.failed.is_empty() {
+                    ui.add_space(4.0);
+                    ui.colored_label(
+                        egui::Color32::LIGHT_RED,
+                        format!("❌ {} could not be added", bulk.failed.len()),
+                    );
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for (input, error) in &bulk.failed {
+                                ui.label(egui::RichText::new(format!("{input}: {error}")).small());
+                            }
+                        });
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(bulk.is_done(), egui::Button::new("Add More"))
+                        .clicked()
+                    {
+                        should_reset = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if should_add && let Some(list_id) = state.current_list_id.clone() {
+            let inputs = view_state
+                .bulk_add_input
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(String::from)
+                .collect();
+            effects.extend(state.add_many_by_slug(&list_id, inputs));
+            view_state.bulk_add_input.clear();
+        }
+
+        if should_reset {
+            state.bulk_add = None;
+        }
+
+        // Closing while running only hides the window, the remaining mods are still added
+        if should_close || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.bulk_add_open = false;
+            state.bulk_add = None;
+        }
+
+        effects
+    }
+}
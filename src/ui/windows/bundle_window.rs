@@ -0,0 +1,138 @@
+use crate::app::{AppState, Effect};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct BundleWindow;
+
+impl BundleWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        if view_state.bundle_version_input.is_empty()
+            && let Some(latest_version) = state.latest_release()
+        {
+            view_state.bundle_version_input = latest_version.id.clone();
+        }
+
+        let overlay = egui::Area::new(egui::Id::new("bundle_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = view_state.bundle_window_open;
+        let mut should_save = false;
+        let mut should_close = false;
+
+        let title = if view_state.bundle_edit_id.is_some() {
+            "📦 Edit Bundle"
+        } else {
+            "📦 Create Bundle"
+        };
+
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(360.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.label("Bundle Name:");
+                ui.text_edit_singleline(&mut view_state.bundle_name_input);
+
+                ui.add_space(10.0);
+
+                ui.label("Minecraft Version:");
+                let display_version = state
+                    .minecraft_versions
+                    .iter()
+                    .find(|v| v.id == view_state.bundle_version_input)
+                    .map(|v| v.name.clone())
+                    .unwrap_or_else(|| view_state.bundle_version_input.clone());
+                egui::ComboBox::from_id_salt("bundle_version_selector")
+                    .selected_text(display_version)
+                    .show_ui(ui, |ui| {
+                        for version in state.selectable_versions(false) {
+                            ui.selectable_value(
+                                &mut view_state.bundle_version_input,
+                                version.id.clone(),
+                                &version.name,
+                            );
+                        }
+                    });
+
+                ui.add_space(10.0);
+
+                ui.label("Lists:");
+                egui::ScrollArea::vertical()
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        for list in &state.mod_lists {
+                            let mut included = view_state.bundle_list_ids.contains(&list.id);
+                            let label = format!(
+                                "{} {} ({})",
+                                list.content_type.emoji(),
+                                list.name,
+                                list.content_type.display_name()
+                            );
+                            if ui.checkbox(&mut included, label).changed() {
+                                if included {
+                                    view_state.bundle_list_ids.push(list.id.clone());
+                                } else {
+                                    view_state.bundle_list_ids.retain(|id| id != &list.id);
+                                }
+                            }
+                        }
+                    });
+                ui.label(
+                    egui::RichText::new("Lists in the bundle are switched to its version").weak(),
+                );
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    let can_save = !view_state.bundle_name_input.trim().is_empty()
+                        && !view_state.bundle_version_input.is_empty();
+                    if ui
+                        .add_enabled(can_save, egui::Button::new("Save"))
+                        .clicked()
+                    {
+                        should_save = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if should_save {
+            // Lists deleted since the bundle was made drop out here
+            let list_ids = view_state
+                .bundle_list_ids
+                .iter()
+                .filter(|id| state.mod_lists.iter().any(|l| &l.id == *id))
+                .cloned()
+                .collect();
+            effects.extend(state.save_bundle(
+                view_state.bundle_edit_id.as_deref(),
+                view_state.bundle_name_input.trim().to_string(),
+                view_state.bundle_version_input.clone(),
+                list_ids,
+            ));
+            should_close = true;
+        }
+
+        if should_close || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.bundle_window_open = false;
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,80 @@
+use crate::app::{AppState, Effect};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct CleanUpOrphansWindow;
+
+impl CleanUpOrphansWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some(orphans) = view_state.confirm_clean_up_orphans.clone() else {
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("clean_up_orphans_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut should_clean = false;
+        let mut should_cancel = false;
+
+        egui::Window::new("🧹 Clean Up Removed Mods")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(320.0);
+
+                ui.label(format!(
+                    "Delete these {} files of mods that are no longer in the list? \
+                     This can't be undone.",
+                    orphans.len()
+                ));
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for (mod_id, filename) in &orphans {
+                            ui.label(egui::RichText::new(filename).weak())
+                                .on_hover_text(mod_id);
+                        }
+                    });
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(egui::RichText::new("🧹 Clean Up").color(egui::Color32::LIGHT_RED))
+                        .clicked()
+                    {
+                        should_clean = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_clean {
+            view_state.confirm_clean_up_orphans = None;
+            let mod_ids: Vec<String> = orphans.into_iter().map(|(mod_id, _)| mod_id).collect();
+            effects.extend(state.clean_up_orphaned_files(&mod_ids));
+        } else if should_cancel || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.confirm_clean_up_orphans = None;
+        }
+
+        effects
+    }
+}
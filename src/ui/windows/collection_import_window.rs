@@ -0,0 +1,97 @@
+use crate::app::{AppState, Effect};
+use crate::domain::parse_collection_id;
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct CollectionImportWindow;
+
+impl CollectionImportWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let overlay_id = egui::Id::new("collection_import_overlay");
+        let overlay = egui::Area::new(overlay_id)
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+
+            if ui
+                .interact(screen_rect, overlay_id.with("click"), egui::Sense::click())
+                .clicked()
+            {
+                view_state.collection_import_open = false;
+            }
+        });
+
+        let mut is_open = view_state.collection_import_open;
+        let mut should_fetch = false;
+        let mut should_close = false;
+
+        egui::Window::new("📚 Import Modrinth Collection")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.label("Collection:");
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut view_state.collection_input)
+                        .hint_text("Collection ID or modrinth.com link"),
+                );
+                if response.changed() {
+                    view_state.collection_error = None;
+                }
+
+                if let Some(error) = &view_state.collection_error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                ui.add_space(4.0);
+                ui.label(
+                    egui::RichText::new(
+                        "Version and loader are picked after the collection is loaded",
+                    )
+                    .weak(),
+                );
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !view_state.collection_input.trim().is_empty(),
+                            egui::Button::new("Load"),
+                        )
+                        .clicked()
+                    {
+                        should_fetch = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        if should_close || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.collection_import_open = false;
+        } else if should_fetch || ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+            match parse_collection_id(&view_state.collection_input) {
+                Ok(collection_id) => {
+                    effects.extend(state.start_collection_import(collection_id));
+                    view_state.collection_import_open = false;
+                    view_state.collection_input.clear();
+                }
+                Err(e) => view_state.collection_error = Some(e),
+            }
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,159 @@
+use crate::app::{AppState, Effect, MatrixCell};
+use eframe::egui;
+
+pub struct CompatibilityWindow;
+
+impl CompatibilityWindow {
+    pub fn show(ctx: &egui::Context, state: &mut AppState) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let (Some(matrix), Some(list)) = (&state.compatibility_matrix, state.get_current_list())
+        else {
+            state.compatibility_matrix = None;
+            return effects;
+        };
+        if matrix.list_id != list.id {
+            state.compatibility_matrix = None;
+            return effects;
+        }
+
+        let entries: Vec<(String, String)> = list
+            .mods
+            .iter()
+            .filter(|e| !e.archived)
+            .map(|e| (e.mod_id.clone(), e.mod_name.clone()))
+            .collect();
+        let candidates: Vec<String> = state
+            .selectable_versions(list.include_snapshots)
+            .into_iter()
+            .filter(|v| !matrix.versions.contains(&v.id))
+            .map(|v| v.id.clone())
+            .collect();
+
+        let best_count = matrix
+            .versions
+            .iter()
+            .map(|v| matrix.available_count(v))
+            .max()
+            .unwrap_or(0);
+
+        let mut is_open = true;
+        let mut added_version = None;
+        let mut removed_version = None;
+
+        egui::Window::new("🧮 Compatibility Matrix")
+            .collapsible(false)
+            .resizable(true)
+            .default_size([640.0, 420.0])
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for version in &matrix.versions {
+                        if ui
+                            .small_button(format!("{version} ✖"))
+                            .on_hover_text("Remove this version")
+                            .clicked()
+                        {
+                            removed_version = Some(version.clone());
+                        }
+                    }
+
+                    egui::ComboBox::from_id_salt("matrix_add_version")
+                        .selected_text("➕ Add version")
+                        .show_ui(ui, |ui| {
+                            egui::ScrollArea::vertical()
+                                .max_height(300.0)
+                                .show(ui, |ui| {
+                                    for version in &candidates {
+                                        if ui.selectable_label(false, version).clicked() {
+                                            added_version = Some(version.clone());
+                                        }
+                                    }
+                                });
+                        });
+                });
+
+                if state.is_offline {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        "📴 Offline, only already checked versions are shown",
+                    );
+                }
+                ui.separator();
+
+                if entries.is_empty() {
+                    ui.label("This list has no active items.");
+                    return;
+                }
+
+                egui::ScrollArea::both().show(ui, |ui| {
+                    egui::Grid::new("compatibility_matrix_grid")
+                        .striped(true)
+                        .spacing([16.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.strong("Project");
+                            for version in &matrix.versions {
+                                let count = matrix.available_count(version);
+                                let header = egui::RichText::new(format!(
+                                    "{version} ({count}/{})",
+                                    entries.len()
+                                ))
+                                .strong();
+                                if count == best_count && count > 0 {
+                                    ui.label(header.color(egui::Color32::GREEN))
+                                        .on_hover_text("Most items available");
+                                } else {
+                                    ui.label(header);
+                                }
+                            }
+                            ui.end_row();
+
+                            for (mod_id, mod_name) in &entries {
+                                ui.label(mod_name);
+                                for version in &matrix.versions {
+                                    match matrix.cell(mod_id, version) {
+                                        Some(MatrixCell::Loading) => {
+                                            ui.spinner();
+                                        }
+                                        Some(MatrixCell::Available(project_version)) => {
+                                            ui.colored_label(
+                                                egui::Color32::GREEN,
+                                                format!("✓ {project_version}"),
+                                            );
+                                        }
+                                        Some(MatrixCell::Unavailable) => {
+                                            ui.colored_label(egui::Color32::LIGHT_RED, "✗");
+                                        }
+                                        Some(MatrixCell::Failed) => {
+                                            ui.weak("?").on_hover_text("Could not load details");
+                                        }
+                                        None => {
+                                            ui.weak("–");
+                                        }
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if !is_open {
+            state.compatibility_matrix = None;
+            return effects;
+        }
+
+        if let Some(matrix) = &mut state.compatibility_matrix {
+            if let Some(version) = removed_version {
+                matrix.versions.retain(|v| *v != version);
+            }
+            if let Some(version) = added_version {
+                matrix.versions.push(version);
+                effects.extend(state.check_compatibility());
+            }
+        }
+
+        effects
+    }
+}
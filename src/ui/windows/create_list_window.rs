@@ -17,8 +17,10 @@ impl CreateListWindow {
         if view_state.new_list_name.is_empty() {
             view_state.new_list_name = state.default_list_name.clone();
         }
-        if view_state.new_list_version.is_empty() && !state.minecraft_versions.is_empty() {
-            view_state.new_list_version = state.minecraft_versions[0].id.clone();
+        if view_state.new_list_version.is_empty()
+            && let Some(latest_version) = state.latest_release()
+        {
+            view_state.new_list_version = latest_version.id.clone();
         }
         if view_state.new_list_loader.is_empty()
             && let Some(loaders) = state.loaders_for_type(view_state.new_list_type)
@@ -26,6 +28,10 @@ impl CreateListWindow {
         {
             view_state.new_list_loader = loaders[0].id.clone();
         }
+        if !view_state.new_list_dir_prefilled {
+            view_state.new_list_dir = state.default_dir_for(view_state.new_list_type);
+            view_state.new_list_dir_prefilled = true;
+        }
 
         let overlay = egui::Area::new(egui::Id::new("create_list_overlay"))
             .order(egui::Order::Background)
@@ -63,16 +69,11 @@ impl CreateListWindow {
                 ui.add_space(10.0);
 
                 ui.label("Content Type:");
+                let previous_type = view_state.new_list_type;
                 egui::ComboBox::from_id_salt("new_list_type_selector")
                     .selected_text(view_state.new_list_type.display_name())
                     .show_ui(ui, |ui| {
-                        for p_type in &[
-                            ProjectType::Mod,
-                            ProjectType::ResourcePack,
-                            ProjectType::Shader,
-                            ProjectType::Datapack,
-                            ProjectType::Plugin,
-                        ] {
+                        for p_type in &ProjectType::all() {
                             if ui
                                 .selectable_value(
                                     &mut view_state.new_list_type,
@@ -87,6 +88,12 @@ impl CreateListWindow {
 
                                 view_state.new_list_loader.clear();
 
+                                // Follows the type unless the folder was picked by hand
+                                if view_state.new_list_dir == state.default_dir_for(previous_type) {
+                                    view_state.new_list_dir =
+                                        state.default_dir_for(view_state.new_list_type);
+                                }
+
                                 if let Some(loaders) =
                                     state.loaders_for_type(view_state.new_list_type)
                                     && !loaders.is_empty()
@@ -110,7 +117,7 @@ impl CreateListWindow {
                 egui::ComboBox::from_id_salt("new_list_version_selector")
                     .selected_text(display_version)
                     .show_ui(ui, |ui| {
-                        for version in &state.minecraft_versions {
+                        for version in state.selectable_versions(false) {
                             ui.selectable_value(
                                 &mut view_state.new_list_version,
                                 version.id.clone(),
@@ -132,7 +139,7 @@ impl CreateListWindow {
                         let is_current_valid = !view_state.new_list_loader.is_empty()
                             && loaders_vec
                                 .iter()
-                                .any(|l| l.id == view_state.new_list_loader);
+                                .any(|l| l.matches(&view_state.new_list_loader));
 
                         if !is_current_valid {
                             view_state.new_list_loader = loaders_vec[0].id.clone();
@@ -140,7 +147,7 @@ impl CreateListWindow {
 
                         let display_loader = loaders_vec
                             .iter()
-                            .find(|l| l.id == view_state.new_list_loader)
+                            .find(|l| l.matches(&view_state.new_list_loader))
                             .map(|l| l.name.clone())
                             .unwrap_or_else(|| loaders_vec[0].name.clone());
 
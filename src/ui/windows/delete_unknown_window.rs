@@ -0,0 +1,77 @@
+use crate::app::{AppState, Effect};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct DeleteUnknownWindow;
+
+impl DeleteUnknownWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some(filenames) = view_state.confirm_delete_unknown.clone() else {
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("delete_unknown_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut should_delete = false;
+        let mut should_cancel = false;
+
+        egui::Window::new("🗑 Delete Unknown Files")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(320.0);
+
+                ui.label(format!(
+                    "Delete these {} files from the download folder? This can't be undone.",
+                    filenames.len()
+                ));
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for filename in &filenames {
+                            ui.label(egui::RichText::new(filename).weak());
+                        }
+                    });
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(egui::RichText::new("🗑 Delete All").color(egui::Color32::LIGHT_RED))
+                        .clicked()
+                    {
+                        should_delete = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_delete {
+            view_state.confirm_delete_unknown = None;
+            effects.extend(state.delete_unknown_files(&filenames));
+        } else if should_cancel || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.confirm_delete_unknown = None;
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,73 @@
+use crate::app::{AppState, Effect};
+use eframe::egui;
+
+pub struct DependencyWindow;
+
+impl DependencyWindow {
+    pub fn show(ctx: &egui::Context, state: &mut AppState) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some((mod_info, dependencies)) = state.pending_dependencies.clone() else {
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("dependency_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut should_add = false;
+        let mut should_skip = false;
+
+        egui::Window::new("🔗 Required Dependencies")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(300.0);
+
+                ui.label(format!(
+                    "{} requires the following items that are not in this list yet:",
+                    mod_info.name
+                ));
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        for dependency in &dependencies {
+                            ui.label(format!("• {} by {}", dependency.name, dependency.author));
+                        }
+                    });
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(format!("➕ Add {} Dependencies", dependencies.len()))
+                        .clicked()
+                    {
+                        should_add = true;
+                    }
+                    if ui.button("Skip").clicked() {
+                        should_skip = true;
+                    }
+                });
+            });
+
+        if should_add {
+            state.pending_dependencies = None;
+            effects.extend(state.add_mod_with_dependencies(mod_info, dependencies));
+        } else if should_skip || !is_open {
+            state.pending_dependencies = None;
+        }
+
+        effects
+    }
+}
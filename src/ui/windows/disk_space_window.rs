@@ -0,0 +1,68 @@
+use crate::app::{AppState, Effect};
+use crate::domain::format_bytes;
+use eframe::egui;
+
+pub struct DiskSpaceWindow;
+
+impl DiskSpaceWindow {
+    pub fn show(ctx: &egui::Context, state: &mut AppState) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some(low_space) = state.low_disk_space.clone() else {
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("disk_space_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut should_download = false;
+        let mut should_cancel = false;
+
+        egui::Window::new("⚠ Not Enough Disk Space")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(300.0);
+
+                ui.label(format!(
+                    "Downloading {} items needs more space than is free on the target drive.",
+                    low_space.mod_ids.len()
+                ));
+                ui.add_space(8.0);
+                ui.label(format!("Required: {}", format_bytes(low_space.needed)));
+                ui.colored_label(
+                    egui::Color32::LIGHT_RED,
+                    format!("Available: {}", format_bytes(low_space.available)),
+                );
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Download Anyway").clicked() {
+                        should_download = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_download {
+            state.low_disk_space = None;
+            effects.extend(state.start_download_batch(&low_space.mod_ids));
+        } else if should_cancel || !is_open {
+            state.low_disk_space = None;
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,116 @@
+use crate::app::{AppState, HistoryStatusFilter};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct DownloadHistoryWindow;
+
+impl DownloadHistoryWindow {
+    pub fn show(ctx: &egui::Context, state: &AppState, view_state: &mut ViewState) {
+        let mut is_open = view_state.download_history_open;
+
+        egui::Window::new("📜 Download History")
+            .collapsible(false)
+            .resizable(true)
+            .default_size([520.0, 400.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("List:");
+                    let selected_list = view_state
+                        .history_list_filter
+                        .as_ref()
+                        .and_then(|id| state.get_list_by_id(id))
+                        .map(|l| l.name.clone())
+                        .unwrap_or_else(|| "All lists".to_string());
+
+                    egui::ComboBox::from_id_salt("history_list_filter")
+                        .selected_text(selected_list)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut view_state.history_list_filter,
+                                None,
+                                "All lists",
+                            );
+                            for list in &state.mod_lists {
+                                ui.selectable_value(
+                                    &mut view_state.history_list_filter,
+                                    Some(list.id.clone()),
+                                    &list.name,
+                                );
+                            }
+                        });
+
+                    ui.label("Status:");
+                    egui::ComboBox::from_id_salt("history_status_filter")
+                        .selected_text(view_state.history_status_filter.display_name())
+                        .show_ui(ui, |ui| {
+                            for filter in HistoryStatusFilter::all() {
+                                ui.selectable_value(
+                                    &mut view_state.history_status_filter,
+                                    *filter,
+                                    filter.display_name(),
+                                );
+                            }
+                        });
+                });
+
+                ui.separator();
+
+                let records: Vec<_> = state
+                    .download_history
+                    .iter()
+                    .rev()
+                    .filter(|r| {
+                        view_state
+                            .history_list_filter
+                            .as_ref()
+                            .is_none_or(|id| r.list_id.as_ref() == Some(id))
+                    })
+                    .filter(|r| view_state.history_status_filter.matches(r))
+                    .collect();
+
+                if records.is_empty() {
+                    ui.weak("No downloads recorded yet.");
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("download_history_grid")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                for record in records {
+                                    ui.label(
+                                        record
+                                            .timestamp
+                                            .with_timezone(&chrono::Local)
+                                            .format("%Y-%m-%d %H:%M")
+                                            .to_string(),
+                                    );
+                                    ui.label(&record.mod_name).on_hover_text(&record.file_name);
+                                    ui.label(&record.version);
+                                    match record.failure {
+                                        None => {
+                                            ui.label("✅ Downloaded");
+                                        }
+                                        Some(failure) => {
+                                            let label = ui.colored_label(
+                                                egui::Color32::LIGHT_RED,
+                                                format!("❌ Failed ({})", failure.reason()),
+                                            );
+                                            if let Some(error) = &record.error {
+                                                label.on_hover_text(error);
+                                            }
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        view_state.download_history_open = is_open;
+    }
+}
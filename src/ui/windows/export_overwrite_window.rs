@@ -0,0 +1,84 @@
+use crate::app::{AppState, Effect};
+use crate::domain::OverwritePolicy;
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct ExportOverwriteWindow;
+
+impl ExportOverwriteWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some((path, subset)) = view_state.pending_export.clone() else {
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("export_overwrite_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut chosen_policy = None;
+        let mut should_cancel = false;
+
+        egui::Window::new("⚠ File Already Exists")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(300.0);
+
+                let file_name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                ui.label(format!("{file_name} already exists. Replace it?"));
+                ui.add_space(4.0);
+                ui.label(
+                    egui::RichText::new("A backup keeps the old file next to it as .bak").weak(),
+                );
+
+                ui.add_space(8.0);
+                ui.checkbox(
+                    &mut view_state.export_dont_ask_again,
+                    "Don't ask again this session",
+                );
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("💾 Back Up and Overwrite").clicked() {
+                        chosen_policy = Some(OverwritePolicy::Backup);
+                    }
+                    if ui.button("Overwrite").clicked() {
+                        chosen_policy = Some(OverwritePolicy::Overwrite);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if let Some(policy) = chosen_policy {
+            view_state.pending_export = None;
+            if view_state.export_dont_ask_again {
+                view_state.export_overwrite_choice = Some(policy);
+            }
+            effects.extend(state.export_current_list(path, policy, subset.as_deref()));
+        } else if should_cancel || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.pending_export = None;
+        }
+
+        effects
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::{AppState, Effect, ListAction};
+use crate::domain::{ModEntry, diff_lists};
 use crate::ui::ViewState;
 use eframe::egui;
 
@@ -12,7 +13,9 @@ impl ImportWindow {
     ) -> Vec<Effect> {
         let mut effects = Vec::new();
 
-        if view_state.pending_import_list.is_none() {
+        if view_state.pending_import_list.is_none()
+            && view_state.active_action != ListAction::ShareLink
+        {
             view_state.import_window_open = false;
             return effects;
         }
@@ -33,6 +36,7 @@ impl ImportWindow {
             {
                 view_state.import_window_open = false;
                 view_state.pending_import_list = None;
+                view_state.import_duplicate_of = None;
             }
         });
 
@@ -45,9 +49,12 @@ impl ImportWindow {
         let title = match view_state.active_action {
             ListAction::Import => "📥 Import Mod List",
             ListAction::Duplicate => "👥 Duplicate Mod List",
+            ListAction::ShareLink => "🔗 Import from Share Link",
         };
 
         let mut should_finalize = false;
+        let mut should_merge = false;
+        let mut should_replace = false;
         let mut should_close = false;
         let mut is_open = view_state.import_window_open;
 
@@ -57,9 +64,210 @@ impl ImportWindow {
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .open(&mut is_open)
             .show(ctx, |ui| {
+                if view_state.pending_import_list.is_none() {
+                    ui.label("Share link:");
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut view_state.share_link_input)
+                            .hint_text("Paste a share link"),
+                    );
+                    if response.changed() {
+                        view_state.share_link_error = None;
+                    }
+
+                    if let Some(error) = &view_state.share_link_error {
+                        ui.colored_label(egui::Color32::LIGHT_RED, error);
+                    }
+
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                !view_state.share_link_input.trim().is_empty(),
+                                egui::Button::new("Decode"),
+                            )
+                            .clicked()
+                        {
+                            match crate::infra::decode_share_link(&view_state.share_link_input) {
+                                Ok(list) => {
+                                    view_state.import_name_input =
+                                        format!("{} (Shared)", list.name);
+                                    view_state.pending_import_list = Some(list);
+                                    view_state.share_link_input.clear();
+                                }
+                                Err(e) => view_state.share_link_error = Some(format!("{e:#}")),
+                            }
+                        }
+                        if ui.button("Cancel").clicked() {
+                            should_close = true;
+                        }
+                    });
+                    return;
+                }
+
+                if let Some(existing_id) = &view_state.import_duplicate_of
+                    && let Some(existing) = state.mod_lists.iter().find(|l| &l.id == existing_id)
+                    && let Some(imported) = &view_state.pending_import_list
+                {
+                    let identical = existing.content_key() == imported.content_key();
+                    ui.colored_label(
+                        egui::Color32::GOLD,
+                        if identical {
+                            format!(
+                                "⚠ A list with identical mods already exists: '{}'",
+                                existing.name
+                            )
+                        } else {
+                            format!("⚠ This looks like a newer version of '{}'", existing.name)
+                        },
+                    );
+                    ui.label(
+                        egui::RichText::new(
+                            "Merging adds anything missing and keeps the existing pinned versions, \
+                             replacing applies all changes below",
+                        )
+                        .weak(),
+                    );
+
+                    let diff = diff_lists(existing, imported);
+                    if diff.is_empty() {
+                        ui.label(egui::RichText::new("No changes").weak());
+                    }
+
+                    let entry_label = |entry: &ModEntry| {
+                        let archived = if entry.archived { " (archived)" } else { "" };
+                        format!("{}{archived}", entry.mod_name)
+                    };
+                    let version_label = |entry: &ModEntry| {
+                        entry
+                            .pinned_version
+                            .clone()
+                            .unwrap_or_else(|| "latest".to_string())
+                    };
+                    let sections: [(&str, Vec<String>); 4] = [
+                        (
+                            "➕ Added",
+                            diff.added.iter().map(|e| entry_label(e)).collect(),
+                        ),
+                        (
+                            "➖ Removed",
+                            diff.removed.iter().map(|e| entry_label(e)).collect(),
+                        ),
+                        (
+                            "⬆ Version changed",
+                            diff.version_changed
+                                .iter()
+                                .map(|(old, new)| {
+                                    format!(
+                                        "{}: {} → {}",
+                                        new.mod_name,
+                                        version_label(old),
+                                        version_label(new)
+                                    )
+                                })
+                                .collect(),
+                        ),
+                        (
+                            "⚠ Source changed",
+                            diff.source_changed
+                                .iter()
+                                .map(|(old, new)| {
+                                    format!(
+                                        "{} ({}) → {} ({})",
+                                        old.mod_name,
+                                        old.provider.display_name(),
+                                        new.mod_name,
+                                        new.provider.display_name()
+                                    )
+                                })
+                                .collect(),
+                        ),
+                    ];
+                    for (label, lines) in sections {
+                        if lines.is_empty() {
+                            continue;
+                        }
+                        egui::CollapsingHeader::new(format!("{label} ({})", lines.len()))
+                            .id_salt(label)
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                egui::ScrollArea::vertical()
+                                    .id_salt(label)
+                                    .max_height(120.0)
+                                    .show(ui, |ui| {
+                                        for line in lines {
+                                            ui.label(line);
+                                        }
+                                    });
+                            });
+                    }
+
+                    ui.add_space(12.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("🔀 Merge").clicked() {
+                            should_merge = true;
+                        }
+                        if ui
+                            .button("⟳ Replace")
+                            .on_hover_text("Locked entries are kept as they are")
+                            .clicked()
+                        {
+                            should_replace = true;
+                        }
+                        if ui.button("Import as New").clicked() {
+                            should_finalize = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            should_close = true;
+                        }
+                    });
+                    return;
+                }
+
                 ui.label("List Name:");
                 ui.text_edit_singleline(&mut view_state.import_name_input);
 
+                if view_state.active_action == ListAction::Duplicate
+                    && let Some(list) = &view_state.pending_import_list
+                {
+                    let content_type = list.content_type;
+                    let include_snapshots = list.include_snapshots;
+
+                    ui.add_space(8.0);
+                    ui.label("Minecraft Version:");
+                    egui::ComboBox::from_id_salt("duplicate_version")
+                        .selected_text(&view_state.duplicate_version)
+                        .show_ui(ui, |ui| {
+                            for ver in state.selectable_versions(include_snapshots) {
+                                ui.selectable_value(
+                                    &mut view_state.duplicate_version,
+                                    ver.id.clone(),
+                                    ver.display_label(),
+                                );
+                            }
+                        });
+
+                    effects.extend(state.ensure_loaders_for_type(content_type));
+                    let loaders = state.loaders_for_type(content_type).unwrap_or(&[]);
+                    let loader_name = loaders
+                        .iter()
+                        .find(|l| l.matches(&view_state.duplicate_loader))
+                        .map(|l| l.name.clone())
+                        .unwrap_or_else(|| view_state.duplicate_loader.clone());
+
+                    ui.label("Mod Loader:");
+                    egui::ComboBox::from_id_salt("duplicate_loader")
+                        .selected_text(loader_name)
+                        .show_ui(ui, |ui| {
+                            for loader in loaders {
+                                ui.selectable_value(
+                                    &mut view_state.duplicate_loader,
+                                    loader.id.clone(),
+                                    &loader.name,
+                                );
+                            }
+                        });
+                }
+
                 ui.add_space(8.0);
                 ui.label(egui::RichText::new(format!("Contains {mod_count} items")).weak());
 
@@ -74,12 +282,39 @@ impl ImportWindow {
                 });
             });
 
+        let confirmed = should_finalize
+            || (view_state.import_duplicate_of.is_none()
+                && ctx.input(|i| i.key_pressed(egui::Key::Enter)));
+
         if should_close || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
             view_state.import_window_open = false;
             view_state.pending_import_list = None;
-        } else if (should_finalize || ctx.input(|i| i.key_pressed(egui::Key::Enter)))
-            && let Some(mut list) = view_state.pending_import_list.take()
+            view_state.import_duplicate_of = None;
+        } else if should_merge
+            && let Some(existing_id) = view_state.import_duplicate_of.take()
+            && let Some(list) = view_state.pending_import_list.take()
+        {
+            effects.extend(state.merge_import(&existing_id, list));
+            view_state.import_window_open = false;
+            view_state.import_name_input.clear();
+        } else if should_replace
+            && let Some(existing_id) = view_state.import_duplicate_of.take()
+            && let Some(list) = view_state.pending_import_list.take()
+        {
+            effects.extend(state.replace_with_import(&existing_id, list));
+            view_state.import_window_open = false;
+            view_state.import_name_input.clear();
+        } else if confirmed
+            && view_state.import_duplicate_of.is_none()
+            && view_state.active_action != ListAction::Duplicate
+            && let Some(list) = &view_state.pending_import_list
+            && let Some(existing_id) = state
+                .find_duplicate_list(list)
+                .or_else(|| state.find_previous_version(list))
         {
+            view_state.import_duplicate_of = Some(existing_id);
+        } else if confirmed && let Some(mut list) = view_state.pending_import_list.take() {
+            view_state.import_duplicate_of = None;
             list.id = format!("list_{}", chrono::Utc::now().timestamp_millis());
             list.name = view_state.import_name_input.trim().to_string();
 
@@ -87,7 +322,19 @@ impl ImportWindow {
                 list.name = "Unnamed List".to_string();
             }
 
-            effects.extend(state.finalize_import(list));
+            let retarget = view_state.active_action == ListAction::Duplicate
+                && (view_state.duplicate_version != state.get_effective_version()
+                    || view_state.duplicate_loader != state.get_effective_loader());
+
+            if retarget {
+                effects.extend(state.duplicate_retargeted(
+                    list,
+                    std::mem::take(&mut view_state.duplicate_version),
+                    std::mem::take(&mut view_state.duplicate_loader),
+                ));
+            } else {
+                effects.extend(state.finalize_import(list));
+            }
             view_state.import_window_open = false;
             view_state.import_name_input.clear();
         }
@@ -1,5 +1,5 @@
 use crate::app::{AppState, Effect};
-use crate::domain::{ModEntry, ModList, ProjectType};
+use crate::domain::{ModEntry, ModList, ProjectType, ReleaseChannel};
 use crate::ui::{ViewState, dialogs::Dialogs};
 use eframe::egui;
 
@@ -63,7 +63,7 @@ impl LegacyImportSettingsWindow {
                 egui::ComboBox::from_id_salt("legacy_import_version_selector")
                     .selected_text(display_version)
                     .show_ui(ui, |ui| {
-                        for version in &state.minecraft_versions {
+                        for version in state.selectable_versions(false) {
                             ui.selectable_value(
                                 &mut view_state.legacy_import_version,
                                 version.id.clone(),
@@ -78,7 +78,7 @@ impl LegacyImportSettingsWindow {
                 let loaders = state.mod_loaders.clone();
                 let display_loader = loaders
                     .iter()
-                    .find(|l| l.id == view_state.legacy_import_loader)
+                    .find(|l| l.matches(&view_state.legacy_import_loader))
                     .map(|l| l.name.clone())
                     .unwrap_or_else(|| view_state.legacy_import_loader.clone());
 
@@ -106,6 +106,63 @@ impl LegacyImportSettingsWindow {
                     }
                 });
 
+                if let Some(entries) = &mut view_state.legacy_import_preview {
+                    ui.add_space(10.0);
+                    ui.label(format!("Entries ({}):", entries.len()));
+
+                    let mut remove_idx = None;
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for (idx, entry) in entries.iter_mut().enumerate() {
+                                ui.horizontal(|ui| {
+                                    ui.text_edit_singleline(entry);
+                                    if ui.small_button("🗑").on_hover_text("Remove").clicked() {
+                                        remove_idx = Some(idx);
+                                    }
+                                });
+                            }
+                        });
+                    if let Some(idx) = remove_idx {
+                        entries.remove(idx);
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut view_state.legacy_import_new_entry);
+                        if ui.button("➕ Add").clicked() {
+                            let entry = view_state.legacy_import_new_entry.trim().to_string();
+                            if !entry.is_empty() {
+                                entries.push(entry);
+                            }
+                            view_state.legacy_import_new_entry.clear();
+                        }
+                    });
+
+                    ui.add_space(12.0);
+                    let slugs: Vec<String> = entries
+                        .iter()
+                        .map(|e| e.trim().to_string())
+                        .filter(|e| !e.is_empty())
+                        .collect();
+                    if ui
+                        .add_enabled(
+                            !slugs.is_empty(),
+                            egui::Button::new(format!("🔍 Resolve {} entries", slugs.len())),
+                        )
+                        .clicked()
+                    {
+                        effects.extend(state.resolve_legacy_import(
+                            view_state.legacy_import_name.clone(),
+                            slugs,
+                            view_state.legacy_import_version.clone(),
+                            view_state.legacy_import_loader.clone(),
+                        ));
+                        view_state.legacy_import_preview = None;
+                        view_state.legacy_import_settings_open = false;
+                    }
+                    return;
+                }
+
                 ui.add_space(12.0);
                 ui.horizontal(|ui| {
                     if ui.button("Import").clicked() {
@@ -118,6 +175,12 @@ impl LegacyImportSettingsWindow {
                                     added_at: chrono::Utc::now(),
                                     archived: false,
                                     compatibility_override: false,
+                                    provider: m.provider,
+                                    added_as_dependency: false,
+                                    pinned_version: None,
+                                    filename_override: None,
+                                    disabled: false,
+                                    locked: false,
                                 })
                                 .collect();
 
@@ -130,7 +193,7 @@ impl LegacyImportSettingsWindow {
                                 loader: state
                                     .mod_loaders
                                     .iter()
-                                    .find(|l| l.id == view_state.legacy_import_loader)
+                                    .find(|l| l.matches(&view_state.legacy_import_loader))
                                     .cloned()
                                     .unwrap_or(crate::domain::ModLoader {
                                         id: view_state.legacy_import_loader.clone(),
@@ -138,6 +201,14 @@ impl LegacyImportSettingsWindow {
                                     }),
                                 download_dir: view_state.legacy_import_dir.clone(),
                                 content_type: ProjectType::Mod,
+                                include_snapshots: false,
+                                server_layout: false,
+                                server_mode: false,
+                                loader_version: None,
+                                allowed_channel: ReleaseChannel::Alpha,
+                                notes: String::new(),
+                                tags: Vec::new(),
+                                is_favorite: false,
                             };
 
                             state.mod_lists.push(list.clone());
@@ -149,11 +220,9 @@ impl LegacyImportSettingsWindow {
                 });
             });
 
-        if should_close {
+        if should_close || !is_open {
             view_state.reset_legacy_import();
             view_state.legacy_import_settings_open = false;
-        } else {
-            view_state.legacy_import_settings_open = is_open;
         }
         effects
     }
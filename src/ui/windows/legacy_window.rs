@@ -1,6 +1,8 @@
 use crate::app::{AppState, Effect, LegacyState};
+use crate::domain::{LegacyMatch, ModInfo};
 use crate::ui::ViewState;
 use eframe::egui;
+use std::sync::Arc;
 
 pub struct LegacyWindow;
 
@@ -10,7 +12,7 @@ impl LegacyWindow {
         state: &mut AppState,
         view_state: &mut ViewState,
     ) -> Vec<Effect> {
-        let effects = Vec::new();
+        let mut effects = Vec::new();
 
         let overlay = egui::Area::new(egui::Id::new("legacy_overlay"))
             .order(egui::Order::Background)
@@ -31,11 +33,14 @@ impl LegacyWindow {
                 && matches!(state.legacy_state, LegacyState::Complete { .. })
             {
                 state.legacy_state = LegacyState::Idle;
+                state.clear_legacy_matches();
             }
         });
 
         let mut is_open = true;
         let mut should_import = false;
+        let mut picked: Option<(usize, Arc<ModInfo>)> = None;
+        let mut search_index: Option<usize> = None;
         let mut suggested_name = String::new();
 
         let window_title = match &state.legacy_state {
@@ -52,7 +57,7 @@ impl LegacyWindow {
 
         egui::Window::new(window_title)
             .collapsible(false)
-            .resizable(false)
+            .resizable(!state.legacy_matches.is_empty())
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .open(&mut is_open)
             .show(ctx, |ui| {
@@ -108,6 +113,20 @@ impl LegacyWindow {
                                     egui::Color32::LIGHT_RED,
                                     format!("❌ Failed: {fail_count}"),
                                 );
+                                if *is_import {
+                                    egui::CollapsingHeader::new("Unresolved entries")
+                                        .id_salt("legacy_failed_entries")
+                                        .show(ui, |ui| {
+                                            egui::ScrollArea::vertical().max_height(150.0).show(
+                                                ui,
+                                                |ui| {
+                                                    for entry in failed {
+                                                        ui.label(entry);
+                                                    }
+                                                },
+                                            );
+                                        });
+                                }
                             }
                             if warn_count > 0 {
                                 ui.colored_label(
@@ -116,6 +135,49 @@ impl LegacyWindow {
                                 );
                             }
 
+                            let chosen = state.pending_legacy_mods.as_deref().unwrap_or_default();
+                            if *is_import
+                                && !state.legacy_matches.is_empty()
+                                && state.legacy_matches.len() == chosen.len()
+                            {
+                                let by_name =
+                                    state.legacy_matches.iter().filter(|m| m.by_name).count();
+                                let title = if by_name > 0 {
+                                    format!(
+                                        "Matched projects ({by_name} found by name, check these)"
+                                    )
+                                } else {
+                                    "Matched projects".to_string()
+                                };
+                                egui::CollapsingHeader::new(title)
+                                    .id_salt("legacy_matches")
+                                    .default_open(by_name > 0)
+                                    .show(ui, |ui| {
+                                        egui::ScrollArea::vertical().max_height(250.0).show(
+                                            ui,
+                                            |ui| {
+                                                for (index, (legacy_match, current)) in state
+                                                    .legacy_matches
+                                                    .iter_mut()
+                                                    .zip(chosen)
+                                                    .enumerate()
+                                                {
+                                                    Self::render_match_row(
+                                                        ui,
+                                                        index,
+                                                        legacy_match,
+                                                        current,
+                                                        state.legacy_candidates_loading
+                                                            == Some(index),
+                                                        &mut picked,
+                                                        &mut search_index,
+                                                    );
+                                                }
+                                            },
+                                        );
+                                    });
+                            }
+
                             if *is_import && is_importable && success_count > 0 {
                                 ui.add_space(15.0);
                                 ui.separator();
@@ -132,7 +194,15 @@ impl LegacyWindow {
                 }
             });
 
+        if let Some((index, candidate)) = picked {
+            state.pick_legacy_match(index, candidate);
+        }
+        if let Some(index) = search_index {
+            effects.extend(state.find_legacy_candidates(index));
+        }
+
         if should_import {
+            state.clear_legacy_matches();
             if let Some(mods) = state.pending_legacy_mods.take() {
                 view_state.legacy_import_mods = Some(mods);
                 view_state.legacy_import_name = suggested_name;
@@ -142,8 +212,67 @@ impl LegacyWindow {
         } else if !is_open {
             state.legacy_state = LegacyState::Idle;
             state.pending_legacy_mods = None;
+            state.clear_legacy_matches();
+            view_state.reset_legacy_import();
         }
 
         effects
     }
+
+    fn render_match_row(
+        ui: &mut egui::Ui,
+        index: usize,
+        legacy_match: &mut LegacyMatch,
+        current: &Arc<ModInfo>,
+        searching: bool,
+        picked: &mut Option<(usize, Arc<ModInfo>)>,
+        search_index: &mut Option<usize>,
+    ) {
+        let describe = |info: &ModInfo| format!("{} ({}) by {}", info.name, info.slug, info.author);
+
+        ui.horizontal(|ui| {
+            if legacy_match.by_name {
+                ui.colored_label(egui::Color32::GOLD, "⚠")
+                    .on_hover_text("No project has this slug, matched by searching the name");
+            }
+            ui.label(egui::RichText::new(format!("{} →", legacy_match.input)).weak());
+
+            egui::ComboBox::from_id_salt(("legacy_match", index))
+                .selected_text(describe(current))
+                .width(260.0)
+                .show_ui(ui, |ui| {
+                    for candidate in &legacy_match.candidates {
+                        if ui
+                            .selectable_label(candidate.id == current.id, describe(candidate))
+                            .clicked()
+                        {
+                            *picked = Some((index, candidate.clone()));
+                        }
+                    }
+                });
+
+            // Editable so a different search can turn up projects the first one missed
+            let query = ui.add(
+                egui::TextEdit::singleline(&mut legacy_match.query)
+                    .desired_width(140.0)
+                    .hint_text("Search Modrinth"),
+            );
+            let submitted = query.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if searching {
+                ui.spinner();
+            } else if (ui
+                .add_enabled(
+                    !legacy_match.query.trim().is_empty(),
+                    egui::Button::new("🔍").small(),
+                )
+                .on_hover_text("Search for other matches, results are added to the list")
+                .clicked()
+                || submitted)
+                && !legacy_match.query.trim().is_empty()
+            {
+                *search_index = Some(index);
+            }
+        });
+    }
 }
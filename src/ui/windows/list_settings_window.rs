@@ -1,4 +1,5 @@
 use crate::app::{AppRuntime, AppState, Effect};
+use crate::domain::{ModList, ProjectType, ReleaseChannel, has_pinnable_loader_version};
 use crate::ui::{ViewState, dialogs::Dialogs};
 use eframe::egui;
 
@@ -65,6 +66,28 @@ impl ListSettingsWindow {
                     let list_loader_name = list.loader.name.clone();
                     let list_loader_id = list.loader.id.clone();
                     let list_download_dir = list.download_dir.clone();
+                    let include_snapshots = *view_state
+                        .list_settings_snapshots
+                        .get_or_insert(list.include_snapshots);
+                    let server_layout = *view_state
+                        .list_settings_server_layout
+                        .get_or_insert(list.server_layout);
+                    let server_mode = *view_state
+                        .list_settings_server_mode
+                        .get_or_insert(list.server_mode);
+                    let previous_channel = list.allowed_channel;
+                    let allowed_channel = view_state
+                        .list_settings_channel
+                        .get_or_insert(list.allowed_channel);
+                    view_state
+                        .list_settings_notes
+                        .get_or_insert_with(|| list.notes.clone());
+                    view_state
+                        .list_settings_tags
+                        .get_or_insert_with(|| list.tags.join(", "));
+                    view_state
+                        .list_settings_loader_version
+                        .get_or_insert_with(|| list.loader_version.clone());
                     let list_clone = list.clone();
 
                     ui.heading(&list_name);
@@ -78,13 +101,27 @@ impl ListSettingsWindow {
                             &view_state.list_settings_version
                         })
                         .show_ui(ui, |ui| {
-                            for ver in &state.minecraft_versions {
-                                if ui.selectable_label(false, &ver.name).clicked() {
+                            for ver in state.selectable_versions(include_snapshots) {
+                                let label = if ver.is_release() {
+                                    egui::RichText::new(ver.display_label())
+                                } else {
+                                    egui::RichText::new(ver.display_label()).weak()
+                                };
+                                if ui.selectable_label(false, label).clicked() {
                                     view_state.list_settings_version = ver.id.clone();
                                 }
                             }
                         });
 
+                    let mut snapshots_toggle = include_snapshots;
+                    if ui
+                        .checkbox(&mut snapshots_toggle, "Include snapshots")
+                        .on_hover_text("Show snapshot, beta and alpha versions")
+                        .changed()
+                    {
+                        view_state.list_settings_snapshots = Some(snapshots_toggle);
+                    }
+
                     ui.add_space(5.0);
 
                     ui.label("Mod Loader:");
@@ -99,7 +136,7 @@ impl ListSettingsWindow {
                     } else {
                         loaders
                             .iter()
-                            .find(|l| l.id == view_state.list_settings_loader)
+                            .find(|l| l.matches(&view_state.list_settings_loader))
                             .map(|l| l.name.clone())
                             .unwrap_or_else(|| view_state.list_settings_loader.clone())
                     };
@@ -114,6 +151,94 @@ impl ListSettingsWindow {
                             }
                         });
 
+                    let selected_version = if view_state.list_settings_version.is_empty() {
+                        state.get_effective_version()
+                    } else {
+                        view_state.list_settings_version.clone()
+                    };
+                    let selected_loader = if view_state.list_settings_loader.is_empty() {
+                        state.get_effective_loader()
+                    } else {
+                        view_state.list_settings_loader.clone()
+                    };
+                    // Pinned builds are only kept while the meta API still lists them for the version
+                    let mut available_loader_versions = None;
+                    if has_pinnable_loader_version(&selected_loader) {
+                        effects.extend(
+                            state.load_loader_versions_if_needed(&selected_version, &selected_loader),
+                        );
+                        let versions = state
+                            .loader_versions
+                            .get(&(selected_version.clone(), selected_loader.clone()));
+                        available_loader_versions = versions.cloned();
+
+                        // Unpinned Forge exports use the promoted recommended build
+                        let default_label = if selected_loader == "forge" {
+                            "Recommended"
+                        } else {
+                            "Latest"
+                        };
+
+                        ui.add_space(5.0);
+                        ui.label("Loader Version:");
+                        let pinned = view_state
+                            .list_settings_loader_version
+                            .get_or_insert(None);
+                        egui::ComboBox::from_id_salt("list_settings_loader_version")
+                            .selected_text(pinned.as_deref().unwrap_or(default_label))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(pinned, None, default_label);
+                                match versions {
+                                    Some(versions) => {
+                                        for version in versions {
+                                            ui.selectable_value(
+                                                pinned,
+                                                Some(version.clone()),
+                                                version,
+                                            );
+                                        }
+                                    }
+                                    None => {
+                                        ui.horizontal(|ui| {
+                                            ui.spinner();
+                                            ui.label("Loading...");
+                                        });
+                                    }
+                                }
+                            });
+
+                        if let (Some(pinned), Some(versions)) = (pinned.as_ref(), versions)
+                            && !versions.contains(pinned)
+                        {
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                format!(
+                                    "⚠ Loader {pinned} doesn't support Minecraft {selected_version}, \
+                                     saving resets it to {}",
+                                    default_label.to_lowercase()
+                                ),
+                            );
+                        }
+                    }
+
+                    ui.add_space(5.0);
+
+                    ui.label("Auto-select versions up to:");
+                    egui::ComboBox::from_id_salt("list_settings_channel")
+                        .selected_text(allowed_channel.display_name())
+                        .show_ui(ui, |ui| {
+                            for channel in ReleaseChannel::all() {
+                                ui.selectable_value(
+                                    allowed_channel,
+                                    channel,
+                                    channel.display_name(),
+                                );
+                            }
+                        })
+                        .response
+                        .on_hover_text("Less stable versions can still be pinned manually");
+                    let allowed_channel = *allowed_channel;
+
                     ui.add_space(5.0);
 
                     ui.label("Download Directory:");
@@ -126,6 +251,103 @@ impl ListSettingsWindow {
                         }
                     });
 
+                    let raw_dir = view_state.list_settings_dir.trim();
+                    let resolved_dir = crate::infra::resolve_path(raw_dir);
+                    match &resolved_dir {
+                        Ok(resolved) if resolved.as_path() != std::path::Path::new(raw_dir) => {
+                            ui.label(
+                                egui::RichText::new(format!("→ {}", resolved.display()))
+                                    .small()
+                                    .weak(),
+                            );
+                        }
+                        Err(e) if !raw_dir.is_empty() => {
+                            ui.colored_label(
+                                egui::Color32::YELLOW,
+                                format!("⚠ {e}, the default download folder is used"),
+                            );
+                        }
+                        _ => {}
+                    }
+                    ui.label(
+                        egui::RichText::new("Supports ~, ${MC_DIR} and environment variables")
+                            .small()
+                            .weak(),
+                    );
+
+                    // Resolving symlinks touches the disk, so it only runs when the input changes
+                    if view_state
+                        .list_settings_mods_overlap
+                        .as_ref()
+                        .is_none_or(|(checked, _)| checked != raw_dir)
+                    {
+                        let overlap = crate::infra::minecraft_mods_dir().filter(|mods_dir| {
+                            resolved_dir.as_ref().is_ok_and(|resolved| {
+                                crate::infra::paths_overlap(resolved, mods_dir)
+                            })
+                        });
+                        view_state.list_settings_mods_overlap =
+                            Some((raw_dir.to_string(), overlap));
+                    }
+
+                    if let Some((_, Some(mods_dir))) = &view_state.list_settings_mods_overlap {
+                        ui.colored_label(
+                            egui::Color32::LIGHT_RED,
+                            format!(
+                                "⚠ This overlaps the Minecraft mods folder ({}). Removing \
+                                 entries or deleting unknown files here changes your game \
+                                 install directly.",
+                                mods_dir.display()
+                            ),
+                        );
+                    }
+
+                    if let Some(folder) = content_type.server_folder() {
+                        let mut layout_toggle = server_layout;
+                        if ui
+                            .checkbox(&mut layout_toggle, "Server layout")
+                            .on_hover_text(format!("Download into the {folder} subfolder"))
+                            .changed()
+                        {
+                            view_state.list_settings_server_layout = Some(layout_toggle);
+                        }
+                    }
+
+                    if content_type == ProjectType::Mod {
+                        let mut mode_toggle = server_mode;
+                        if ui
+                            .checkbox(&mut mode_toggle, "Server mode")
+                            .on_hover_text("Skip client-only mods when downloading")
+                            .changed()
+                        {
+                            view_state.list_settings_server_mode = Some(mode_toggle);
+                        }
+                    }
+
+                    ui.add_space(5.0);
+
+                    ui.label("Notes:");
+                    if let Some(notes) = &mut view_state.list_settings_notes {
+                        ui.add(
+                            egui::TextEdit::multiline(notes)
+                                .hint_text("What is this list for?")
+                                .desired_rows(3)
+                                .desired_width(f32::INFINITY),
+                        );
+                    }
+
+                    ui.add_space(5.0);
+
+                    ui.label("Tags:");
+                    if let Some(tags) = &mut view_state.list_settings_tags {
+                        ui.add(
+                            egui::TextEdit::singleline(tags)
+                                .hint_text("e.g. survival, friends server")
+                                .desired_width(f32::INFINITY),
+                        )
+                        .on_hover_text("Comma separated, the first tag groups the list in the sidebar");
+                    }
+
                     ui.add_space(10.0);
 
                     if ui.button("💾 Save Settings").clicked() {
@@ -150,7 +372,7 @@ impl ListSettingsWindow {
                         let loader_obj = state
                             .mod_loaders
                             .iter()
-                            .find(|l| l.id == new_loader_id)
+                            .find(|l| l.matches(&new_loader_id))
                             .cloned()
                             .unwrap_or(crate::domain::ModLoader {
                                 id: new_loader_id.clone(),
@@ -161,6 +383,30 @@ impl ListSettingsWindow {
                         updated_list.version = new_version;
                         updated_list.loader = loader_obj;
                         updated_list.download_dir = new_dir;
+                        updated_list.include_snapshots = include_snapshots;
+                        updated_list.server_layout = server_layout;
+                        updated_list.server_mode = server_mode;
+                        updated_list.loader_version = if has_pinnable_loader_version(&new_loader_id)
+                        {
+                            view_state
+                                .list_settings_loader_version
+                                .take()
+                                .flatten()
+                                .filter(|pinned| {
+                                    available_loader_versions
+                                        .as_ref()
+                                        .is_none_or(|versions| versions.contains(pinned))
+                                })
+                        } else {
+                            None
+                        };
+                        updated_list.allowed_channel = allowed_channel;
+                        if let Some(notes) = view_state.list_settings_notes.take() {
+                            updated_list.notes = notes.trim().to_string();
+                        }
+                        if let Some(tags) = view_state.list_settings_tags.take() {
+                            updated_list.tags = ModList::parse_tags(&tags);
+                        }
 
                         if let Some(pos) =
                             state.mod_lists.iter().position(|l| l.id == updated_list.id)
@@ -168,7 +414,10 @@ impl ListSettingsWindow {
                             state.mod_lists[pos] = updated_list.clone();
                         }
 
-                        state.effective_settings_cache.clear();
+                        state.invalidate_effective_settings();
+                        if allowed_channel != previous_channel {
+                            state.forget_current_details();
+                        }
                         effects.extend(state.invalidate_and_reload());
 
                         effects.push(Effect::SaveList { list: updated_list });
@@ -0,0 +1,86 @@
+use crate::app::{AppState, Effect};
+use crate::ui::{LockedAction, ViewState};
+use eframe::egui;
+
+pub struct LockedActionWindow;
+
+impl LockedActionWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some((mod_id, action)) = view_state.confirm_locked_action.clone() else {
+            return effects;
+        };
+        let Some(mod_name) = state.get_current_list().and_then(|list| {
+            list.mods
+                .iter()
+                .find(|e| e.mod_id == mod_id)
+                .map(|e| e.mod_name.clone())
+        }) else {
+            view_state.confirm_locked_action = None;
+            return effects;
+        };
+
+        let overlay = egui::Area::new(egui::Id::new("locked_action_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let (question, confirm_text) = match action {
+            LockedAction::Update => (
+                format!("{mod_name} is locked. Replace the downloaded file anyway?"),
+                "🔄 Update Anyway",
+            ),
+            LockedAction::Delete => (
+                format!("{mod_name} is locked. Remove it from the list and delete its file?"),
+                "🗑 Delete Anyway",
+            ),
+        };
+
+        let mut is_open = true;
+        let mut should_confirm = false;
+        let mut should_cancel = false;
+
+        egui::Window::new("🔒 Locked Item")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(320.0);
+
+                ui.label(question);
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button(confirm_text).clicked() {
+                        should_confirm = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_confirm {
+            view_state.confirm_locked_action = None;
+            effects.extend(match action {
+                LockedAction::Update => state.start_download(&mod_id),
+                LockedAction::Delete => state.delete_mod(&mod_id),
+            });
+        } else if should_cancel || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.confirm_locked_action = None;
+        }
+
+        effects
+    }
+}
@@ -0,0 +1,85 @@
+use crate::app::{AppState, Effect};
+use crate::ui::explorer;
+use eframe::egui;
+
+pub struct LockfileWindow;
+
+impl LockfileWindow {
+    pub fn show(ctx: &egui::Context, state: &mut AppState) -> Vec<Effect> {
+        let effects = Vec::new();
+
+        let Some(install) = &state.lockfile_install else {
+            return effects;
+        };
+        let download_dir = install.download_dir.clone();
+        let (current, total, message) = install.progress.clone();
+        let result = install.result.clone();
+
+        let mut is_open = true;
+        let mut should_close = false;
+
+        egui::Window::new("🔐 Install Lockfile")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(download_dir.display().to_string()).weak());
+                ui.add_space(4.0);
+
+                let Some((installed, unchanged, errors)) = result else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(&message);
+                    });
+                    if total > 0 {
+                        ui.add(
+                            egui::ProgressBar::new(current as f32 / total as f32)
+                                .text(format!("{current}/{total}")),
+                        );
+                    }
+                    return;
+                };
+
+                ui.label(format!(
+                    "✅ {installed} installed, {unchanged} already up to date"
+                ));
+
+                if !errors.is_empty() {
+                    ui.add_space(4.0);
+                    ui.colored_label(
+                        egui::Color32::LIGHT_RED,
+                        format!(
+                            "❌ {} file(s) could not be reproduced, the folder doesn't match the lockfile",
+                            errors.len()
+                        ),
+                    );
+                    egui::ScrollArea::vertical()
+                        .max_height(250.0)
+                        .show(ui, |ui| {
+                            for error in &errors {
+                                ui.label(egui::RichText::new(error).small());
+                            }
+                        });
+                }
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("📂 Open Folder").clicked() {
+                        explorer::open_folder(&download_dir);
+                    }
+                    if ui.button("Close").clicked() {
+                        should_close = true;
+                    }
+                });
+            });
+
+        // Closing while running only hides the window, the install keeps going
+        if should_close || !is_open {
+            state.lockfile_install = None;
+        }
+
+        effects
+    }
+}
@@ -1,15 +1,47 @@
+mod alternatives_window;
+mod bulk_add_window;
+mod bundle_window;
+mod clean_up_orphans_window;
+mod collection_import_window;
+mod compatibility_window;
 mod create_list_window;
+mod delete_unknown_window;
+mod dependency_window;
+mod disk_space_window;
+mod download_history_window;
+mod export_overwrite_window;
 mod import_window;
 mod legacy_import_settings_window;
 mod legacy_window;
 mod list_settings_window;
+mod locked_action_window;
+mod lockfile_window;
+mod overrule_all_window;
+mod quit_window;
 mod search_window;
 mod settings_window;
+mod verify_window;
 
+pub use alternatives_window::AlternativesWindow;
+pub use bulk_add_window::BulkAddWindow;
+pub use bundle_window::BundleWindow;
+pub use clean_up_orphans_window::CleanUpOrphansWindow;
+pub use collection_import_window::CollectionImportWindow;
+pub use compatibility_window::CompatibilityWindow;
 pub use create_list_window::CreateListWindow;
+pub use delete_unknown_window::DeleteUnknownWindow;
+pub use dependency_window::DependencyWindow;
+pub use disk_space_window::DiskSpaceWindow;
+pub use download_history_window::DownloadHistoryWindow;
+pub use export_overwrite_window::ExportOverwriteWindow;
 pub use import_window::ImportWindow;
 pub use legacy_import_settings_window::LegacyImportSettingsWindow;
 pub use legacy_window::LegacyWindow;
 pub use list_settings_window::ListSettingsWindow;
+pub use locked_action_window::LockedActionWindow;
+pub use lockfile_window::LockfileWindow;
+pub use overrule_all_window::OverruleAllWindow;
+pub use quit_window::QuitWindow;
 pub use search_window::SearchWindow;
 pub use settings_window::SettingsWindow;
+pub use verify_window::VerifyWindow;
@@ -0,0 +1,90 @@
+use crate::app::{AppState, Effect};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct OverruleAllWindow;
+
+impl OverruleAllWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &mut AppState,
+        view_state: &mut ViewState,
+    ) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let overrulable_ids = state.get_overrulable_mod_ids();
+        let overrulable: Vec<String> = state
+            .get_current_list()
+            .map(|list| {
+                list.mods
+                    .iter()
+                    .filter(|e| overrulable_ids.contains(&e.mod_id))
+                    .map(|e| e.mod_name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if overrulable.is_empty() {
+            view_state.confirm_overrule_all = false;
+            return effects;
+        }
+
+        let overlay = egui::Area::new(egui::Id::new("overrule_all_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut is_open = true;
+        let mut should_overrule = false;
+        let mut should_cancel = false;
+
+        egui::Window::new("⚠ Overrule Incompatible Items")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                ui.set_min_width(320.0);
+
+                ui.label(format!(
+                    "Mark {} items as compatible even though their metadata says otherwise?",
+                    overrulable.len()
+                ));
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 165, 0),
+                    "They will be downloaded without version or loader checks.",
+                );
+                ui.add_space(8.0);
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for name in &overrulable {
+                            ui.label(egui::RichText::new(name).weak());
+                        }
+                    });
+
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("⚠ Overrule All").clicked() {
+                        should_overrule = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        should_cancel = true;
+                    }
+                });
+            });
+
+        if should_overrule {
+            view_state.confirm_overrule_all = false;
+            effects.extend(state.set_all_incompatible_overruled(true));
+        } else if should_cancel || !is_open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            view_state.confirm_overrule_all = false;
+        }
+
+        effects
+    }
+}
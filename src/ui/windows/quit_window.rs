@@ -0,0 +1,79 @@
+use crate::app::{AppRuntime, AppState};
+use crate::ui::ViewState;
+use eframe::egui;
+
+pub struct QuitWindow;
+
+impl QuitWindow {
+    pub fn show(
+        ctx: &egui::Context,
+        state: &AppState,
+        view_state: &mut ViewState,
+        runtime: &AppRuntime,
+    ) {
+        let active = state.active_download_count();
+
+        let overlay = egui::Area::new(egui::Id::new("quit_overlay"))
+            .order(egui::Order::Background)
+            .fixed_pos(egui::pos2(0.0, 0.0));
+
+        overlay.show(ctx, |ui| {
+            let screen_rect = ctx.content_rect();
+            ui.painter()
+                .rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(128));
+        });
+
+        let mut keep_running = false;
+        let mut quit_now = false;
+
+        egui::Window::new("⏏ Quit")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if view_state.quit_when_done {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(format!("Waiting for {active} download(s) to finish..."));
+                    });
+                } else {
+                    ui.label(format!("{active} download(s) still in progress."));
+                }
+                ui.label(
+                    egui::RichText::new(
+                        "Partly downloaded files are kept and resumed next time, \
+                         only verified files are ever saved under their final name.",
+                    )
+                    .weak(),
+                );
+
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if !view_state.quit_when_done && ui.button("⏳ Wait and Quit").clicked() {
+                        view_state.quit_when_done = true;
+                    }
+                    if ui.button("⏹ Cancel Downloads and Quit").clicked() {
+                        runtime.cancel_downloads(true);
+                        view_state.quit_when_done = true;
+                    }
+                    if ui.button("Quit Anyway").clicked() {
+                        quit_now = true;
+                    }
+                    if ui.button("Keep Running").clicked() {
+                        keep_running = true;
+                    }
+                });
+            });
+
+        if quit_now {
+            view_state.quit_confirmed = true;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+
+        if keep_running {
+            runtime.cancel_downloads(false);
+            view_state.quit_prompt_open = false;
+            view_state.quit_when_done = false;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::app::{AppRuntime, AppState, Effect};
+use crate::domain::{ProviderKind, SearchSort};
 use crate::ui::ViewState;
 use eframe::egui;
 
@@ -39,10 +40,14 @@ impl SearchWindow {
             }
         });
 
+        effects.extend(state.load_search_categories());
+
         let current_type = state.get_current_list_type();
         let mut is_open = view_state.search_window_open;
+        let mut filters_changed = false;
         let mut mod_to_add = None;
         let mut should_close_window = false;
+        let mut selection_moved = None;
 
         egui::Window::new(format!("🔍 Search {}", current_type.display_name()))
             .collapsible(false)
@@ -58,21 +63,139 @@ impl SearchWindow {
                             .desired_width(400.0),
                     );
 
+                    if view_state.search_focus_requested {
+                        view_state.search_focus_requested = false;
+                        query_response.request_focus();
+                    }
+
                     if query_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))
                     {
                         view_state.is_searching = true;
-                        effects.extend(state.perform_search(&view_state.search_window_query));
+                        view_state.search_selected_index = None;
+                        effects
+                            .extend(state.perform_search(&view_state.search_window_query, false));
+                    } else if let Some(index) = Self::handle_result_keys(
+                        ctx,
+                        view_state,
+                        state.search_window_results.len(),
+                        query_response.has_focus(),
+                    ) {
+                        // Leave the query field so the next Enter adds instead of searching
+                        query_response.surrender_focus();
+                        selection_moved = Some(index);
                     }
 
+                    egui::ComboBox::from_id_salt("search_provider_selector")
+                        .selected_text(state.search_provider.display_name())
+                        .show_ui(ui, |ui| {
+                            for provider in &[ProviderKind::Modrinth, ProviderKind::CurseForge] {
+                                if ui
+                                    .selectable_value(
+                                        &mut state.search_provider,
+                                        *provider,
+                                        provider.display_name(),
+                                    )
+                                    .changed()
+                                {
+                                    state.search_categories.clear();
+                                    filters_changed = true;
+                                }
+                            }
+                        });
+
                     ui.checkbox(&mut state.search_filter_exact, "Match version/loader");
 
+                    egui::ComboBox::from_id_salt("search_sort_selector")
+                        .selected_text(format!("↕ {}", state.search_sort.display_name()))
+                        .show_ui(ui, |ui| {
+                            for sort in SearchSort::all() {
+                                if ui
+                                    .selectable_value(
+                                        &mut state.search_sort,
+                                        sort,
+                                        sort.display_name(),
+                                    )
+                                    .changed()
+                                {
+                                    filters_changed = true;
+                                }
+                            }
+                        });
+
+                    let category_label = if state.search_categories.is_empty() {
+                        "🏷 Categories".to_string()
+                    } else {
+                        format!("🏷 Categories ({})", state.search_categories.len())
+                    };
+                    ui.menu_button(category_label, |ui| {
+                        let Some(categories) = state.get_search_categories().cloned() else {
+                            ui.spinner();
+                            return;
+                        };
+                        if categories.is_empty() {
+                            ui.label("No categories available");
+                            return;
+                        }
+
+                        if ui
+                            .add_enabled(
+                                !state.search_categories.is_empty(),
+                                egui::Button::new("Clear all"),
+                            )
+                            .clicked()
+                        {
+                            state.search_categories.clear();
+                            filters_changed = true;
+                        }
+                        ui.separator();
+
+                        egui::ScrollArea::vertical()
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                for category in &categories {
+                                    let mut selected =
+                                        state.search_categories.contains(&category.id);
+                                    if ui.checkbox(&mut selected, &category.name).changed() {
+                                        if selected {
+                                            state.search_categories.insert(category.id.clone());
+                                        } else {
+                                            state.search_categories.remove(&category.id);
+                                        }
+                                        filters_changed = true;
+                                    }
+                                }
+                            });
+                    });
+
                     if ui.button("Search").clicked() {
                         view_state.is_searching = true;
-                        effects.extend(state.perform_search(&view_state.search_window_query));
+                        view_state.search_selected_index = None;
+                        effects
+                            .extend(state.perform_search(&view_state.search_window_query, false));
+                    }
+                    if ui
+                        .add_enabled(
+                            !view_state.search_window_query.is_empty(),
+                            egui::Button::new("⟳"),
+                        )
+                        .on_hover_text("Search again without using cached results")
+                        .clicked()
+                    {
+                        state.search_window_results.clear();
+                        view_state.is_searching = true;
+                        view_state.search_selected_index = None;
+                        effects.extend(state.perform_search(&view_state.search_window_query, true));
                     }
                 });
                 ui.separator();
 
+                if filters_changed && !view_state.search_window_query.is_empty() {
+                    state.search_window_results.clear();
+                    view_state.search_selected_index = None;
+                    view_state.is_searching = true;
+                    effects.extend(state.perform_search(&view_state.search_window_query, false));
+                }
+
                 if !state.search_window_results.is_empty()
                     || view_state.search_window_query.is_empty()
                 {
@@ -91,61 +214,98 @@ impl SearchWindow {
                     } else if state.search_window_results.is_empty() {
                         ui.label("Enter a search query");
                     } else {
-                        for mod_info in &state.search_window_results {
-                            ui.horizontal(|ui| {
-                                if !mod_info.icon_url.is_empty() {
-                                    if let Some(handle) =
-                                        runtime.icon_service.get(&mod_info.icon_url)
-                                    {
-                                        ui.add(
-                                            egui::Image::from_texture(handle)
-                                                .fit_to_exact_size(egui::vec2(32.0, 32.0)),
-                                        );
+                        let current_list = state.get_current_list();
+                        let add_selected = view_state.search_selected_index.is_some()
+                            && !ctx.wants_keyboard_input()
+                            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                        for (index, mod_info) in state.search_window_results.iter().enumerate() {
+                            let already_added =
+                                current_list.is_some_and(|list| list.contains_mod(&mod_info.id));
+                            let is_selected = view_state.search_selected_index == Some(index);
+
+                            if is_selected && add_selected && !already_added {
+                                mod_to_add = Some(mod_info.clone());
+                                should_close_window = true;
+                            }
+
+                            let row_fill = if is_selected {
+                                ui.visuals().selection.bg_fill.gamma_multiply(0.3)
+                            } else {
+                                egui::Color32::TRANSPARENT
+                            };
+
+                            let row = egui::Frame::NONE.fill(row_fill).show(ui, |ui| {
+                                ui.horizontal(|ui| {
+                                    if already_added {
+                                        ui.multiply_opacity(0.6);
+                                    }
+
+                                    if !mod_info.icon_url.is_empty() {
+                                        if let Some(handle) =
+                                            runtime.icon_service.get(&mod_info.icon_url)
+                                        {
+                                            ui.add(
+                                                egui::Image::from_texture(handle)
+                                                    .fit_to_exact_size(egui::vec2(32.0, 32.0)),
+                                            );
+                                        } else {
+                                            ui.add_sized(
+                                                egui::vec2(32.0, 32.0),
+                                                egui::Spinner::new(),
+                                            );
+                                        }
                                     } else {
-                                        ui.add_sized(egui::vec2(32.0, 32.0), egui::Spinner::new());
+                                        ui.add_space(32.0);
                                     }
-                                } else {
-                                    ui.add_space(32.0);
-                                }
-                                ui.add_space(4.0);
-
-                                let button_width = 50.0;
-                                let spacing = 8.0;
-                                let available_width = ui.available_width() - button_width - spacing;
-
-                                ui.vertical(|ui| {
-                                    ui.set_max_width(available_width);
-                                    let project_link = runtime
-                                        .get_project_link(&mod_info.project_type, &mod_info.id);
-                                    ui.hyperlink_to(&mod_info.name, project_link);
-                                    ui.add(
-                                        egui::Label::new(&mod_info.description)
-                                            .wrap_mode(egui::TextWrapMode::Wrap),
+                                    ui.add_space(4.0);
+
+                                    let button_width = if already_added { 70.0 } else { 50.0 };
+                                    let spacing = 8.0;
+                                    let available_width =
+                                        ui.available_width() - button_width - spacing;
+
+                                    ui.vertical(|ui| {
+                                        ui.set_max_width(available_width);
+                                        let project_link = runtime.get_project_link(
+                                            mod_info.provider,
+                                            &mod_info.project_type,
+                                            &mod_info.id,
+                                        );
+                                        ui.hyperlink_to(&mod_info.name, project_link);
+                                        ui.add(
+                                            egui::Label::new(&mod_info.description)
+                                                .wrap_mode(egui::TextWrapMode::Wrap),
+                                        );
+                                        ui.label(format!(
+                                            "👤 {} | ⬇ {}",
+                                            mod_info.author, mod_info.download_count
+                                        ));
+                                    });
+
+                                    ui.with_layout(
+                                        egui::Layout::right_to_left(egui::Align::Center),
+                                        |ui| {
+                                            if already_added {
+                                                ui.add_enabled(false, egui::Button::new("✓ Added"));
+                                            } else if ui.button("Add").clicked() {
+                                                mod_to_add = Some(mod_info.clone());
+                                                should_close_window = true;
+                                            }
+                                        },
                                     );
-                                    ui.label(format!(
-                                        "👤 {} | ⬇ {}",
-                                        mod_info.author, mod_info.download_count
-                                    ));
                                 });
-
-                                ui.with_layout(
-                                    egui::Layout::right_to_left(egui::Align::Center),
-                                    |ui| {
-                                        if ui.button("Add").clicked() {
-                                            mod_to_add = Some(mod_info.clone());
-                                            should_close_window = true;
-                                        }
-                                    },
-                                );
                             });
+                            if selection_moved == Some(index) {
+                                row.response.scroll_to_me(None);
+                            }
                             ui.separator();
                         }
                     }
                 });
 
                 if let Some(mod_info) = mod_to_add {
-                    effects.extend(state.add_mod_to_current_list(mod_info.clone()));
-                    effects.extend(state.load_mod_details_if_needed(&mod_info.id));
+                    effects.extend(state.add_mod_to_current_list(mod_info));
                 }
             });
 
@@ -159,10 +319,51 @@ impl SearchWindow {
 
         if !view_state.search_window_open {
             view_state.search_window_query.clear();
+            view_state.search_selected_index = None;
             view_state.is_searching = false;
             state.search_window_results.clear();
         }
 
         effects
     }
+
+    // Moves the result highlight with the arrow keys, and Home/End outside the query field.
+    // Returns the new index when the selection changed.
+    fn handle_result_keys(
+        ctx: &egui::Context,
+        view_state: &mut ViewState,
+        result_count: usize,
+        query_focused: bool,
+    ) -> Option<usize> {
+        if result_count == 0 {
+            return None;
+        }
+
+        let jumps_allowed = !query_focused && !ctx.wants_keyboard_input();
+        let (down, up, home, end) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                jumps_allowed && i.consume_key(egui::Modifiers::NONE, egui::Key::Home),
+                jumps_allowed && i.consume_key(egui::Modifiers::NONE, egui::Key::End),
+            )
+        });
+
+        let last = result_count - 1;
+        let current = view_state.search_selected_index.map(|i| i.min(last));
+        let next = if home {
+            0
+        } else if end {
+            last
+        } else if down {
+            current.map_or(0, |i| (i + 1).min(last))
+        } else if up {
+            current.map_or(0, |i| i.saturating_sub(1))
+        } else {
+            return None;
+        };
+
+        view_state.search_selected_index = Some(next);
+        Some(next)
+    }
 }
@@ -1,5 +1,12 @@
 use crate::app::{AppState, Effect};
-use crate::ui::ViewState;
+use crate::domain::{
+    CompatPolicy, DEFAULT_MODRINTH_API_URL, DEFAULT_USER_AGENT_CONTACT, MAX_CACHE_TTL_HOURS,
+    MAX_CONNECT_TIMEOUT_SECS, MAX_DOWNLOAD_RETRIES, MAX_PARALLEL_DOWNLOADS_LIMIT,
+    MAX_STALL_TIMEOUT_SECS, MAX_UPDATE_CHECK_HOURS, ProjectType, Theme, user_agent,
+    validate_api_url, validate_user_agent_contact,
+};
+use crate::infra::build_client;
+use crate::ui::{ViewState, dialogs::Dialogs};
 use eframe::egui;
 
 pub struct SettingsWindow;
@@ -14,6 +21,22 @@ impl SettingsWindow {
 
         if view_state.app_settings_default_name.is_empty() && view_state.settings_window_open {
             view_state.app_settings_default_name = state.default_list_name.clone();
+            view_state.app_settings_curseforge_key = state.curseforge_api_key.clone();
+            view_state.app_settings_modrinth_url = state.modrinth_api_url.clone();
+            view_state.app_settings_modrinth_url_error = None;
+            view_state.app_settings_user_agent_contact = state.user_agent_contact.clone();
+            view_state.app_settings_user_agent_error = None;
+            view_state.app_settings_proxy = state.proxy.clone();
+            view_state.app_settings_timeouts = state.timeouts;
+            view_state.app_settings_proxy_error = None;
+            view_state.app_settings_max_downloads = state.max_parallel_downloads;
+            view_state.app_settings_download_retries = state.download_retries;
+            view_state.app_settings_update_check_hours = state.update_check_hours;
+            view_state.app_settings_clean_replaced = state.clean_replaced_files;
+            view_state.app_settings_compat_policy = state.compat_policy;
+            view_state.app_settings_cache_ttl = state.cache_ttl;
+            view_state.app_settings_default_dirs = state.default_dirs.clone();
+            view_state.app_settings_theme = state.theme;
         }
 
         let overlay = egui::Area::new(egui::Id::new("settings_overlay"))
@@ -54,14 +77,338 @@ impl SettingsWindow {
                 ui.label("Default list name:");
                 ui.text_edit_singleline(&mut view_state.app_settings_default_name);
 
+                ui.add_space(5.0);
+
+                ui.label("Theme:");
+                egui::ComboBox::from_id_salt("settings_theme")
+                    .selected_text(view_state.app_settings_theme.display_name())
+                    .show_ui(ui, |ui| {
+                        for theme in Theme::all() {
+                            ui.selectable_value(
+                                &mut view_state.app_settings_theme,
+                                theme,
+                                theme.display_name(),
+                            );
+                        }
+                    });
+
+                ui.add_space(5.0);
+
+                ui.label("CurseForge API key:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut view_state.app_settings_curseforge_key)
+                        .password(true)
+                        .hint_text("Required to search CurseForge"),
+                );
+
+                ui.add_space(5.0);
+
+                ui.label("Modrinth API URL:");
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut view_state.app_settings_modrinth_url)
+                            .hint_text(DEFAULT_MODRINTH_API_URL),
+                    )
+                    .on_hover_text("For Modrinth compatible mirrors or proxies, leave empty for the official API")
+                    .changed()
+                {
+                    view_state.app_settings_modrinth_url_error = None;
+                }
+                if let Some(error) = &view_state.app_settings_modrinth_url_error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                ui.add_space(5.0);
+
+                ui.label("Contact sent to APIs:");
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut view_state.app_settings_user_agent_contact)
+                            .hint_text(DEFAULT_USER_AGENT_CONTACT),
+                    )
+                    .on_hover_text(format!(
+                        "Part of the User-Agent so API operators can reach you instead of blocking, \
+                         for example an email address. Sent as: {}",
+                        user_agent(&view_state.app_settings_user_agent_contact)
+                    ))
+                    .changed()
+                {
+                    view_state.app_settings_user_agent_error = None;
+                }
+                if let Some(error) = &view_state.app_settings_user_agent_error {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                ui.add_space(5.0);
+
+                ui.collapsing("Proxy", |ui| {
+                    let mut changed = false;
+                    egui::Grid::new("proxy_grid").num_columns(2).show(ui, |ui| {
+                        ui.label("HTTP proxy:");
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut view_state.app_settings_proxy.http)
+                                    .hint_text("From HTTP_PROXY"),
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("HTTPS proxy:");
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut view_state.app_settings_proxy.https)
+                                    .hint_text("From HTTPS_PROXY"),
+                            )
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("No proxy for:");
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(
+                                    &mut view_state.app_settings_proxy.no_proxy,
+                                )
+                                .hint_text("localhost, .example.com"),
+                            )
+                            .on_hover_text("Comma separated hosts or domains that bypass the proxy")
+                            .changed();
+                        ui.end_row();
+                    });
+                    if changed {
+                        view_state.app_settings_proxy_error = None;
+                    }
+                    ui.weak("Empty fields use the system environment variables");
+                });
+                if let Some(error) = view_state
+                    .app_settings_proxy_error
+                    .as_ref()
+                    .or(state.http_client_error.as_ref())
+                {
+                    ui.colored_label(egui::Color32::LIGHT_RED, error);
+                }
+
+                ui.add_space(5.0);
+
+                ui.label("Max simultaneous downloads:");
+                ui.add(egui::Slider::new(
+                    &mut view_state.app_settings_max_downloads,
+                    1..=MAX_PARALLEL_DOWNLOADS_LIMIT,
+                ));
+
+                ui.add_space(5.0);
+
+                ui.label("Automatic download retries:");
+                ui.add(egui::Slider::new(
+                    &mut view_state.app_settings_download_retries,
+                    0..=MAX_DOWNLOAD_RETRIES,
+                ))
+                .on_hover_text("Retries timeouts and server errors with increasing delays");
+
+                ui.add_space(5.0);
+
+                ui.label("Check for updates in the background every:");
+                ui.add(
+                    egui::Slider::new(
+                        &mut view_state.app_settings_update_check_hours,
+                        0..=MAX_UPDATE_CHECK_HOURS,
+                    )
+                    .suffix(" h"),
+                )
+                .on_hover_text("Looks for newer versions of installed items in all lists, 0 turns it off");
+
+                ui.add_space(5.0);
+
+                ui.checkbox(
+                    &mut view_state.app_settings_clean_replaced,
+                    "Delete old versions on update",
+                )
+                .on_hover_text(
+                    "Removes the file an update replaces. Only files this app downloaded are \
+                     deleted, never other files in the folder.",
+                );
+
+                ui.add_space(5.0);
+
+                ui.label("Show mods as compatible with:");
+                egui::ComboBox::from_id_salt("settings_compat_policy")
+                    .selected_text(view_state.app_settings_compat_policy.display_name())
+                    .show_ui(ui, |ui| {
+                        for policy in CompatPolicy::all() {
+                            ui.selectable_value(
+                                &mut view_state.app_settings_compat_policy,
+                                policy,
+                                policy.display_name(),
+                            );
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Looser matching marks a mod usable when it only lists a nearby \
+                         Minecraft version. It may still fail to load.",
+                    );
+
+                ui.add_space(5.0);
+
+                ui.collapsing("Default download folders", |ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            "Used for new lists of each type, existing lists keep their folder",
+                        )
+                        .weak(),
+                    );
+                    let fallback = state.default_dir_fallback();
+                    egui::Grid::new("default_dirs_grid")
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            for project_type in ProjectType::all() {
+                                ui.label(project_type.display_name());
+                                let dir = view_state
+                                    .app_settings_default_dirs
+                                    .entry(project_type)
+                                    .or_default();
+                                ui.add(
+                                    egui::TextEdit::singleline(dir)
+                                        .hint_text(fallback.as_str())
+                                        .desired_width(220.0),
+                                );
+                                if ui.button("Browse...").clicked()
+                                    && let Some(path) = Dialogs::pick_folder()
+                                {
+                                    *dir = path.to_string_lossy().to_string();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(5.0);
+
+                ui.collapsing("Timeouts", |ui| {
+                    egui::Grid::new("timeouts_grid")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("Connect:");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut view_state.app_settings_timeouts.connect_secs,
+                                    1..=MAX_CONNECT_TIMEOUT_SECS,
+                                )
+                                .suffix(" s"),
+                            )
+                            .on_hover_text("How long to wait for a server to accept the connection");
+                            ui.end_row();
+
+                            ui.label("Stall:");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut view_state.app_settings_timeouts.stall_secs,
+                                    5..=MAX_STALL_TIMEOUT_SECS,
+                                )
+                                .suffix(" s"),
+                            )
+                            .on_hover_text(
+                                "Aborts a transfer only when no data arrives for this long. \
+                                 Slow downloads that keep making progress are never cut off.",
+                            );
+                            ui.end_row();
+                        });
+                });
+
+                ui.add_space(5.0);
+
+                ui.collapsing("Metadata cache lifetime", |ui| {
+                    egui::Grid::new("cache_ttl_grid")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            for project_type in ProjectType::all() {
+                                ui.label(project_type.display_name());
+                                ui.add(
+                                    egui::Slider::new(
+                                        view_state.app_settings_cache_ttl.ttl_for_mut(project_type),
+                                        1..=MAX_CACHE_TTL_HOURS,
+                                    )
+                                    .logarithmic(true)
+                                    .suffix(" h"),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !state.is_refreshing_game_data(),
+                            egui::Button::new("🔄 Refresh versions & loaders"),
+                        )
+                        .on_hover_text("Versions and loaders are cached for 24 hours")
+                        .clicked()
+                    {
+                        effects.extend(state.refresh_game_data());
+                    }
+                    if state.is_refreshing_game_data() {
+                        ui.spinner();
+                    }
+                });
+
                 ui.add_space(10.0);
 
                 if ui.button("💾 Save Settings").clicked() {
+                    // Nothing is applied until every field is valid
+                    let modrinth_api_url =
+                        match validate_api_url(&view_state.app_settings_modrinth_url) {
+                            Ok(url) => url,
+                            Err(e) => {
+                                view_state.app_settings_modrinth_url_error = Some(e);
+                                return;
+                            }
+                        };
+                    let user_agent_contact = match validate_user_agent_contact(
+                        &view_state.app_settings_user_agent_contact,
+                    ) {
+                        Ok(contact) => contact,
+                        Err(e) => {
+                            view_state.app_settings_user_agent_error = Some(e);
+                            return;
+                        }
+                    };
+                    if let Err(e) = build_client(
+                        &view_state.app_settings_proxy,
+                        view_state.app_settings_timeouts,
+                        &user_agent_contact,
+                    ) {
+                        view_state.app_settings_proxy_error = Some(format!("{e:#}"));
+                        return;
+                    }
+                    state.modrinth_api_url = modrinth_api_url;
+                    state.user_agent_contact = user_agent_contact;
+                    state.proxy = view_state.app_settings_proxy.clone();
+                    state.timeouts = view_state.app_settings_timeouts;
                     state.default_list_name = view_state.app_settings_default_name.clone();
+                    state.curseforge_api_key =
+                        view_state.app_settings_curseforge_key.trim().to_string();
+                    state.max_parallel_downloads = view_state.app_settings_max_downloads;
+                    state.download_retries = view_state.app_settings_download_retries;
+                    state.update_check_hours = view_state.app_settings_update_check_hours;
+                    state.clean_replaced_files = view_state.app_settings_clean_replaced;
+                    state.set_compat_policy(view_state.app_settings_compat_policy);
+                    state.default_dirs = view_state
+                        .app_settings_default_dirs
+                        .iter()
+                        .filter(|(_, dir)| !dir.trim().is_empty())
+                        .map(|(project_type, dir)| (*project_type, dir.trim().to_string()))
+                        .collect();
+                    state.theme = view_state.app_settings_theme;
+                    crate::ui::apply_theme(ctx, state.theme);
+                    let cache_ttl = view_state.app_settings_cache_ttl;
+                    let reload = state.set_cache_ttl(cache_ttl);
                     effects.push(Effect::SaveConfig {
-                        current_list_id: state.current_list_id.clone(),
-                        default_list_name: state.default_list_name.clone(),
+                        config: state.app_config(),
                     });
+                    // Queued after SaveConfig, which hands the new lifetime to the service
+                    effects.extend(reload);
                     should_close = true;
                 }
             });
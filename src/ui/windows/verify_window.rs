@@ -0,0 +1,138 @@
+use crate::app::{AppState, Effect};
+use crate::infra::VerifyStatus;
+use eframe::egui;
+
+pub struct VerifyWindow;
+
+impl VerifyWindow {
+    pub fn show(ctx: &egui::Context, state: &mut AppState) -> Vec<Effect> {
+        let mut effects = Vec::new();
+
+        let Some(verification) = &state.verification else {
+            return effects;
+        };
+        let results = verification.results.clone();
+        let full = verification.full;
+        let (checked, total) = verification.progress;
+
+        let mut is_open = true;
+        let mut redownload = None;
+        let mut run_full = false;
+
+        egui::Window::new("🛡 Verify Downloads")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .open(&mut is_open)
+            .show(ctx, |ui| {
+                let Some(results) = results else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        if full && total > 0 {
+                            ui.label(format!("Verifying files {checked}/{total}..."));
+                        } else {
+                            ui.label("Checking downloaded files...");
+                        }
+                    });
+                    if full && total > 0 {
+                        ui.add(egui::ProgressBar::new(checked as f32 / total as f32));
+                    }
+                    return;
+                };
+
+                if results.is_empty() {
+                    ui.label("No downloaded files to check.");
+                    return;
+                }
+
+                let broken: Vec<String> = results
+                    .iter()
+                    .filter(|r| {
+                        matches!(r.status, VerifyStatus::HashMismatch | VerifyStatus::Missing)
+                    })
+                    .map(|r| r.mod_id.clone())
+                    .collect();
+                let ok_count = results
+                    .iter()
+                    .filter(|r| r.status == VerifyStatus::Ok)
+                    .count();
+                let unverified_count = results
+                    .iter()
+                    .filter(|r| r.status == VerifyStatus::Unverified)
+                    .count();
+
+                if full {
+                    ui.label(format!(
+                        "{ok_count} intact, {} broken, {unverified_count} without a recorded hash",
+                        broken.len()
+                    ));
+                } else {
+                    ui.label(format!("All {ok_count} downloaded files are present."));
+                    if ui
+                        .button("🔍 Check hashes")
+                        .on_hover_text("Hash every file to find modified or corrupted downloads")
+                        .clicked()
+                    {
+                        run_full = true;
+                    }
+                }
+                ui.add_space(4.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for result in results.iter().filter(|r| r.status != VerifyStatus::Ok) {
+                            ui.horizontal(|ui| {
+                                let (color, text, hint) = match result.status {
+                                    VerifyStatus::HashMismatch => (
+                                        egui::Color32::LIGHT_RED,
+                                        "✖ Modified",
+                                        "File contents don't match the downloaded version",
+                                    ),
+                                    VerifyStatus::Missing => (
+                                        egui::Color32::LIGHT_RED,
+                                        "✖ Missing",
+                                        "File was removed from the download directory",
+                                    ),
+                                    VerifyStatus::Unverified => (
+                                        egui::Color32::GRAY,
+                                        "? Unverified",
+                                        "Downloaded before hashes were recorded",
+                                    ),
+                                    VerifyStatus::Ok => unreachable!(),
+                                };
+                                ui.colored_label(color, text).on_hover_text(hint);
+                                ui.strong(&result.mod_name);
+                                ui.label(egui::RichText::new(&result.file).small().weak());
+                            });
+                        }
+                    });
+
+                if !broken.is_empty() {
+                    ui.add_space(8.0);
+                    if ui
+                        .add_enabled(
+                            !state.is_offline,
+                            egui::Button::new(format!("⬇ Re-download broken ({})", broken.len())),
+                        )
+                        .on_disabled_hover_text("Downloads are unavailable while offline")
+                        .clicked()
+                    {
+                        redownload = Some(broken);
+                    }
+                }
+            });
+
+        if run_full {
+            effects.extend(state.verify_current_list(true));
+        } else if let Some(mod_ids) = redownload {
+            effects.extend(state.start_bulk_download(mod_ids));
+            state.verification = None;
+        } else if !is_open {
+            state.verification = None;
+        }
+
+        effects
+    }
+}